@@ -0,0 +1,331 @@
+/*
+ * Runs solutions through the registry:
+ *
+ *     cargo run -p aoc-cli --bin aoc22 -- --day 14 --part 2
+ *     cat input.txt | cargo run -p aoc-cli --bin aoc22 -- --day 14 --part 2 --input -
+ *
+ * With --progress, the slow solutions (day 15 part 2, day 16 part 2 and
+ * day 19) show how far along they are on stderr. With --timeout (30s,
+ * 500ms, 2m, or plain seconds), a run that takes too long is abandoned:
+ * days 16, 19 and 24 notice the cancellation and stop searching, and the
+ * rest are left running in the background while the process exits.
+ *
+ * Without --input, this reads input/2022/dayN.txt (from the repository
+ * root, where cargo runs it), which is where `cargo aoc input` saves the
+ * puzzle input. Overrides from aoc.toml (see the solutions' src/config.rs)
+ * are applied, same as everywhere else.
+ *
+ * With --verbose, the days that have more to say than the answer print it
+ * to stderr first: so far that's day 7, which shows the directory tree with
+ * each directory's total size, then every directory from largest down.
+ *
+ * With --year, this runs a later year's days instead, the ones new-day has
+ * scaffolded and registered, reading input/<year>/dayN.txt.
+ *
+ * With --cache, the parsed inputs for the slowest generators (days 16 and
+ * 22) are saved under target/aoc-cache and reused on later runs with the
+ * same input; see the solutions' src/cache.rs. This needs the `serde`
+ * feature.
+ *
+ * There are also two subcommands for tracking performance (see
+ * src/timings.rs; the JSON needs the `serde` feature, and with the
+ * `alloc-metrics` feature run-all also counts each solution's allocations):
+ *
+ *     aoc22 run-all [--json report.json] [--runs N]
+ *     aoc22 bench --baseline old.json --current new.json [--threshold 0.1]
+ */
+use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+use std::{env, process, thread};
+
+use advent_of_code_2022::config::{self, Config};
+use advent_of_code_2022::y2022::day7;
+use advent_of_code_2022::error::AocError;
+use advent_of_code_2022::registry;
+use advent_of_code_2022::utils::cancel::CancelToken;
+use advent_of_code_2022::utils::input::read_input;
+use advent_of_code_2022::utils::progress::{NoProgress, Progress};
+#[cfg(feature = "serde")]
+use aoc_cli::timings;
+use aoc_cli::timings::TimingReport;
+
+const USAGE: &str =
+    "usage: aoc22 [--year <year>] --day <1-25> --part <1-2> [--input <path, or - for stdin>]
+                  [--progress] [--verbose] [--timeout <30s, 500ms, 2m>] [--cache]
+       aoc22 run-all [--year <year>] [--json <report.json>] [--runs <n>] [--cache]
+       aoc22 bench --baseline <old.json> --current <new.json> [--threshold <fraction>]";
+
+/**
+ * Parses "--flag value" pairs, where `switches` are the flags that don't
+ * take a value. Returns None for anything else.
+ */
+fn parse_flags(args: &[String], switches: &[&str]) -> Option<Vec<(String, String)>> {
+    let mut flags = Vec::new();
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        if !flag.starts_with("--") {
+            return None;
+        }
+        let value = if switches.contains(&flag.as_str()) {
+            String::new()
+        } else {
+            args.next()?.clone()
+        };
+        flags.push((flag.clone(), value));
+    }
+    Some(flags)
+}
+
+fn flag<'a>(flags: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    flags
+        .iter()
+        .find(|(flag, _)| flag == name)
+        .map(|(_, value)| value.as_str())
+}
+
+fn usage() -> ! {
+    eprintln!("{USAGE}");
+    process::exit(2);
+}
+
+fn fail(message: impl std::fmt::Display) -> ! {
+    eprintln!("error: {message}");
+    process::exit(1);
+}
+
+/**
+ * The year given with --year, 2022 by default.
+ */
+fn year(flags: &[(String, String)]) -> u16 {
+    match flag(flags, "--year").map(str::parse::<u16>) {
+        None => 2022,
+        Some(Ok(year)) => year,
+        Some(Err(_)) => usage(),
+    }
+}
+
+fn load_config() -> Config {
+    Config::load(config::DEFAULT_PATH)
+        .unwrap_or_else(|err| fail(format!("in {}: {err}", config::DEFAULT_PATH)))
+}
+
+/**
+ * Turns on the parsed-input cache if --cache was given.
+ */
+fn enable_cache(flags: &[(String, String)]) {
+    if flag(flags, "--cache").is_none() {
+        return;
+    }
+    #[cfg(feature = "serde")]
+    advent_of_code_2022::cache::enable();
+    #[cfg(not(feature = "serde"))]
+    fail("--cache needs the serde feature");
+}
+
+/**
+ * Prints progress updates on a single line of stderr, redrawing it only
+ * when the percentage changes.
+ */
+fn print_progress(what: &str, done: usize, total: usize) {
+    static LAST_PERCENT: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+    let percent = done * 100 / total.max(1);
+    if LAST_PERCENT.swap(percent, Ordering::Relaxed) == percent {
+        return;
+    }
+    let mut stderr = std::io::stderr().lock();
+    let _ = write!(stderr, "\r{what}: {done}/{total} ({percent}%)");
+    if done == total {
+        let _ = writeln!(stderr);
+    }
+}
+
+/**
+ * The --verbose output for the days that have any. Input the day can't
+ * parse is left for the solution itself to report.
+ */
+fn print_details(year: u16, day: u8, input: &str) {
+    if let (2022, 7) = (year, day) {
+        let Ok(root) = day7::generator(input) else {
+            return;
+        };
+        eprint!("{}", day7::render_tree(&root));
+        eprintln!();
+        for (path, size) in day7::largest_directories(&root) {
+            eprintln!("{size:>12}  {path}");
+        }
+    }
+}
+
+/**
+ * Parses a timeout like "30s", "500ms" or "2m". A bare number is seconds.
+ */
+fn parse_timeout(text: &str) -> Option<Duration> {
+    let (number, unit) = match text.find(|c: char| !c.is_ascii_digit()) {
+        Some(split) => text.split_at(split),
+        None => (text, "s"),
+    };
+    let number: u64 = number.parse().ok()?;
+    match unit {
+        "ms" => Some(Duration::from_millis(number)),
+        "s" => Some(Duration::from_secs(number)),
+        "m" => Some(Duration::from_secs(number * 60)),
+        _ => None,
+    }
+}
+
+fn run_one(args: &[String]) {
+    let flags =
+        parse_flags(args, &["--progress", "--verbose", "--cache"]).unwrap_or_else(|| usage());
+    enable_cache(&flags);
+    let timeout = match flag(&flags, "--timeout").map(parse_timeout) {
+        None => None,
+        Some(Some(timeout)) => Some(timeout),
+        Some(None) => usage(),
+    };
+    let (Some(Ok(day)), Some(Ok(part))) = (
+        flag(&flags, "--day").map(str::parse::<u8>),
+        flag(&flags, "--part").map(str::parse::<u8>),
+    ) else {
+        usage();
+    };
+
+    let year = year(&flags);
+
+    let Some(solution) = registry::find_in(year, day, part) else {
+        eprintln!("no solution for {year} day {day} part {part}");
+        process::exit(2);
+    };
+
+    let path = flag(&flags, "--input")
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("input/{year}/day{day}.txt"));
+    let input = match read_input(&path) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("error: {err}");
+            if path != "-" {
+                eprintln!(
+                    "(fetch it with `cargo aoc input -d {day} -y {year}`, or pipe it in with --input -)"
+                );
+            }
+            process::exit(1);
+        }
+    };
+
+    if flag(&flags, "--verbose").is_some() {
+        print_details(year, day, &input);
+    }
+
+    let config = load_config();
+    let show_progress = flag(&flags, "--progress").is_some();
+    let cancel = CancelToken::new();
+
+    // The solution runs on its own thread so the timeout can be enforced
+    // even for the days that never check the token.
+    let (sender, receiver) = mpsc::channel();
+    let token = cancel.clone();
+    thread::spawn(move || {
+        let progress: &dyn Progress = if show_progress {
+            &print_progress
+        } else {
+            &NoProgress
+        };
+        let _ = sender.send(config.run_with(solution, &input, progress, &token));
+    });
+
+    let result = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout),
+        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+    match result {
+        Ok(Ok(answer)) => println!("{answer}"),
+        Ok(Err(AocError::Cancelled)) | Err(RecvTimeoutError::Timeout) => {
+            // Let the searches that check the token wind down; the others
+            // die with the process.
+            cancel.cancel();
+            fail(format!("day {day} part {part} timed out"));
+        }
+        Ok(Err(err)) => fail(format!("{path}: {err}")),
+        // The solution panicked, and has already said why.
+        Err(RecvTimeoutError::Disconnected) => process::exit(1),
+    }
+}
+
+fn run_all(args: &[String]) {
+    let flags = parse_flags(args, &["--cache"]).unwrap_or_else(|| usage());
+    enable_cache(&flags);
+    let runs = match flag(&flags, "--runs").map(str::parse::<u32>) {
+        None => 1,
+        Some(Ok(runs)) => runs,
+        Some(Err(_)) => usage(),
+    };
+
+    let year = year(&flags);
+    let report = TimingReport::measure(year, &format!("input/{year}"), &load_config(), runs);
+    // With --json, the report goes to a file, ready for `aoc22 bench`.
+    if let Some(path) = flag(&flags, "--json") {
+        #[cfg(feature = "serde")]
+        std::fs::write(path, report.to_json()).unwrap_or_else(|err| fail(format!("{path}: {err}")));
+        #[cfg(not(feature = "serde"))]
+        fail(format!("--json {path} needs the serde feature"));
+    } else {
+        for timing in &report.timings {
+            let line = format!(
+                "day {:>2} part {}  {:>12.2?}",
+                timing.day,
+                timing.part,
+                std::time::Duration::from_nanos(timing.nanos)
+            );
+            #[cfg(feature = "alloc-metrics")]
+            let line = format!(
+                "{line}  {:>9} allocs {:>12} bytes",
+                timing.allocs, timing.bytes
+            );
+            println!("{line}");
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+fn bench(args: &[String]) {
+    let flags = parse_flags(args, &[]).unwrap_or_else(|| usage());
+    let (Some(baseline), Some(current)) = (flag(&flags, "--baseline"), flag(&flags, "--current"))
+    else {
+        usage();
+    };
+    let threshold = match flag(&flags, "--threshold").map(str::parse::<f64>) {
+        None => 0.1,
+        Some(Ok(threshold)) if threshold >= 0.0 => threshold,
+        Some(_) => usage(),
+    };
+
+    let baseline = TimingReport::load(baseline).unwrap_or_else(|err| fail(err));
+    let current = TimingReport::load(current).unwrap_or_else(|err| fail(err));
+    let comparisons = timings::compare(&baseline, &current);
+    print!("{}", timings::render(&comparisons, threshold));
+
+    // Fail on regressions, so this can gate a CI job.
+    if comparisons
+        .iter()
+        .any(|comparison| comparison.verdict(threshold) == timings::Verdict::Slower)
+    {
+        process::exit(1);
+    }
+}
+
+#[cfg(not(feature = "serde"))]
+fn bench(_args: &[String]) {
+    fail("bench needs the serde feature");
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("run-all") => run_all(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        _ => run_one(&args),
+    }
+}
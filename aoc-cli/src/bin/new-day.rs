@@ -0,0 +1,177 @@
+/*
+ * Scaffolds a new day for a future year:
+ *
+ *     cargo run -p aoc-cli --bin new-day -- 2023 1
+ *
+ * creates src/y2023/day1.rs from a template, declares it in src/y2023/mod.rs
+ * (declaring the year module in src/lib.rs too, if needed), registers both
+ * parts in src/registry.rs, and adds empty input files under input/2023/.
+ *
+ * cargo-aoc only understands a single year per crate, so new years don't
+ * use the #[aoc] attributes; they're run through the registry instead:
+ *
+ *     cargo run -p aoc-cli --bin aoc22 -- --year 2023 --day 1 --part 1
+ *
+ * and the golden test picks them up once input/2023/answers.toml exists.
+ */
+use std::{env, fs, io, path::Path, process};
+
+fn template(year: u32, day: u32) -> String {
+    format!(
+        r#"/*
+ * Advent of Code {year}, day {day}.
+ */
+use crate::utils::input::normalize;
+
+pub fn part1(input: &str) -> usize {{
+    let _input = normalize(input);
+    0
+}}
+
+pub fn part2(input: &str) -> usize {{
+    let _input = normalize(input);
+    0
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::{{part1, part2}};
+
+    // Paste the puzzle's example here, and fill in the expected answers.
+    const EXAMPLE: &str = "";
+
+    #[test]
+    fn test_part1() {{
+        assert_eq!(part1(EXAMPLE), 0);
+    }}
+
+    #[test]
+    fn test_part2() {{
+        assert_eq!(part2(EXAMPLE), 0);
+    }}
+}}
+"#
+    )
+}
+
+/**
+ * Appends `line` to the file at `path` (creating it if needed), unless the
+ * file already contains it.
+ */
+fn append_line(path: &Path, line: &str) -> io::Result<()> {
+    let mut contents = fs::read_to_string(path).unwrap_or_default();
+    if contents.lines().any(|existing| existing == line) {
+        return Ok(());
+    }
+
+    if !contents.is_empty() && !contents.ends_with('\n') {
+        contents.push('\n');
+    }
+    contents.push_str(line);
+    contents.push('\n');
+    fs::write(path, contents)
+}
+
+/**
+ * Declares the year module in lib.rs, after the existing year modules.
+ */
+fn declare_year(lib_path: &Path, year: u32) -> io::Result<()> {
+    let lib = fs::read_to_string(lib_path)?;
+    let declaration = format!("pub mod y{year};");
+    if lib.lines().any(|line| line == declaration) {
+        return Ok(());
+    }
+
+    let Some(last_year) = lib.rfind("pub mod y") else {
+        return Err(io::Error::other("couldn't find the year modules in lib.rs"));
+    };
+    let position = last_year + lib[last_year..].find('\n').map_or(0, |i| i + 1);
+
+    let mut updated = lib.clone();
+    updated.insert_str(
+        position,
+        &format!("#[cfg(feature = \"std\")]\n{declaration}\n"),
+    );
+    fs::write(lib_path, updated)
+}
+
+/**
+ * Adds both parts of the day to the end of registry::SOLUTIONS.
+ */
+fn register(registry_path: &Path, year: u32, day: u32) -> io::Result<()> {
+    let registry = fs::read_to_string(registry_path)?;
+    let entries: String = (1..=2)
+        .map(|part| {
+            format!("    solution!({year}, {day}, {part}, y{year}::day{day}::part{part}),\n")
+        })
+        .collect();
+    if registry.contains(&entries) {
+        return Ok(());
+    }
+
+    let Some(table) = registry.find("pub static SOLUTIONS") else {
+        return Err(io::Error::other("couldn't find SOLUTIONS in registry.rs"));
+    };
+    let Some(end) = registry[table..].find("\n];").map(|end| table + end + 1) else {
+        return Err(io::Error::other(
+            "couldn't find the end of SOLUTIONS in registry.rs",
+        ));
+    };
+
+    let mut updated = registry.clone();
+    updated.insert_str(end, &entries);
+    fs::write(registry_path, updated)
+}
+
+fn scaffold(root: &Path, year: u32, day: u32) -> io::Result<()> {
+    let year_dir = root.join(format!("src/y{year}"));
+    let module_path = year_dir.join(format!("day{day}.rs"));
+    if module_path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", module_path.display()),
+        ));
+    }
+
+    fs::create_dir_all(&year_dir)?;
+    fs::write(&module_path, template(year, day))?;
+    append_line(&year_dir.join("mod.rs"), &format!("pub mod day{day};"))?;
+    declare_year(&root.join("src/lib.rs"), year)?;
+    register(&root.join("src/registry.rs"), year, day)?;
+
+    let input_dir = root.join(format!("input/{year}"));
+    fs::create_dir_all(input_dir.join("test"))?;
+    for input_path in [
+        input_dir.join(format!("day{day}.txt")),
+        input_dir.join(format!("test/day{day}.txt")),
+    ] {
+        if !input_path.exists() {
+            fs::write(input_path, "")?;
+        }
+    }
+
+    println!("created {}", module_path.display());
+    println!("registered {year} day {day} in src/registry.rs");
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let parsed = match args.as_slice() {
+        [year, day] => year.parse::<u32>().ok().zip(day.parse::<u32>().ok()),
+        _ => None,
+    };
+
+    let Some((year, day)) = parsed.filter(|&(year, day)| year > 2022 && (1..=25).contains(&day))
+    else {
+        eprintln!("usage: new-day <year> <day>   (year after 2022, day 1-25)");
+        process::exit(2);
+    };
+
+    // The solutions crate is the root of the workspace, one level up.
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    if let Err(err) = scaffold(&root, year, day) {
+        eprintln!("error: {err}");
+        process::exit(1);
+    }
+}
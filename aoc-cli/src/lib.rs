@@ -0,0 +1,12 @@
+/*
+ * The command-line side of the repository, kept apart from the solutions
+ * so that they don't need to know about it:
+ *
+ *   - aoc22 (src/bin/aoc22.rs) runs solutions through the registry, and
+ *     times them;
+ *   - new-day (src/bin/new-day.rs) scaffolds a day for a new year.
+ *
+ * The solutions themselves are the advent-of-code-2022 crate at the root
+ * of the workspace, which cargo-aoc needs to find there.
+ */
+pub mod timings;
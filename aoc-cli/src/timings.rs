@@ -0,0 +1,273 @@
+/*
+ * Timing reports, for keeping an eye on performance across rewrites:
+ *
+ *     aoc22 run-all --json old.json
+ *     (make some changes)
+ *     aoc22 run-all --json new.json
+ *     aoc22 bench --baseline old.json --current new.json
+ *
+ * A report is just the time each solution took on the real input, parsing
+ * included (unlike `cargo aoc bench`). Reading and writing them as JSON
+ * needs the `serde` feature:
+ *
+ *     {"timings":[{"day":1,"part":1,"nanos":51234}, ...]}
+ *
+ * With the `alloc-metrics` feature, each timing also records how many
+ * allocations the solution made and how many bytes they came to, as
+ * "allocs" and "bytes". Reports without them read back as zero.
+ */
+use std::{fmt::Write, fs, time::Duration, time::Instant};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "alloc-metrics")]
+use advent_of_code_2022::alloc_metrics::{self, AllocStats};
+#[cfg(feature = "serde")]
+use advent_of_code_2022::error::AocError;
+use advent_of_code_2022::{config::Config, registry::SOLUTIONS};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Timing {
+    pub day: u8,
+    pub part: u8,
+    pub nanos: u64,
+    #[cfg(feature = "alloc-metrics")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub allocs: usize,
+    #[cfg(feature = "alloc-metrics")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bytes: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TimingReport {
+    pub timings: Vec<Timing>,
+}
+
+impl TimingReport {
+    /**
+     * Times every solution for `year` whose input is in `input_dir` (as
+     * dayN.txt), keeping the fastest of `runs` runs. Days without an input
+     * file are left out.
+     */
+    pub fn measure(year: u16, input_dir: &str, config: &Config, runs: u32) -> Self {
+        let mut timings = Vec::new();
+        for solution in SOLUTIONS.iter().filter(|solution| solution.year == year) {
+            let Ok(input) = fs::read_to_string(format!("{input_dir}/day{}.txt", solution.day))
+            else {
+                continue;
+            };
+            let mut fastest = Duration::MAX;
+            // Every run allocates the same, so the last one's count will do.
+            #[cfg(feature = "alloc-metrics")]
+            let mut allocs = AllocStats::default();
+            for _ in 0..runs.max(1) {
+                let run = || {
+                    config
+                        .run(solution, &input)
+                        .unwrap_or_else(|err| panic!("day {}: {err}", solution.day))
+                };
+                let start = Instant::now();
+                #[cfg(not(feature = "alloc-metrics"))]
+                run();
+                #[cfg(feature = "alloc-metrics")]
+                {
+                    allocs = alloc_metrics::measure(run).1;
+                }
+                fastest = fastest.min(start.elapsed());
+            }
+            timings.push(Timing {
+                day: solution.day,
+                part: solution.part,
+                nanos: fastest.as_nanos() as u64,
+                #[cfg(feature = "alloc-metrics")]
+                allocs: allocs.allocations,
+                #[cfg(feature = "alloc-metrics")]
+                bytes: allocs.total_bytes,
+            });
+        }
+        TimingReport { timings }
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("timings always serialize")
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn from_json(text: &str) -> Result<Self, AocError> {
+        serde_json::from_str(text).map_err(|err| AocError::parse(err.line(), err.to_string()))
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn load(path: &str) -> Result<Self, AocError> {
+        let text = fs::read_to_string(path).map_err(|err| AocError::Io {
+            path: path.to_string(),
+            message: err.to_string(),
+        })?;
+        Self::from_json(&text).map_err(|err| AocError::Io {
+            path: path.to_string(),
+            message: err.to_string(),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Faster,
+    Slower,
+    Same,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Comparison {
+    pub day: u8,
+    pub part: u8,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Comparison {
+    /**
+     * How many times faster the current run is (below 1 means slower).
+     */
+    pub fn speedup(&self) -> f64 {
+        self.baseline.as_secs_f64() / self.current.as_secs_f64().max(1e-9)
+    }
+
+    /**
+     * Changes within `threshold` (e.g. 0.1 for 10%) either way count as
+     * noise.
+     */
+    pub fn verdict(&self, threshold: f64) -> Verdict {
+        let speedup = self.speedup();
+        if speedup > 1.0 + threshold {
+            Verdict::Faster
+        } else if speedup < 1.0 / (1.0 + threshold) {
+            Verdict::Slower
+        } else {
+            Verdict::Same
+        }
+    }
+}
+
+/**
+ * Pairs up the solutions timed in both reports, in the current report's
+ * order. Solutions in only one of them are skipped.
+ */
+pub fn compare(baseline: &TimingReport, current: &TimingReport) -> Vec<Comparison> {
+    current
+        .timings
+        .iter()
+        .filter_map(|now| {
+            let before = baseline
+                .timings
+                .iter()
+                .find(|before| (before.day, before.part) == (now.day, now.part))?;
+            Some(Comparison {
+                day: now.day,
+                part: now.part,
+                baseline: Duration::from_nanos(before.nanos),
+                current: Duration::from_nanos(now.nanos),
+            })
+        })
+        .collect()
+}
+
+/**
+ * One line per comparison, plus a summary line.
+ */
+pub fn render(comparisons: &[Comparison], threshold: f64) -> String {
+    let mut out = String::new();
+    let (mut faster, mut slower) = (0, 0);
+    for comparison in comparisons {
+        let verdict = comparison.verdict(threshold);
+        let label = match verdict {
+            Verdict::Faster => "faster",
+            Verdict::Slower => "SLOWER",
+            Verdict::Same => "",
+        };
+        faster += usize::from(verdict == Verdict::Faster);
+        slower += usize::from(verdict == Verdict::Slower);
+        let line = format!(
+            "day {:>2} part {}  {:>12.2?} -> {:>12.2?}  {:>6.2}x  {label}",
+            comparison.day,
+            comparison.part,
+            comparison.baseline,
+            comparison.current,
+            comparison.speedup(),
+        );
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    // Writing to a String can't fail.
+    let _ = writeln!(
+        out,
+        "{faster} faster, {slower} slower (threshold {:.0}%)",
+        threshold * 100.0
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(timings: &[(u8, u8, u64)]) -> TimingReport {
+        TimingReport {
+            timings: timings
+                .iter()
+                .map(|&(day, part, nanos)| Timing {
+                    day,
+                    part,
+                    nanos,
+                    #[cfg(feature = "alloc-metrics")]
+                    allocs: 0,
+                    #[cfg(feature = "alloc-metrics")]
+                    bytes: 0,
+                })
+                .collect(),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json() {
+        let original = report(&[(1, 1, 500), (16, 2, 1_000_000)]);
+        let json = original.to_json();
+        let without_allocs =
+            r#"{"timings":[{"day":1,"part":1,"nanos":500},{"day":16,"part":2,"nanos":1000000}]}"#;
+        #[cfg(not(feature = "alloc-metrics"))]
+        assert_eq!(json, without_allocs);
+        #[cfg(feature = "alloc-metrics")]
+        assert_eq!(
+            json,
+            r#"{"timings":[{"day":1,"part":1,"nanos":500,"allocs":0,"bytes":0},{"day":16,"part":2,"nanos":1000000,"allocs":0,"bytes":0}]}"#
+        );
+        assert_eq!(TimingReport::from_json(&json).unwrap(), original);
+        assert_eq!(TimingReport::from_json(without_allocs).unwrap(), original);
+        assert!(matches!(
+            TimingReport::from_json("{\n\"timings\": 3}"),
+            Err(AocError::Parse { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = report(&[(1, 1, 1000), (1, 2, 1000), (2, 1, 1000), (3, 1, 5)]);
+        let current = report(&[(1, 1, 500), (1, 2, 1050), (2, 1, 2000), (4, 1, 5)]);
+        let comparisons = compare(&baseline, &current);
+
+        let verdicts: Vec<Verdict> = comparisons.iter().map(|c| c.verdict(0.1)).collect();
+        assert_eq!(verdicts, [Verdict::Faster, Verdict::Same, Verdict::Slower]);
+        assert_eq!(comparisons[0].speedup(), 2.0);
+
+        let table = render(&comparisons, 0.1);
+        assert_eq!(table.lines().count(), 4);
+        assert!(table.lines().nth(2).unwrap().ends_with("0.50x  SLOWER"));
+        assert!(table.ends_with("1 faster, 1 slower (threshold 10%)\n"));
+    }
+}
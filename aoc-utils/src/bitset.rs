@@ -0,0 +1,111 @@
+use core::fmt::{self, Display};
+
+// A bitset is *much* faster than using e.g. a HashSet<usize> for sets of
+// small integers, like the room IDs in day 16.
+//
+// This is an extremely limited implementation that supports at most 32 elements.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Bitset {
+    bits: u32,
+}
+
+impl Bitset {
+    pub fn new() -> Self {
+        Bitset { bits: 0 }
+    }
+
+    /**
+     * Creates a bitset directly from its bit representation: the i-th bit
+     * of `bits` is set iff i is in the set.
+     */
+    pub fn from_bits(bits: u32) -> Self {
+        Bitset { bits }
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    pub fn insert(&mut self, value: usize) {
+        self.bits |= 1u32 << value;
+    }
+
+    pub fn remove(&mut self, value: usize) {
+        self.bits &= !(1u32 << value);
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        (self.bits & (1u32 << value)) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    pub fn iter(&self) -> BitsetIterator<'_> {
+        BitsetIterator {
+            bitset: self,
+            current: 0,
+        }
+    }
+}
+
+impl Display for Bitset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, item) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{item}")?;
+        }
+        write!(f, "]")
+    }
+}
+
+/**
+ * Iterator over the elements in a bitset.
+ */
+pub struct BitsetIterator<'a> {
+    bitset: &'a Bitset,
+    current: usize,
+}
+
+impl<'a> Iterator for BitsetIterator<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for i in self.current..32 {
+            if self.bitset.contains(i) {
+                self.current = i + 1;
+                return Some(i);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Bitset;
+
+    #[test]
+    fn test_bitset() {
+        let mut set = Bitset::new();
+        set.insert(3);
+        set.insert(0);
+        set.insert(31);
+        set.remove(3);
+
+        assert!(set.contains(0));
+        assert!(!set.contains(3));
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0, 31]);
+        assert_eq!(set.to_string(), "[0, 31]");
+    }
+}
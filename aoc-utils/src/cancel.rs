@@ -0,0 +1,76 @@
+/*
+ * Cooperative cancellation for the long searches.
+ *
+ * A CancelToken is shared between whoever might want to stop a search (a
+ * timeout, say) and the search itself, which checks it every so often and
+ * gives up with Cancelled once it's been cancelled. Checking is a single
+ * relaxed atomic load, so it's cheap enough for the inner loops.
+ */
+use alloc::sync::Arc;
+use core::fmt;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+     * Asks every search holding a clone of this token to stop.
+     */
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /**
+     * Err(Cancelled) if the token has been cancelled, for use with `?`.
+     */
+    pub fn check(&self) -> Result<(), Cancelled> {
+        if self.is_cancelled() {
+            Err(Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/**
+ * The error for a search that stopped because its token was cancelled.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cancelled")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Cancelled {}
+
+#[cfg(test)]
+mod tests {
+    use super::{CancelToken, Cancelled};
+
+    #[test]
+    fn test_cancel() {
+        let token = CancelToken::new();
+        let search = token.clone();
+        assert_eq!(search.check(), Ok(()));
+
+        token.cancel();
+        assert!(search.is_cancelled());
+        assert_eq!(search.check(), Err(Cancelled));
+    }
+}
@@ -0,0 +1,205 @@
+use core::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::neighbors::Point3;
+
+/**
+ * A position or offset on a 2D grid. As with puzzle inputs, y grows
+ * downwards, so Direction::Up is (0, -1).
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec2 {
+    pub x: i32,
+    pub y: i32,
+}
+
+/**
+ * A position or offset in 3D space.
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Vec2 {
+    pub const fn new(x: i32, y: i32) -> Self {
+        Vec2 { x, y }
+    }
+
+    /**
+     * The distance from the origin, moving along the axes only.
+     */
+    pub fn manhattan(self) -> i32 {
+        self.x.abs() + self.y.abs()
+    }
+
+    /**
+     * The cell one step away in `direction`.
+     */
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.delta()
+    }
+}
+
+impl Vec3 {
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        Vec3 { x, y, z }
+    }
+
+    pub fn manhattan(self) -> i32 {
+        self.x.abs() + self.y.abs() + self.z.abs()
+    }
+}
+
+/*
+ * The arithmetic is the same for both, component by component.
+ */
+macro_rules! vector_ops {
+    ($vec:ident { $($field:ident),+ }) => {
+        impl Add for $vec {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                $vec { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl Sub for $vec {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                $vec { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl Mul<i32> for $vec {
+            type Output = Self;
+
+            fn mul(self, scale: i32) -> Self {
+                $vec { $($field: self.$field * scale),+ }
+            }
+        }
+
+        impl Neg for $vec {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                $vec { $($field: -self.$field),+ }
+            }
+        }
+
+        impl AddAssign for $vec {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl SubAssign for $vec {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+    };
+}
+
+vector_ops!(Vec2 { x, y });
+vector_ops!(Vec3 { x, y, z });
+
+impl From<(i32, i32)> for Vec2 {
+    fn from((x, y): (i32, i32)) -> Self {
+        Vec2 { x, y }
+    }
+}
+
+impl From<Point3> for Vec3 {
+    fn from((x, y, z): Point3) -> Self {
+        Vec3 { x, y, z }
+    }
+}
+
+impl From<Vec3> for Point3 {
+    fn from(vec: Vec3) -> Self {
+        (vec.x, vec.y, vec.z)
+    }
+}
+
+/**
+ * One of the four directions on a 2D grid, listed clockwise from Up.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /**
+     * The offset of one step in this direction.
+     */
+    pub fn delta(self) -> Vec2 {
+        match self {
+            Direction::Up => Vec2::new(0, -1),
+            Direction::Right => Vec2::new(1, 0),
+            Direction::Down => Vec2::new(0, 1),
+            Direction::Left => Vec2::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, Vec2, Vec3};
+
+    #[test]
+    fn test_vectors() {
+        let a = Vec2::new(1, 2);
+        let b = Vec2::from((4, -2));
+        assert_eq!(a + b, Vec2::new(5, 0));
+        assert_eq!(a - b, Vec2::new(-3, 4));
+        assert_eq!((a - b).manhattan(), 7);
+        assert_eq!(-a * 3, Vec2::new(-3, -6));
+
+        let mut c = Vec3::from((1, 2, 3));
+        c += Vec3::new(1, 1, 1);
+        c -= Vec3::new(0, 5, 0);
+        assert_eq!(c, Vec3::new(2, -2, 4));
+        assert_eq!(c.manhattan(), 8);
+        assert_eq!(<(i32, i32, i32)>::from(c), (2, -2, 4));
+    }
+
+    #[test]
+    fn test_directions() {
+        let start = Vec2::new(0, 0);
+        assert_eq!(start.step(Direction::Up), Vec2::new(0, -1));
+
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.opposite());
+            assert_eq!(direction.delta() + direction.opposite().delta(), start);
+        }
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+    }
+}
@@ -0,0 +1,131 @@
+use alloc::vec::Vec;
+
+/**
+ * A rectangular grid of cells, stored row by row in one Vec. Cells are
+ * addressed as (row, col), with (0, 0) at the top left, the way puzzle
+ * inputs are read.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid2D<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid2D<T> {
+    /**
+     * A `width` by `height` grid with every cell set to `fill`.
+     */
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid2D {
+            width,
+            height,
+            cells: alloc::vec![fill; width * height],
+        }
+    }
+
+    /**
+     * Builds a grid from its rows. None if they aren't all the same length.
+     */
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Option<Self> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for row in rows {
+            if *width.get_or_insert(row.len()) != row.len() {
+                return None;
+            }
+            cells.extend(row);
+            height += 1;
+        }
+        Some(Grid2D {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    /**
+     * Reads one cell per character, one row per line.
+     */
+    pub fn parse(input: &str, cell: impl Fn(char) -> T) -> Option<Self> {
+        Self::from_rows(input.lines().map(|line| line.chars().map(&cell).collect()))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        self.index(row, col).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        self.index(row, col).map(|index| &mut self.cells[index])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks() won't take a size of 0, but then there are no cells anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /**
+     * Every cell's position, in reading order.
+     */
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /**
+     * The cells above, left of, right of and below (row, col) that are
+     * inside the grid.
+     */
+    pub fn neighbors(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+        [(-1, 0), (0, -1), (0, 1), (1, 0)]
+            .into_iter()
+            .filter_map(move |(d_row, d_col)| {
+                let row = row.checked_add_signed(d_row)?;
+                let col = col.checked_add_signed(d_col)?;
+                (row < height && col < width).then_some((row, col))
+            })
+    }
+
+    fn index(&self, row: usize, col: usize) -> Option<usize> {
+        (row < self.height && col < self.width).then(|| row * self.width + col)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid2D;
+
+    #[test]
+    fn test_grid() {
+        let mut grid = Grid2D::parse("ab\ncd\nef", |c| c).unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert_eq!(grid.get(2, 1), Some(&'f'));
+        assert_eq!(grid.get(1, 2), None);
+
+        *grid.get_mut(0, 0).unwrap() = 'z';
+        let rows: Vec<&[char]> = grid.rows().collect();
+        assert_eq!(rows, [&['z', 'b'][..], &['c', 'd'], &['e', 'f']]);
+        assert_eq!(grid.positions().nth(3), Some((1, 1)));
+
+        let neighbors: Vec<_> = grid.neighbors(0, 1).collect();
+        assert_eq!(neighbors, [(0, 0), (1, 1)]);
+        assert_eq!(grid.neighbors(1, 0).count(), 3);
+
+        assert_eq!(Grid2D::parse("ab\nc", |c| c), None);
+        assert_eq!(Grid2D::new(3, 2, 0u8).rows().count(), 2);
+        assert_eq!(Grid2D::<u8>::from_rows([]).unwrap().rows().count(), 0);
+    }
+}
@@ -0,0 +1,13 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::BuildHasherDefault;
+
+use rustc_hash::FxHasher;
+
+// std's HashMap uses SipHash, which is DoS-resistant but slow for the small
+// integer/tuple keys we hash millions of times in the search-heavy days.
+// FxHash is the (non-cryptographic) hasher used inside rustc; it's much
+// faster for keys like these.
+//
+// Build these with `FastMap::default()` rather than `new()`.
+pub type FastMap<K, V> = HashMap<K, V, BuildHasherDefault<FxHasher>>;
+pub type FastSet<T> = HashSet<T, BuildHasherDefault<FxHasher>>;
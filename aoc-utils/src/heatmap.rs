@@ -0,0 +1,107 @@
+/*
+ * Draws a grid of numbers as a heatmap, either as text or as an SVG, with
+ * each cell shaded by its value relative to the largest one in the grid.
+ */
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+/**
+ * Characters for the text version, from lightest (zero) to darkest.
+ */
+const RAMP: &[u8] = b" .:-=+*#%@";
+
+/**
+ * The shade for each value, from 0 (for zero) up to `levels - 1` (for the
+ * largest value). Anything nonzero gets at least the first shade, so that
+ * small values don't disappear.
+ */
+fn levels(grid: &[Vec<usize>], levels: usize) -> Vec<Vec<usize>> {
+    let max = grid.iter().flatten().copied().max().unwrap_or(0);
+    grid.iter()
+        .map(|row| {
+            row.iter()
+                .map(|&value| {
+                    if max == 0 {
+                        0
+                    } else {
+                        (value * (levels - 1)).div_ceil(max)
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+pub fn ascii(grid: &[Vec<usize>]) -> String {
+    let mut out = String::new();
+    for row in levels(grid, RAMP.len()) {
+        out.extend(row.iter().map(|&level| RAMP[level] as char));
+        out.push('\n');
+    }
+    out
+}
+
+/**
+ * Draws each cell as a `cell_size` square, darker for larger values.
+ * Cells with a value of zero are left blank.
+ */
+pub fn svg(grid: &[Vec<usize>], cell_size: usize) -> String {
+    const SHADES: usize = 101;
+
+    let height = grid.len() * cell_size;
+    let width = grid.first().map_or(0, |row| row.len()) * cell_size;
+
+    let mut out = String::new();
+    // Writing to a String can't fail.
+    let _ = writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}">"#
+    );
+    let _ = writeln!(out, r#"<rect width="100%" height="100%" fill="white"/>"#);
+    for (y, row) in levels(grid, SHADES).iter().enumerate() {
+        for (x, &level) in row.iter().enumerate() {
+            if level == 0 {
+                continue;
+            }
+            let _ = writeln!(
+                out,
+                r#"<rect x="{}" y="{}" width="{cell_size}" height="{cell_size}" fill="darkgreen" fill-opacity="{:.2}"/>"#,
+                x * cell_size,
+                y * cell_size,
+                level as f64 / (SHADES - 1) as f64,
+            );
+        }
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_ascii() {
+        let grid = vec![vec![0, 1, 9], vec![18, 9, 0]];
+        assert_eq!(ascii(&grid), " .+\n@+ \n");
+        assert_eq!(ascii(&[vec![0, 0]]), "  \n");
+    }
+
+    #[test]
+    fn test_svg() {
+        let image = svg(&[vec![0, 2], vec![1, 2]], 10);
+        assert!(
+            image.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20">"#)
+        );
+        assert!(image.contains(
+            r#"<rect x="10" y="0" width="10" height="10" fill="darkgreen" fill-opacity="1.00"/>"#
+        ));
+        assert!(image.contains(
+            r#"<rect x="0" y="10" width="10" height="10" fill="darkgreen" fill-opacity="0.50"/>"#
+        ));
+        // The background, plus one for each nonzero cell.
+        assert_eq!(image.matches("<rect").count(), 4);
+    }
+}
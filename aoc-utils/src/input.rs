@@ -0,0 +1,106 @@
+use alloc::{borrow::Cow, string::ToString, vec::Vec};
+
+/**
+ * Puts puzzle input into a canonical form: `\n` line endings, no trailing
+ * whitespace at the end of the input, and exactly one trailing newline.
+ *
+ * Most days are written against input in exactly this form, and quietly
+ * break on e.g. a file saved with Windows line endings. Input that is
+ * already canonical is returned as-is without copying.
+ */
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    if is_normalized(input.as_bytes()) {
+        return Cow::Borrowed(input);
+    }
+
+    let mut result = input.replace("\r\n", "\n").trim_end().to_string();
+    result.push('\n');
+    Cow::Owned(result)
+}
+
+/**
+ * Like `normalize`, but for days that work directly on the input bytes.
+ */
+pub fn normalize_bytes(input: &[u8]) -> Cow<'_, [u8]> {
+    if is_normalized(input) {
+        return Cow::Borrowed(input);
+    }
+
+    let mut result: Vec<u8> = Vec::with_capacity(input.len() + 1);
+    for (i, &b) in input.iter().enumerate() {
+        if b == b'\r' && input.get(i + 1) == Some(&b'\n') {
+            continue;
+        }
+        result.push(b);
+    }
+    while result.last().is_some_and(|b| b.is_ascii_whitespace()) {
+        result.pop();
+    }
+    result.push(b'\n');
+    Cow::Owned(result)
+}
+
+/**
+ * Scans the input for integers (each an optional '-' followed by digits),
+ * treating every other byte as a separator. This skips the UTF-8 checks and
+ * intermediate slices of splitting and str::parse, which adds up on inputs
+ * that are mostly numbers.
+ */
+pub fn ints_from_bytes(input: &[u8]) -> impl Iterator<Item = i64> + '_ {
+    let mut pos = 0;
+    core::iter::from_fn(move || {
+        // Skip ahead to the start of the next number.
+        while pos < input.len() && !input[pos].is_ascii_digit() {
+            pos += 1;
+        }
+        if pos == input.len() {
+            return None;
+        }
+        let negative = pos > 0 && input[pos - 1] == b'-';
+
+        let mut value: i64 = 0;
+        while pos < input.len() && input[pos].is_ascii_digit() {
+            value = value * 10 + (input[pos] - b'0') as i64;
+            pos += 1;
+        }
+        Some(if negative { -value } else { value })
+    })
+}
+
+fn is_normalized(input: &[u8]) -> bool {
+    match input {
+        [.., last, b'\n'] => !last.is_ascii_whitespace() && !input.contains(&b'\r'),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::{ints_from_bytes, normalize, normalize_bytes};
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_normalize() {
+        assert!(matches!(normalize("a\nb\n"), Cow::Borrowed("a\nb\n")));
+        assert_eq!(normalize("a\r\nb\r\n"), "a\nb\n");
+        assert_eq!(normalize("a\n\nb"), "a\n\nb\n");
+        assert_eq!(normalize("a\nb  \n\n\n"), "a\nb\n");
+        assert_eq!(normalize("  a\r\n\r\nb\r\n"), "  a\n\nb\n");
+    }
+
+    #[test]
+    fn test_normalize_bytes() {
+        assert!(matches!(normalize_bytes(b"abc\n"), Cow::Borrowed(_)));
+        assert_eq!(&*normalize_bytes(b"abc\r\ndef\r\n"), b"abc\ndef\n");
+        assert_eq!(&*normalize_bytes(b"abc"), b"abc\n");
+    }
+
+    #[test]
+    fn test_ints_from_bytes() {
+        let ints: Vec<i64> = ints_from_bytes(b"498,4 -> 502,-9\n-3\r\n\n12").collect();
+        assert_eq!(ints, [498, 4, 502, -9, -3, 12]);
+        assert_eq!(ints_from_bytes(b"no numbers - here").count(), 0);
+    }
+}
@@ -0,0 +1,243 @@
+use alloc::vec::Vec;
+use core::cmp::{max, min};
+
+use num::PrimInt;
+
+/**
+ * Represents a closed interval over the integers.
+ * For example, Interval { start: 2, end: 4 } represents [2, 3, 4].
+ *
+ * An interval whose start is after its end contains no points.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: PrimInt> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start > self.end
+    }
+
+    /**
+     * The number of integers in this interval.
+     */
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::zero()
+        } else {
+            self.end - self.start + T::one()
+        }
+    }
+
+    /**
+     * Checks whether this interval entirely contains another.
+     */
+    pub fn contains(&self, other: &Self) -> bool {
+        (self.start <= other.start) && (self.end >= other.end)
+    }
+
+    /**
+     * Checks whether this interval has any overlap with another by
+     * checking if the intersection is nonempty.
+     */
+    pub fn overlaps(&self, other: &Self) -> bool {
+        // This is maybe the first time that leetcode has been
+        // useful in the real world (to the extent that AoC counts).
+        max(self.start, other.start) <= min(self.end, other.end)
+    }
+
+    /**
+     * Returns the points common to both intervals, if there are any.
+     */
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let result = Self::new(max(self.start, other.start), min(self.end, other.end));
+        if result.is_empty() {
+            None
+        } else {
+            Some(result)
+        }
+    }
+
+    /**
+     * Returns the union of the two intervals, if it is itself an interval.
+     *
+     * Because we're working over the integers, intervals that merely touch
+     * can be merged: the union of [0, 4] and [5, 8] is [0, 8].
+     */
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        if self.is_empty() {
+            return Some(*other);
+        }
+        if other.is_empty() {
+            return Some(*self);
+        }
+
+        let (first, second) = if self.start <= other.start {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        // Careful not to compute end+1 when the end is already T::max_value().
+        let touching = first.end < T::max_value() && second.start == first.end + T::one();
+        if second.start <= first.end || touching {
+            Some(Self::new(first.start, max(first.end, second.end)))
+        } else {
+            None
+        }
+    }
+}
+
+/**
+ * A set of integers, stored as a sorted list of disjoint intervals.
+ *
+ * Intervals are merged as they're added, so no two stored intervals
+ * overlap or even touch.
+ */
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        // Every stored interval in [first, last) can be merged with the new one.
+        let first = self
+            .intervals
+            .partition_point(|int| int.end < interval.start && int.union(&interval).is_none());
+        let mut merged = interval;
+        let mut last = first;
+        while last < self.intervals.len() {
+            match merged.union(&self.intervals[last]) {
+                Some(union) => merged = union,
+                None => break,
+            }
+            last += 1;
+        }
+
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let point = Interval::new(value, value);
+        self.intervals.iter().any(|int| int.contains(&point))
+    }
+
+    /**
+     * The total number of integers in the set.
+     */
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::zero(), |total, int| total + int.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /**
+     * Returns the part of this set that lies within `bounds`.
+     */
+    pub fn clamp(&self, bounds: &Interval<T>) -> Self {
+        Self {
+            intervals: self
+                .intervals
+                .iter()
+                .filter_map(|int| int.intersection(bounds))
+                .collect(),
+        }
+    }
+}
+
+impl<T: PrimInt> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        // Sorting first means each interval can only merge with the last one added.
+        let mut intervals: Vec<Interval<T>> = iter.into_iter().filter(|i| !i.is_empty()).collect();
+        intervals.sort_by_key(|int| int.start);
+
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            if let Some(last) = merged.last_mut() {
+                if let Some(union) = last.union(&interval) {
+                    *last = union;
+                    continue;
+                }
+            }
+            merged.push(interval);
+        }
+
+        Self { intervals: merged }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Interval, IntervalSet};
+
+    #[test]
+    fn test_len() {
+        assert_eq!(Interval::new(2, 4).len(), 3);
+        assert_eq!(Interval::new(-3, -3).len(), 1);
+        assert_eq!(Interval::new(5, 4).len(), 0);
+        assert!(Interval::new(5u32, 4).is_empty());
+    }
+
+    #[test]
+    fn test_intersection_and_union() {
+        let a = Interval::new(0, 4);
+        let b = Interval::new(3, 8);
+        let c = Interval::new(5, 8);
+        let d = Interval::new(6, 8);
+
+        assert_eq!(a.intersection(&b), Some(Interval::new(3, 4)));
+        assert_eq!(a.intersection(&c), None);
+
+        assert_eq!(a.union(&b), Some(Interval::new(0, 8)));
+        assert_eq!(c.union(&a), Some(Interval::new(0, 8)));
+        assert_eq!(a.union(&d), None);
+    }
+
+    #[test]
+    fn test_interval_set() {
+        let mut set: IntervalSet<i32> = [Interval::new(10, 12), Interval::new(0, 4)]
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), 8);
+
+        set.insert(Interval::new(5, 6));
+        assert_eq!(
+            set.intervals(),
+            &[Interval::new(0, 6), Interval::new(10, 12)]
+        );
+
+        set.insert(Interval::new(8, 8));
+        set.insert(Interval::new(7, 9));
+        assert_eq!(set.intervals(), &[Interval::new(0, 12)]);
+        assert!(set.contains(7));
+        assert!(!set.contains(13));
+
+        let clamped = set.clamp(&Interval::new(-5, 3));
+        assert_eq!(clamped.intervals(), &[Interval::new(0, 3)]);
+    }
+}
@@ -0,0 +1,28 @@
+/*!
+ * The helpers from the 2022 solutions that aren't specific to any one
+ * puzzle, in a crate of their own so later years can depend on them
+ * without pulling in cargo-aoc or the solutions themselves.
+ *
+ * The `std` feature is on by default. Without it, only the modules that
+ * need nothing beyond core + alloc are built.
+ */
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod bitset;
+pub mod cancel;
+pub mod geometry;
+pub mod grid;
+#[cfg(feature = "std")]
+pub mod hash;
+pub mod heatmap;
+pub mod input;
+pub mod interval;
+#[cfg(feature = "std")]
+pub mod memo;
+pub mod neighbors;
+pub mod parse;
+pub mod progress;
+#[cfg(feature = "std")]
+pub mod search;
@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+use std::hash::Hash;
+
+use crate::hash::FastMap;
+
+// A memoization table for the recursive searches, with hit/miss counters
+// and an optional cap on the number of entries. Once the cap is reached,
+// the least recently used entry is evicted to make room for a new one.
+//
+// Without a cap this is just a hash map plus a couple of counters; the
+// bookkeeping for LRU eviction only happens when there is a cap.
+pub struct Memo<K, V> {
+    entries: FastMap<K, (V, u64)>,
+    // Only used with a capacity: maps the last time each entry was used
+    // to its key, so the oldest entry is always first.
+    recency: BTreeMap<u64, K>,
+    capacity: Option<usize>,
+    clock: u64,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+impl<K: Hash + Eq + Clone, V> Memo<K, V> {
+    pub fn new() -> Self {
+        Memo {
+            entries: FastMap::default(),
+            recency: BTreeMap::new(),
+            capacity: None,
+            clock: 0,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+        }
+    }
+
+    /**
+     * Creates a memo that holds at most `capacity` entries.
+     */
+    pub fn with_capacity(capacity: usize) -> Self {
+        assert!(capacity > 0, "memo capacity must be positive");
+        Memo {
+            capacity: Some(capacity),
+            ..Memo::new()
+        }
+    }
+
+    /**
+     * Looks up a previously-stored value, counting a hit or a miss.
+     */
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.clock += 1;
+        match self.entries.get_mut(key) {
+            Some((value, last_used)) => {
+                self.hits += 1;
+                if self.capacity.is_some() {
+                    let key = self.recency.remove(last_used).unwrap();
+                    self.recency.insert(self.clock, key);
+                }
+                *last_used = self.clock;
+                Some(value)
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /**
+     * Stores a value, evicting the least recently used entry if the memo
+     * is full.
+     */
+    pub fn insert(&mut self, key: K, value: V) {
+        self.clock += 1;
+
+        if let Some(capacity) = self.capacity {
+            if let Some((_, last_used)) = self.entries.get(&key) {
+                self.recency.remove(last_used);
+            } else if self.entries.len() >= capacity {
+                let (_, oldest) = self.recency.pop_first().unwrap();
+                self.entries.remove(&oldest);
+                self.evictions += 1;
+            }
+            self.recency.insert(self.clock, key.clone());
+        }
+
+        self.entries.insert(key, (value, self.clock));
+    }
+
+    /**
+     * Returns the stored value for `key`, computing and storing it first if
+     * there isn't one. Recursive searches can't use this (the closure can't
+     * borrow the memo), so they should use get() and insert() instead.
+     */
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, f: F) -> &V {
+        if self.get(&key).is_none() {
+            self.insert(key.clone(), f());
+        }
+        &self.entries[&key].0
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    pub fn evictions(&self) -> u64 {
+        self.evictions
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counters() {
+        let mut memo = Memo::new();
+        assert_eq!(*memo.get_or_insert_with(1, || "one"), "one");
+        assert_eq!(
+            *memo.get_or_insert_with(1, || panic!("already stored")),
+            "one"
+        );
+        assert_eq!(memo.get(&2), None);
+        assert_eq!((memo.hits(), memo.misses(), memo.len()), (1, 2, 1));
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let mut memo = Memo::with_capacity(2);
+        memo.insert(1, 10);
+        memo.insert(2, 20);
+
+        // Touch 1, so that 2 is the least recently used.
+        assert_eq!(memo.get(&1), Some(&10));
+        memo.insert(3, 30);
+
+        assert_eq!(memo.len(), 2);
+        assert_eq!(memo.evictions(), 1);
+        assert_eq!(memo.get(&2), None);
+        assert_eq!(memo.get(&1), Some(&10));
+        assert_eq!(memo.get(&3), Some(&30));
+    }
+}
@@ -0,0 +1,82 @@
+use alloc::vec::Vec;
+
+pub type Point3 = (i32, i32, i32);
+
+/**
+ * Which cells count as "adjacent" to a cell in a 3D grid.
+ *
+ *  - Face:   cells sharing a face (6 neighbors)
+ *  - Edge:   cells sharing a face or an edge (18 neighbors)
+ *  - Vertex: cells sharing a face, an edge, or a corner (26 neighbors)
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Connectivity {
+    Face,
+    Edge,
+    Vertex,
+}
+
+impl Connectivity {
+    /**
+     * The largest number of coordinates that can differ between a cell
+     * and one of its neighbors.
+     */
+    fn max_changed_axes(&self) -> usize {
+        match self {
+            Connectivity::Face => 1,
+            Connectivity::Edge => 2,
+            Connectivity::Vertex => 3,
+        }
+    }
+}
+
+/**
+ * Returns the neighbors of a point under the given connectivity.
+ */
+pub fn neighbors3d(point: &Point3, connectivity: Connectivity) -> Vec<Point3> {
+    let max_changed = connectivity.max_changed_axes();
+
+    let mut result = Vec::with_capacity(26);
+    for dx in -1..=1 {
+        for dy in -1..=1 {
+            for dz in -1..=1 {
+                let changed = [dx, dy, dz].iter().filter(|&&d| d != 0).count();
+                if changed == 0 || changed > max_changed {
+                    continue;
+                }
+                result.push((point.0 + dx, point.1 + dy, point.2 + dz));
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{neighbors3d, Connectivity};
+
+    #[test]
+    fn test_neighbor_counts() {
+        let origin = (0, 0, 0);
+        assert_eq!(neighbors3d(&origin, Connectivity::Face).len(), 6);
+        assert_eq!(neighbors3d(&origin, Connectivity::Edge).len(), 18);
+        assert_eq!(neighbors3d(&origin, Connectivity::Vertex).len(), 26);
+    }
+
+    #[test]
+    fn test_face_neighbors() {
+        let mut neighbors = neighbors3d(&(1, 2, 3), Connectivity::Face);
+        neighbors.sort();
+        assert_eq!(
+            neighbors,
+            vec![
+                (0, 2, 3),
+                (1, 1, 3),
+                (1, 2, 2),
+                (1, 2, 4),
+                (1, 3, 3),
+                (2, 2, 3)
+            ]
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::str::FromStr;
+
+/**
+ * Pulls every integer out of a line of text, in order, ignoring whatever
+ * is around them: "Blueprint 1: Each ore robot costs 4 ore." gives [1, 4].
+ *
+ * A '-' directly before the digits is treated as a sign, so asking for an
+ * unsigned type when the text has negative numbers will panic.
+ */
+pub fn ints<T>(line: &str) -> Vec<T>
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    let bytes = line.as_bytes();
+    let mut result = Vec::new();
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        if !bytes[pos].is_ascii_digit() {
+            pos += 1;
+            continue;
+        }
+
+        let start = if pos > 0 && bytes[pos - 1] == b'-' {
+            pos - 1
+        } else {
+            pos
+        };
+        while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+            pos += 1;
+        }
+
+        let number = &line[start..pos];
+        result.push(
+            number
+                .parse()
+                .unwrap_or_else(|err| panic!("couldn't parse {number}: {err:?}")),
+        );
+    }
+
+    result
+}
+
+/**
+ * Splits the input into blocks separated by blank lines, without the
+ * newlines around them. Expects normalized input (see input::normalize).
+ */
+pub fn blocks(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .split("\n\n")
+        .map(|block| block.trim_matches('\n'))
+        .filter(|block| !block.is_empty())
+}
+
+/**
+ * Reads a rectangle of single digits, one row per line.
+ */
+pub fn grid_of_digits(input: &str) -> Vec<Vec<u8>> {
+    input
+        .lines()
+        .map(|line| {
+            line.bytes()
+                .map(|b| {
+                    assert!(b.is_ascii_digit(), "expected a digit, got {:?}", b as char);
+                    b - b'0'
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_ints() {
+        assert_eq!(ints::<usize>("move 12 from 3 to 9"), vec![12, 3, 9]);
+        assert_eq!(
+            ints::<i32>("Sensor at x=-2, y=15: beacon at x=3-4"),
+            vec![-2, 15, 3, -4]
+        );
+        assert!(ints::<u32>("no numbers - here").is_empty());
+    }
+
+    #[test]
+    fn test_blocks_and_grid() {
+        let input = "12\n34\n\n5\n\n\n67\n";
+        assert_eq!(blocks(input).collect::<Vec<_>>(), vec!["12\n34", "5", "67"]);
+        assert_eq!(grid_of_digits("12\n34\n"), vec![vec![1, 2], vec![3, 4]]);
+    }
+}
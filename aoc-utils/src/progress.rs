@@ -0,0 +1,50 @@
+/*
+ * Progress reporting for the solutions that can take a while.
+ *
+ * The slow loops call `update` now and then with what they're counting,
+ * how many are done and how many there are in total. A closure taking
+ * those three arguments works as a Progress, and NoProgress ignores them.
+ */
+
+pub trait Progress: Sync {
+    fn update(&self, what: &str, done: usize, total: usize);
+}
+
+/**
+ * Reports nothing; what the solutions use when nobody's watching.
+ */
+pub struct NoProgress;
+
+impl Progress for NoProgress {
+    fn update(&self, _what: &str, _done: usize, _total: usize) {}
+}
+
+impl<F: Fn(&str, usize, usize) + Sync> Progress for F {
+    fn update(&self, what: &str, done: usize, total: usize) {
+        self(what, done, total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NoProgress, Progress};
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    fn count_to(total: usize, progress: &dyn Progress) {
+        for done in 1..=total {
+            progress.update("things", done, total);
+        }
+    }
+
+    #[test]
+    fn test_progress() {
+        let calls = AtomicUsize::new(0);
+        count_to(5, &|what: &str, done: usize, total: usize| {
+            assert_eq!((what, total), ("things", 5));
+            assert_eq!(calls.fetch_add(1, Ordering::Relaxed) + 1, done);
+        });
+        assert_eq!(calls.load(Ordering::Relaxed), 5);
+
+        count_to(5, &NoProgress);
+    }
+}
@@ -0,0 +1,340 @@
+/*
+ * Generic graph searches, for the days that would otherwise each grow their
+ * own queue-and-visited-set loop.
+ *
+ * Every search takes a `successors` callback, which lists the nodes reachable
+ * in one step from a node (with their costs, for A*), plus whatever hooks that
+ * kind of search needs: a `prune` callback to skip nodes outright, a
+ * heuristic, or an upper bound. They all return the result along with some
+ * Stats about how much work the search did, which is handy when tuning
+ * heuristics and pruning rules.
+ */
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use crate::hash::{FastMap, FastSet};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    // Nodes whose successors were generated.
+    pub expanded: usize,
+    // Successors generated, including ones that had already been seen.
+    pub generated: usize,
+    // Nodes skipped by the prune callback or the bound.
+    pub pruned: usize,
+    // The most nodes waiting in the queue, stack or heap at once.
+    pub max_frontier: usize,
+}
+
+/**
+ * Every node reachable from `start`, not counting nodes for which `prune`
+ * returns true (or anything only reachable through them).
+ */
+pub fn bfs_reach<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut prune: impl FnMut(&N) -> bool,
+) -> (FastSet<N>, Stats)
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut stats = Stats::default();
+    let mut seen = FastSet::default();
+    let mut queue = VecDeque::new();
+    seen.insert(start.clone());
+    queue.push_back(start);
+
+    while let Some(node) = queue.pop_front() {
+        stats.expanded += 1;
+        for next in successors(&node) {
+            stats.generated += 1;
+            if seen.contains(&next) {
+                continue;
+            }
+            if prune(&next) {
+                stats.pruned += 1;
+                continue;
+            }
+            seen.insert(next.clone());
+            queue.push_back(next);
+        }
+        stats.max_frontier = stats.max_frontier.max(queue.len());
+    }
+
+    (seen, stats)
+}
+
+/**
+ * The shortest path (in steps) from `start` to a node for which `success`
+ * returns true, including both ends.
+ */
+pub fn bfs<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut success: impl FnMut(&N) -> bool,
+) -> (Option<Vec<N>>, Stats)
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut stats = Stats::default();
+    let mut parents: FastMap<N, Option<N>> = FastMap::default();
+    let mut queue = VecDeque::new();
+    parents.insert(start.clone(), None);
+    queue.push_back(start);
+
+    while let Some(node) = queue.pop_front() {
+        if success(&node) {
+            let mut path = vec![node];
+            while let Some(Some(parent)) = parents.get(path.last().unwrap()) {
+                path.push(parent.clone());
+            }
+            path.reverse();
+            return (Some(path), stats);
+        }
+
+        stats.expanded += 1;
+        for next in successors(&node) {
+            stats.generated += 1;
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(node.clone()));
+                queue.push_back(next);
+            }
+        }
+        stats.max_frontier = stats.max_frontier.max(queue.len());
+    }
+
+    (None, stats)
+}
+
+/**
+ * Like bfs_reach(), but depth first, returning the nodes in the order they
+ * were first visited.
+ */
+pub fn dfs<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut prune: impl FnMut(&N) -> bool,
+) -> (Vec<N>, Stats)
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut stats = Stats::default();
+    let mut seen = FastSet::default();
+    let mut order = Vec::new();
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        if !seen.insert(node.clone()) {
+            continue;
+        }
+        stats.expanded += 1;
+
+        // Push in reverse, so the first successor is visited first.
+        let start = stack.len();
+        for next in successors(&node) {
+            stats.generated += 1;
+            if seen.contains(&next) {
+                continue;
+            }
+            if prune(&next) {
+                stats.pruned += 1;
+                continue;
+            }
+            stack.push(next);
+        }
+        stack[start..].reverse();
+        stats.max_frontier = stats.max_frontier.max(stack.len());
+        order.push(node);
+    }
+
+    (order, stats)
+}
+
+/**
+ * The cheapest path from `start` to a node for which `success` returns true,
+ * and its cost. `heuristic` must never overestimate the remaining cost, or
+ * the path found might not be the cheapest.
+ *
+ * Ties between equally promising nodes go to the one furthest along, which
+ * tends to reach the goal sooner.
+ */
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut success: impl FnMut(&N) -> bool,
+) -> (Option<(Vec<N>, C)>, Stats)
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut stats = Stats::default();
+
+    // Every node seen so far, with its parent's index and the cheapest known
+    // cost to get there. The heap refers to nodes by index, so that nodes
+    // don't need to be Ord.
+    let mut nodes: Vec<(N, usize, C)> = vec![(start.clone(), usize::MAX, C::default())];
+    let mut indices: FastMap<N, usize> = FastMap::default();
+    indices.insert(start.clone(), 0);
+
+    let mut frontier = BinaryHeap::new();
+    frontier.push((Reverse(heuristic(&start)), C::default(), 0));
+
+    while let Some((_, cost, index)) = frontier.pop() {
+        // A cheaper way here was found after this entry was pushed.
+        if cost > nodes[index].2 {
+            continue;
+        }
+
+        let node = nodes[index].0.clone();
+        if success(&node) {
+            let mut path = Vec::new();
+            let mut index = index;
+            while index != usize::MAX {
+                path.push(nodes[index].0.clone());
+                index = nodes[index].1;
+            }
+            path.reverse();
+            return (Some((path, cost)), stats);
+        }
+
+        stats.expanded += 1;
+        for (next, step) in successors(&node) {
+            stats.generated += 1;
+            let next_cost = cost + step;
+            let next_index = match indices.get(&next) {
+                Some(&i) if nodes[i].2 <= next_cost => continue,
+                Some(&i) => {
+                    nodes[i].1 = index;
+                    nodes[i].2 = next_cost;
+                    i
+                }
+                None => {
+                    nodes.push((next.clone(), index, next_cost));
+                    indices.insert(next.clone(), nodes.len() - 1);
+                    nodes.len() - 1
+                }
+            };
+            let estimate = next_cost + heuristic(&next);
+            frontier.push((Reverse(estimate), next_cost, next_index));
+        }
+        stats.max_frontier = stats.max_frontier.max(frontier.len());
+    }
+
+    (None, stats)
+}
+
+/**
+ * The node with the largest `value` in the tree grown from `start`, and that
+ * value.
+ *
+ * `bound` gives an upper limit on the value of a node and everything below
+ * it; subtrees that can't beat the best value found so far are skipped. There
+ * is no visited set, so this is for searches where the same node can't be
+ * reached two different ways (or where that's rare enough not to matter).
+ */
+pub fn branch_and_bound<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut value: impl FnMut(&N) -> C,
+    mut bound: impl FnMut(&N) -> C,
+) -> ((N, C), Stats)
+where
+    N: Clone,
+    C: Copy + Ord,
+    I: IntoIterator<Item = N>,
+{
+    let mut stats = Stats::default();
+    let mut best = (start.clone(), value(&start));
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        // The best value may have improved since this node was pushed.
+        if bound(&node) <= best.1 {
+            stats.pruned += 1;
+            continue;
+        }
+
+        stats.expanded += 1;
+        for next in successors(&node) {
+            stats.generated += 1;
+            let next_value = value(&next);
+            if next_value > best.1 {
+                best = (next.clone(), next_value);
+            }
+            stack.push(next);
+        }
+        stats.max_frontier = stats.max_frontier.max(stack.len());
+    }
+
+    (best, stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 3 -> 4, with shortcuts 0 -> 2 and 1 -> 4.
+    fn edges(node: &u32) -> Vec<u32> {
+        match node {
+            0 => vec![1, 2],
+            1 => vec![2, 4],
+            2 => vec![3],
+            3 => vec![4],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_unweighted() {
+        let (reached, stats) = bfs_reach(0, edges, |_| false);
+        assert_eq!(reached.len(), 5);
+        assert_eq!(stats.expanded, 5);
+
+        let (reached, stats) = bfs_reach(0, edges, |&n| n == 2);
+        assert_eq!(reached, FastSet::from_iter([0, 1, 4]));
+        assert_eq!(stats.pruned, 2);
+
+        let (path, _) = bfs(0, edges, |&n| n == 4);
+        assert_eq!(path, Some(vec![0, 1, 4]));
+        assert_eq!(bfs(2, edges, |&n| n == 0).0, None);
+
+        let (order, _) = dfs(0, edges, |_| false);
+        assert_eq!(order, vec![0, 1, 2, 3, 4]);
+        let (order, _) = dfs(0, edges, |&n| n == 1);
+        assert_eq!(order, vec![0, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_weighted() {
+        // The shortcut 1 -> 4 is expensive, so it's cheaper to go the long way.
+        let weighted = |node: &u32| {
+            edges(node)
+                .into_iter()
+                .map(|next| (next, if (*node, next) == (1, 4) { 10 } else { 1 }))
+                .collect::<Vec<_>>()
+        };
+        let (result, _) = astar(0, weighted, |&n| (4 - n) / 2, |&n| n == 4);
+        assert_eq!(result, Some((vec![0, 2, 3, 4], 3)));
+
+        // The longest path: the value is the depth, and nothing is deeper
+        // than 4 steps.
+        let deeper = |&(node, depth): &(u32, u32)| {
+            edges(&node).into_iter().map(move |next| (next, depth + 1))
+        };
+        let ((_, depth), stats) = branch_and_bound(
+            (0, 0),
+            deeper,
+            |&(_, depth)| depth,
+            |&(node, depth)| depth + 4 - node,
+        );
+        assert_eq!(depth, 4);
+        assert!(stats.pruned > 0);
+    }
+}
@@ -0,0 +1,92 @@
+/*
+ * Times comparing day 13's packets the old way, turning an integer into a
+ * freshly allocated one-element list whenever it meets a list, against
+ * Data's own comparison, which borrows the integer as a slice of one:
+ *
+ *     cargo bench --bench day13
+ *
+ * This runs on the real input (input/2022/day13.txt), sorting all of its
+ * packets along with the dividers many times over.
+ */
+use std::cmp::Ordering;
+use std::fs;
+use std::iter::zip;
+use std::time::{Duration, Instant};
+
+use advent_of_code_2022::y2022::day13::{self, Data};
+
+const RUNS: usize = 5;
+const SORTS: usize = 200;
+
+fn best_of<T>(runs: usize, run: impl Fn() -> T) -> (T, Duration) {
+    let mut best = None;
+    let mut answer = None;
+    for _ in 0..runs {
+        let start = Instant::now();
+        answer = Some(run());
+        let elapsed = start.elapsed();
+        best = Some(best.map_or(elapsed, |best: Duration| best.min(elapsed)));
+    }
+    (answer.unwrap(), best.unwrap())
+}
+
+fn allocating_cmp(lhs: &Data, rhs: &Data) -> Ordering {
+    match (lhs, rhs) {
+        (Data::Int(lhs), Data::Int(rhs)) => lhs.cmp(rhs),
+        (Data::Int(value), Data::List(_)) => allocating_cmp(&Data::list_of(*value), rhs),
+        (Data::List(_), Data::Int(value)) => allocating_cmp(lhs, &Data::list_of(*value)),
+        (Data::List(lhs), Data::List(rhs)) => zip(lhs, rhs)
+            .map(|(l, r)| allocating_cmp(l, r))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| lhs.len().cmp(&rhs.len())),
+    }
+}
+
+/*
+ * Sorts every packet, plus the dividers, and finds the decoder key: part 2
+ * done the slow way, so that there are plenty of comparisons to time.
+ */
+fn decoder_key(packets: &[&Data], cmp: impl Fn(&Data, &Data) -> Ordering) -> usize {
+    let dividers = [Data::list_of(2), Data::list_of(6)];
+    let mut key = 0;
+    for _ in 0..SORTS {
+        let mut sorted: Vec<&Data> = packets.iter().copied().chain(&dividers).collect();
+        sorted.sort_by(|lhs, rhs| cmp(lhs, rhs));
+        key = dividers
+            .iter()
+            .map(|divider| 1 + sorted.iter().position(|packet| *packet == divider).unwrap())
+            .product();
+    }
+    key
+}
+
+fn main() {
+    let Ok(input) = fs::read_to_string("input/2022/day13.txt") else {
+        eprintln!("skipping: real input input/2022/day13.txt not found");
+        return;
+    };
+    let pairs = day13::pairs(&input).unwrap();
+    let packets: Vec<&Data> = pairs
+        .iter()
+        .flat_map(|(left, right)| [left, right])
+        .collect();
+
+    let (allocating_key, allocating) = best_of(RUNS, || decoder_key(&packets, allocating_cmp));
+    let (borrowing_key, borrowing) = best_of(RUNS, || {
+        decoder_key(&packets, |lhs, rhs| lhs.partial_cmp(rhs).unwrap())
+    });
+    assert_eq!(allocating_key, borrowing_key);
+    assert_eq!(borrowing_key, day13::part2(&pairs));
+
+    println!(
+        "{:>8} {:>12} {:>12} {:>8}",
+        "packets", "allocating", "borrowing", "speedup"
+    );
+    println!(
+        "{:>8} {:>12.2?} {:>12.2?} {:>7.2}x",
+        packets.len(),
+        allocating,
+        borrowing,
+        allocating.as_secs_f64() / borrowing.as_secs_f64()
+    );
+}
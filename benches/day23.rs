@@ -0,0 +1,48 @@
+/*
+ * Times day 23 with and without the parallel rounds, on square fields of
+ * random elves. The real inputs are too small for the threads to make much
+ * difference, so this goes up to fields 50 times the size:
+ *
+ *     cargo bench --features parallel --bench day23
+ *
+ * Part 2 would take thousands of rounds to settle on the big fields, so
+ * this only times part 1 (ten rounds with every elf active).
+ */
+use std::time::{Duration, Instant};
+
+use advent_of_code_2022::y2022::day23;
+
+const RUNS: usize = 5;
+
+fn best_of<T>(runs: usize, run: impl Fn() -> T) -> (T, Duration) {
+    let mut best = None;
+    let mut answer = None;
+    for _ in 0..runs {
+        let start = Instant::now();
+        answer = Some(run());
+        let elapsed = start.elapsed();
+        best = Some(best.map_or(elapsed, |best: Duration| best.min(elapsed)));
+    }
+    (answer.unwrap(), best.unwrap())
+}
+
+fn main() {
+    println!(
+        "{:>9} {:>8} {:>12} {:>12} {:>8}",
+        "size", "elves", "serial", "parallel", "speedup"
+    );
+    for size in [70, 150, 250, 500] {
+        let elves = day23::random_elves(size, 50, size as u64);
+        let (serial_answer, serial) = best_of(RUNS, || day23::part1(&elves));
+        let (parallel_answer, parallel) = best_of(RUNS, || day23::part1_parallel(&elves));
+        assert_eq!(serial_answer, parallel_answer);
+        println!(
+            "{:>9} {:>8} {:>12.2?} {:>12.2?} {:>7.2}x",
+            format!("{size}x{size}"),
+            elves.len(),
+            serial,
+            parallel,
+            serial.as_secs_f64() / parallel.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,58 @@
+/*
+ * Times adding up SNAFU numbers the old way, parsing each line into its own
+ * BalancedQuinary and summing them by value, against day25::sum_lines(),
+ * which reads every line into one reused buffer and adds it in place:
+ *
+ *     cargo bench --bench day25
+ *
+ * The real input is only about a hundred numbers, so this uses up to a
+ * million random ones.
+ */
+use std::time::{Duration, Instant};
+
+use advent_of_code_2022::y2022::day25::{self, BalancedQuinary, Parsing};
+
+const RUNS: usize = 5;
+
+fn best_of<T>(runs: usize, run: impl Fn() -> T) -> (T, Duration) {
+    let mut best = None;
+    let mut answer = None;
+    for _ in 0..runs {
+        let start = Instant::now();
+        answer = Some(run());
+        let elapsed = start.elapsed();
+        best = Some(best.map_or(elapsed, |best: Duration| best.min(elapsed)));
+    }
+    (answer.unwrap(), best.unwrap())
+}
+
+fn sum_owned(input: &str) -> String {
+    let total: BalancedQuinary = input
+        .lines()
+        .map(|line| line.parse::<BalancedQuinary>().unwrap())
+        .sum();
+    String::from(total)
+}
+
+fn main() {
+    println!(
+        "{:>9} {:>12} {:>12} {:>8}",
+        "numbers", "owned", "in place", "speedup"
+    );
+    for count in [1_000, 10_000, 100_000, 1_000_000] {
+        let input = day25::random_snafu(count, 20, count as u64);
+        let (owned_answer, owned) = best_of(RUNS, || sum_owned(&input));
+        let (in_place_answer, in_place) = best_of(RUNS, || {
+            let total = day25::sum_lines(&input, Parsing::Strict).unwrap();
+            String::from(total)
+        });
+        assert_eq!(owned_answer, in_place_answer);
+        println!(
+            "{:>9} {:>12.2?} {:>12.2?} {:>7.2}x",
+            count,
+            owned,
+            in_place,
+            owned.as_secs_f64() / in_place.as_secs_f64()
+        );
+    }
+}
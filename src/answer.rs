@@ -0,0 +1,91 @@
+/*
+ * The days' answers come in a handful of integer types, plus a couple of
+ * strings (days 5 and 25). The registry hands them all back as an Answer,
+ * so that callers can still tell numbers from text without every one of
+ * them having to know which day returns what.
+ *
+ * Answers print (and compare against strings) the way the puzzle wants
+ * them typed in. With the `serde` feature they serialize as a plain JSON
+ * number or string.
+ */
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(untagged))]
+pub enum Answer {
+    Int(i64),
+    UInt(u64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(value) => write!(f, "{value}"),
+            Answer::UInt(value) => write!(f, "{value}"),
+            Answer::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        let mut rest = Unprinted(other);
+        fmt::write(&mut rest, format_args!("{self}")).is_ok() && rest.0.is_empty()
+    }
+}
+
+/*
+ * The part of a string that the answer hasn't printed yet, so that it can
+ * be compared without printing it into a String first. Printing anything
+ * that doesn't come next is an error.
+ */
+struct Unprinted<'a>(&'a str);
+
+impl fmt::Write for Unprinted<'_> {
+    fn write_str(&mut self, printed: &str) -> fmt::Result {
+        self.0 = self.0.strip_prefix(printed).ok_or(fmt::Error)?;
+        Ok(())
+    }
+}
+
+macro_rules! answer_from {
+    ($variant:ident($inner:ty): $($from:ty),*) => {
+        $(
+            impl From<$from> for Answer {
+                fn from(value: $from) -> Self {
+                    Answer::$variant(value as $inner)
+                }
+            }
+        )*
+    };
+}
+
+answer_from!(Int(i64): i32, i64, isize);
+answer_from!(UInt(u64): u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Answer;
+
+    #[test]
+    fn test_answers() {
+        assert_eq!(Answer::from(-3i32), Answer::Int(-3));
+        assert_eq!(Answer::from(12usize), Answer::UInt(12));
+        assert_eq!(Answer::from(String::from("CMZ")), "CMZ");
+        assert_eq!(Answer::Int(-3).to_string(), "-3");
+        assert_eq!(Answer::UInt(12), "12");
+        assert_ne!(Answer::UInt(12), "012");
+        assert_ne!(Answer::UInt(12), "1");
+        assert_ne!(Answer::Int(-3), "-30");
+    }
+}
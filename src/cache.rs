@@ -0,0 +1,136 @@
+/*
+ * A disk cache for the days with slow generators, so that running them
+ * repeatedly (while benchmarking or debugging a part) only parses the input
+ * once. The parsed value is stored with bincode, named after the day and a
+ * hash of the input:
+ *
+ *     target/aoc-cache/day16-0123456789abcdef.bin
+ *
+ * It's off unless asked for: `aoc22 --cache` turns it on with enable(), and
+ * so does AOC_CACHE=1 for everything else (the golden tests, say), since
+ * cargo-aoc owns its command line. Anything that goes wrong with the cache
+ * (a missing, stale or unreadable file, or a read-only target directory)
+ * just falls back to parsing.
+ */
+use std::collections::hash_map::DefaultHasher;
+use std::env;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/**
+ * Turns the cache on for the rest of the process.
+ */
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed) || matches!(env::var("AOC_CACHE").as_deref(), Ok("1"))
+}
+
+/**
+ * The cache directory: aoc-cache inside cargo's target directory.
+ */
+pub fn cache_dir() -> PathBuf {
+    env::var_os("CARGO_TARGET_DIR")
+        .map_or_else(|| PathBuf::from("target"), PathBuf::from)
+        .join("aoc-cache")
+}
+
+/**
+ * Runs `generator` on the input, or loads its result from the cache if
+ * caching is enabled and this exact input has been parsed before. Errors
+ * aren't cached, so a bad input fails every time.
+ */
+pub fn cached<T, E, F>(day: u8, input: &str, generator: F) -> Result<T, E>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce(&str) -> Result<T, E>,
+{
+    if enabled() {
+        cached_in(&cache_dir(), day, input, generator)
+    } else {
+        generator(input)
+    }
+}
+
+fn cached_in<T, E, F>(dir: &Path, day: u8, input: &str, generator: F) -> Result<T, E>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce(&str) -> Result<T, E>,
+{
+    // DefaultHasher isn't guaranteed to be stable across Rust versions,
+    // but the worst that can happen is a cache miss.
+    let mut hasher = DefaultHasher::new();
+    input.hash(&mut hasher);
+    let path = dir.join(format!("day{day}-{:016x}.bin", hasher.finish()));
+
+    if let Some(parsed) = fs::read(&path)
+        .ok()
+        .and_then(|bytes| bincode::deserialize(&bytes).ok())
+    {
+        return Ok(parsed);
+    }
+
+    let parsed = generator(input)?;
+    if let Ok(bytes) = bincode::serialize(&parsed) {
+        let _ = fs::create_dir_all(dir).and_then(|_| fs::write(&path, bytes));
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cached_in() {
+        let dir = env::temp_dir().join(format!("aoc-cache-test-{}", std::process::id()));
+
+        let first: Result<Vec<u32>, ()> = cached_in(&dir, 1, "1\n2\n", |_| Ok(vec![1, 2]));
+        let second: Result<Vec<u32>, ()> =
+            cached_in(&dir, 1, "1\n2\n", |_| panic!("should be cached"));
+        assert_eq!(first, second);
+
+        // A different input (or day) is a different entry.
+        let other: Result<Vec<u32>, ()> = cached_in(&dir, 1, "3\n", |_| Ok(vec![3]));
+        assert_eq!(other, Ok(vec![3]));
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+
+        // Errors aren't stored.
+        let failed: Result<Vec<u32>, &str> = cached_in(&dir, 1, "x\n", |_| Err("bad"));
+        assert_eq!(failed, Err("bad"));
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_round_trip() {
+        use crate::utils::testing::read_example;
+        use crate::y2022::{day16, day22};
+
+        let (Some(valves), Some(map)) = (read_example("day16.txt"), read_example("day22.txt"))
+        else {
+            return;
+        };
+        let dir = env::temp_dir().join(format!("aoc-cache-round-trip-{}", std::process::id()));
+
+        // Once from the generator, and once back out of the cache.
+        for _ in 0..2 {
+            let graph = cached_in(&dir, 16, &valves, day16::generator).unwrap();
+            assert_eq!(day16::part1(&graph), 1651);
+            let parsed = cached_in(&dir, 22, &map, |map| Ok::<_, ()>(day22::generator(map)));
+            assert_eq!(day22::part1(&parsed.unwrap()), 6032);
+        }
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
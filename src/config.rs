@@ -0,0 +1,239 @@
+/*
+ * Optional overrides for the per-day constants that depend on the input,
+ * read from an `aoc.toml` file so they can be changed without recompiling:
+ *
+ *     [day11]
+ *     part1_rounds = 20
+ *     part2_rounds = 10000
+ *
+ *     [day15]
+ *     row = 10
+ *     max_coord = 20
+ *
+ *     [day19]
+ *     part1_minutes = 24
+ *     part2_minutes = 32
+ *
+ * Every key is optional; anything missing keeps the puzzle's value.
+ */
+use std::{fs, io, path::Path, str::FromStr};
+
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::registry::{cached, Solution};
+use crate::utils::cancel::CancelToken;
+use crate::utils::progress::{NoProgress, Progress};
+use crate::utils::toml_lite::{self, Entry};
+use crate::y2022::{day11, day15, day16, day19, day24};
+
+pub const DEFAULT_PATH: &str = "aoc.toml";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    pub day11_part1_rounds: usize,
+    pub day11_part2_rounds: usize,
+    pub day15_row: i32,
+    pub day15_max_coord: i32,
+    pub day19_part1_minutes: u32,
+    pub day19_part2_minutes: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            day11_part1_rounds: 20,
+            day11_part2_rounds: 10_000,
+            day15_row: 2_000_000,
+            day15_max_coord: 4_000_000,
+            day19_part1_minutes: 24,
+            day19_part2_minutes: 32,
+        }
+    }
+}
+
+impl Config {
+    pub fn parse(text: &str) -> Result<Self, AocError> {
+        let mut config = Config::default();
+        for entry in toml_lite::parse(text)? {
+            config.set(&entry)?;
+        }
+        Ok(config)
+    }
+
+    /**
+     * Applies a single setting, as parse() does for each line.
+     */
+    pub(crate) fn set(&mut self, entry: &Entry) -> Result<(), AocError> {
+        let line = entry.line;
+        match (entry.section.as_str(), entry.key.as_str()) {
+            ("day11", "part1_rounds") => self.day11_part1_rounds = parse_value(&entry.value, line)?,
+            ("day11", "part2_rounds") => self.day11_part2_rounds = parse_value(&entry.value, line)?,
+            ("day15", "row") => self.day15_row = parse_value(&entry.value, line)?,
+            ("day15", "max_coord") => self.day15_max_coord = parse_value(&entry.value, line)?,
+            ("day19", "part1_minutes") => {
+                self.day19_part1_minutes = parse_value(&entry.value, line)?
+            }
+            ("day19", "part2_minutes") => {
+                self.day19_part2_minutes = parse_value(&entry.value, line)?
+            }
+            (section, key) => {
+                return Err(AocError::parse(
+                    line,
+                    format!("unknown setting {key} in [{section}]"),
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    /**
+     * Loads the config at `path`. A missing file just means the defaults.
+     */
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AocError> {
+        match fs::read_to_string(path.as_ref()) {
+            Ok(text) => Config::parse(&text),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(err) => Err(AocError::Io {
+                path: path.as_ref().display().to_string(),
+                message: err.to_string(),
+            }),
+        }
+    }
+
+    /**
+     * Runs a solution, using this config's parameters for the days that
+     * have any.
+     */
+    pub fn run(&self, solution: &Solution, input: &str) -> Result<Answer, AocError> {
+        self.run_with(solution, input, &NoProgress, &CancelToken::new())
+    }
+
+    /**
+     * The same as run(), but the slowest solutions report how far along
+     * they are to `progress` (day 15 part 2, day 16 part 2 and day 19), and
+     * the long searches (days 16, 19 and 24) give up with AocError::Cancelled
+     * if `cancel` is cancelled. Other solutions run to completion regardless.
+     */
+    pub fn run_with(
+        &self,
+        solution: &Solution,
+        input: &str,
+        progress: &dyn Progress,
+        cancel: &CancelToken,
+    ) -> Result<Answer, AocError> {
+        let answer = match (solution.day, solution.part) {
+            // The parameters are all for 2022's puzzles.
+            _ if solution.year != 2022 => solution.run(input)?,
+            (11, 1) => day11::part1_with(input, self.day11_part1_rounds).into(),
+            (11, 2) => day11::part2_with(input, self.day11_part2_rounds).into(),
+            (15, 1) => day15::part1_with(&day15::generator(input), self.day15_row).into(),
+            (15, 2) => {
+                let pairs = day15::generator(input);
+                day15::part2_with_progress(&pairs, self.day15_max_coord, progress).into()
+            }
+            (16, 1) => {
+                let graph = cached(16, input, day16::generator)?;
+                day16::part1_cancellable(&graph, cancel)?.into()
+            }
+            (16, 2) => {
+                let graph = cached(16, input, day16::generator)?;
+                day16::part2_cancellable(&graph, progress, cancel)?.into()
+            }
+            (19, 1) => {
+                let factories = day19::create_factories(input)?;
+                day19::part1_cancellable(&factories, self.day19_part1_minutes, progress, cancel)?
+                    .into()
+            }
+            (19, 2) => {
+                let factories = day19::create_factories(input)?;
+                day19::part2_cancellable(&factories, self.day19_part2_minutes, progress, cancel)?
+                    .into()
+            }
+            (24, 1) => day24::part1_cancellable(&day24::generator(input)?, cancel)?.into(),
+            (24, 2) => day24::part2_cancellable(&day24::generator(input)?, cancel)?.into(),
+            _ => solution.run(input)?,
+        };
+        Ok(answer)
+    }
+}
+
+fn parse_value<T: FromStr>(value: &str, line: usize) -> Result<T, AocError> {
+    value
+        .parse()
+        .map_err(|_| AocError::parse(line, format!("invalid value: {value}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::find;
+    use crate::utils::testing::read_example;
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse("[day15]\nrow = 10\nmax_coord = 20\n").unwrap();
+        assert_eq!(
+            config,
+            Config {
+                day15_row: 10,
+                day15_max_coord: 20,
+                ..Config::default()
+            }
+        );
+
+        assert_eq!(
+            Config::parse("[day15]\nrows = 10\n").unwrap_err(),
+            AocError::parse(2, "unknown setting rows in [day15]")
+        );
+        assert_eq!(
+            Config::parse("[day11]\npart1_rounds = lots\n").unwrap_err(),
+            AocError::parse(2, "invalid value: lots")
+        );
+    }
+
+    #[test]
+    fn test_run() {
+        let Some(input) = read_example("day15.txt") else {
+            return;
+        };
+        let config = Config::parse("[day15]\nrow = 10\nmax_coord = 20\n").unwrap();
+        assert_eq!(config.run(find(15, 1).unwrap(), &input).unwrap(), "26");
+        assert_eq!(
+            config.run(find(15, 2).unwrap(), &input).unwrap(),
+            "56000011"
+        );
+    }
+
+    #[test]
+    fn test_run_with_progress() {
+        let Some(input) = read_example("day15.txt") else {
+            return;
+        };
+        let config = Config::parse("[day15]\nmax_coord = 20\n").unwrap();
+        let last = std::sync::Mutex::new(None);
+        let progress = |what: &str, done: usize, total: usize| {
+            *last.lock().unwrap() = Some((what.to_string(), done, total));
+        };
+        let answer = config.run_with(find(15, 2).unwrap(), &input, &progress, &CancelToken::new());
+        assert_eq!(answer, Ok(Answer::UInt(56000011)));
+        assert_eq!(
+            last.into_inner().unwrap(),
+            Some(("rows scanned".to_string(), 21, 21))
+        );
+    }
+
+    #[test]
+    fn test_run_cancelled() {
+        let Some(input) = read_example("day16.txt") else {
+            return;
+        };
+        let config = Config::default();
+        let cancel = CancelToken::new();
+        let run = |part| config.run_with(find(16, part).unwrap(), &input, &NoProgress, &cancel);
+        assert_eq!(run(2), Ok(Answer::UInt(1707)));
+
+        cancel.cancel();
+        assert_eq!(run(1), Err(AocError::Cancelled));
+        assert_eq!(run(2), Err(AocError::Cancelled));
+    }
+}
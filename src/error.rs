@@ -0,0 +1,36 @@
+/*
+ * Errors for the solutions that validate their input, instead of panicking
+ * (or worse, silently ignoring the bad parts).
+ */
+use thiserror::Error;
+
+use crate::utils::cancel::Cancelled;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AocError {
+    // Line numbers are 1-indexed, to match what an editor shows.
+    #[error("line {line}: {message}")]
+    Parse { line: usize, message: String },
+
+    #[error("{path}: {message}")]
+    Io { path: String, message: String },
+
+    // A search gave up because its CancelToken was cancelled.
+    #[error("cancelled")]
+    Cancelled,
+}
+
+impl AocError {
+    pub fn parse(line: usize, message: impl Into<String>) -> Self {
+        AocError::Parse {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<Cancelled> for AocError {
+    fn from(_: Cancelled) -> Self {
+        AocError::Cancelled
+    }
+}
@@ -0,0 +1,70 @@
+/*
+ * Runs the solutions against the puzzles' worked examples, with the
+ * expected answers kept in a table (input/2022/test/answers.toml, in the
+ * same format as the golden answers) rather than in each day's tests:
+ *
+ *     [day15]
+ *     row = 10
+ *     part1 = 26
+ *
+ * Keys other than partN are settings for the day, the same as in aoc.toml,
+ * for the examples that are smaller than the real puzzle. Days whose
+ * example isn't checked in are skipped.
+ */
+use crate::config::Config;
+use crate::registry::find;
+use crate::utils::testing::read_example;
+use crate::utils::toml_lite;
+
+/**
+ * Checks every answer in the table. All mismatches are reported together.
+ */
+#[test]
+fn example_answers() {
+    let Some(table) = read_example("answers.toml") else {
+        return;
+    };
+    let entries =
+        toml_lite::parse(&table).unwrap_or_else(|err| panic!("malformed answers.toml: {err}"));
+
+    // The settings apply to their own day only, so one Config holds them all.
+    let mut config = Config::default();
+    let mut answers = Vec::new();
+    for entry in entries {
+        match entry.key.strip_prefix("part") {
+            Some(part) => answers.push((entry.section, part.to_string(), entry.value)),
+            None => config
+                .set(&entry)
+                .unwrap_or_else(|err| panic!("in answers.toml: {err}")),
+        }
+    }
+
+    let mut failures = Vec::new();
+    for (day, part, expected) in answers {
+        let name = format!("{day} part{part}");
+        let solution = day
+            .strip_prefix("day")
+            .and_then(|day| day.parse().ok())
+            .zip(part.parse().ok())
+            .and_then(|(day, part)| find(day, part));
+        let Some(solution) = solution else {
+            failures.push(format!("{name}: no such solution"));
+            continue;
+        };
+        let Some(input) = read_example(&format!("{day}.txt")) else {
+            continue;
+        };
+
+        match config.run(solution, &input) {
+            Ok(actual) if actual == expected.as_str() => {}
+            Ok(actual) => failures.push(format!("{name}: expected {expected}, got {actual}")),
+            Err(err) => failures.push(format!("{name}: {err}")),
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "example answers differ:\n{}",
+        failures.join("\n")
+    );
+}
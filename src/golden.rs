@@ -0,0 +1,93 @@
+/*
+ * Regression tests that run every day against the real puzzle inputs and
+ * compare the results against a file of known-correct answers.
+ *
+ * These are slow (several seconds in release mode, much longer in debug) and
+ * depend on personal inputs, so they only run when AOC_GOLDEN is set:
+ *
+ *     AOC_GOLDEN=1 cargo test --release golden
+ *
+ * The answers live in input/<year>/answers.toml, in a small subset of TOML:
+ *
+ *     [day1]
+ *     part1 = 12345
+ *     part2 = "ABCDE"
+ *
+ * Later years scaffolded by new-day are checked the same way, once their
+ * input files have something in them.
+ */
+use std::{collections::HashMap, env, fs};
+
+use crate::registry::SOLUTIONS;
+use crate::utils::toml_lite;
+
+fn parse_answers(text: &str) -> HashMap<(String, String), String> {
+    toml_lite::parse(text)
+        .unwrap_or_else(|err| panic!("malformed answers.toml: {err}"))
+        .into_iter()
+        .map(|entry| ((entry.section, entry.key), entry.value))
+        .collect()
+}
+
+/**
+ * Runs every registered solution against its real input and checks it
+ * against the recorded answer. All mismatches are reported together.
+ */
+#[test]
+fn golden_answers() {
+    if env::var_os("AOC_GOLDEN").is_none() {
+        return;
+    }
+
+    let mut answers_by_year = HashMap::new();
+    let mut failures = Vec::new();
+    for solution in SOLUTIONS {
+        let input_dir = format!("input/{}", solution.year);
+        let day = format!("day{}", solution.day);
+        let part = format!("part{}", solution.part);
+
+        let input_path = format!("{input_dir}/{day}.txt");
+        let input = match fs::read_to_string(&input_path) {
+            // new-day leaves an empty file to paste the input into.
+            Ok(input) if !input.is_empty() => input,
+            _ => {
+                eprintln!("skipping: real input {input_path} not found");
+                continue;
+            }
+        };
+        let answers = answers_by_year.entry(solution.year).or_insert_with(|| {
+            let answers_path = format!("{input_dir}/answers.toml");
+            fs::read_to_string(&answers_path)
+                .map(|text| parse_answers(&text))
+                .unwrap_or_else(|_| panic!("missing {answers_path}"))
+        });
+        let name = format!("{} {day} {part}", solution.year);
+
+        #[cfg(not(feature = "alloc-metrics"))]
+        let actual = solution.run(&input);
+        #[cfg(feature = "alloc-metrics")]
+        let actual = {
+            let (actual, stats) = solution.run_measured(&input);
+            eprintln!("{name}: {stats}");
+            actual
+        };
+        let actual = match actual {
+            Ok(actual) => actual,
+            Err(err) => {
+                failures.push(format!("{name}: {err}"));
+                continue;
+            }
+        };
+        match answers.get(&(day.clone(), part.clone())) {
+            Some(expected) if actual == expected.as_str() => {}
+            Some(expected) => failures.push(format!("{name}: expected {expected}, got {actual}")),
+            None => failures.push(format!("{name}: no answer recorded (got {actual})")),
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "golden answers differ:\n{}",
+        failures.join("\n")
+    );
+}
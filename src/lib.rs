@@ -1,32 +1,51 @@
+// The std feature is on by default. Without it, only the modules that
+// don't need std (just core + alloc) are built.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
 extern crate aoc_runner;
 
+#[cfg(feature = "std")]
 #[macro_use]
 extern crate aoc_runner_derive;
 
-pub mod day1;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod day14;
-pub mod day15;
-pub mod day16;
-pub mod day17;
-pub mod day18;
-pub mod day19;
-pub mod day2;
-pub mod day20;
-pub mod day21;
-pub mod day22;
-pub mod day23;
-pub mod day24;
-pub mod day25;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
+// The 2022 days are the crate's main API, so they're at the top level too
+// (advent_of_code_2022::day1 and so on).
+#[cfg(feature = "std")]
+pub use y2022::{
+    day1, day10, day11, day12, day13, day14, day15, day16, day17, day18, day19, day20, day21,
+    day22, day23, day24, day5, day6, day7, day8, day9,
+};
+pub use y2022::{day2, day25, day3, day4};
+
+#[cfg(feature = "alloc-metrics")]
+pub mod alloc_metrics;
+#[cfg(feature = "std")]
+pub mod answer;
+#[cfg(feature = "serde")]
+pub mod cache;
+#[cfg(feature = "std")]
+pub mod config;
+#[cfg(feature = "std")]
+pub mod error;
+pub mod prelude;
+#[cfg(feature = "std")]
+pub mod registry;
+pub mod utils;
+pub mod y2022;
+
+#[cfg(feature = "pyo3")]
+mod python;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(all(test, feature = "std"))]
+mod examples;
+#[cfg(all(test, feature = "std"))]
+mod golden;
 
+#[cfg(feature = "std")]
 aoc_lib! { year = 2022 }
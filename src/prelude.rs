@@ -0,0 +1,46 @@
+/*!
+ * The reusable parts of the crate, for use outside of cargo-aoc.
+ *
+ * Everything exported here (along with each day's `generator`, `part1`
+ * and `part2` functions) is considered public API, and won't change
+ * without a version bump.
+ *
+ * ```
+ * use advent_of_code_2022::prelude::*;
+ *
+ * // Solutions can be looked up by (day, part) and run on the raw input...
+ * let answer = find(2, 1).unwrap().run("A Y\nB X\nC Z\n").unwrap();
+ * assert_eq!(answer, Answer::UInt(15));
+ * assert_eq!(answer.to_string(), "15");
+ *
+ * // ...or called directly.
+ * assert_eq!(advent_of_code_2022::day2::part2("A Y\nB X\nC Z\n"), 12);
+ *
+ * let mut covered: IntervalSet<i32> = [Interval::new(1, 3), Interval::new(4, 6)]
+ *     .into_iter()
+ *     .collect();
+ * covered.insert(Interval::new(10, 12));
+ * assert_eq!(covered.len(), 9);
+ *
+ * let grid = Grid2D::parse("#..\n.#.\n", |c| c == '#').unwrap();
+ * let step = Vec2::new(0, 0).step(Direction::Right.turn_right());
+ * assert_eq!(grid.get(step.y as usize, step.x as usize), Some(&false));
+ * ```
+ */
+pub use crate::utils::bitset::Bitset;
+pub use crate::utils::geometry::{Direction, Vec2, Vec3};
+pub use crate::utils::grid::Grid2D;
+pub use crate::utils::input::normalize;
+pub use crate::utils::interval::{Interval, IntervalSet};
+pub use crate::utils::neighbors::{neighbors3d, Connectivity, Point3};
+
+#[cfg(feature = "std")]
+pub use crate::answer::Answer;
+#[cfg(feature = "std")]
+pub use crate::error::AocError;
+#[cfg(feature = "std")]
+pub use crate::registry::{find, find_in, Solution, SOLUTIONS};
+#[cfg(feature = "std")]
+pub use crate::utils::hash::{FastMap, FastSet};
+#[cfg(feature = "std")]
+pub use crate::utils::memo::Memo;
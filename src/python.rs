@@ -0,0 +1,173 @@
+/*
+ * Python bindings for the solutions and a few of the reusable types.
+ * Build and install into the current virtualenv with:
+ *
+ *     maturin develop --features pyo3
+ *
+ * and then from Python:
+ *
+ *     import advent_of_code_2022 as aoc
+ *     aoc.solve(1, 1, open("input/2022/day1.txt").read())
+ */
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::{
+    answer::Answer,
+    registry,
+    utils::{
+        grid::Grid2D,
+        interval::{Interval, IntervalSet},
+    },
+};
+
+impl IntoPy<PyObject> for Answer {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        match self {
+            Answer::Int(value) => value.into_py(py),
+            Answer::UInt(value) => value.into_py(py),
+            Answer::Text(text) => text.into_py(py),
+        }
+    }
+}
+
+/**
+ * Runs one part of one day against the given input, returning the answer
+ * as an int, or a str for the days whose answers are text.
+ */
+#[pyfunction]
+fn solve(day: u8, part: u8, input: &str) -> PyResult<Answer> {
+    let solution = registry::find(day, part)
+        .ok_or_else(|| PyValueError::new_err(format!("no solution for day {day} part {part}")))?;
+    solution
+        .run(input)
+        .map_err(|err| PyValueError::new_err(format!("day {day}: {err}")))
+}
+
+/**
+ * A set of integers stored as disjoint closed intervals.
+ */
+#[pyclass(name = "IntervalSet")]
+#[derive(Default)]
+struct PyIntervalSet {
+    inner: IntervalSet<i64>,
+}
+
+#[pymethods]
+impl PyIntervalSet {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+     * Adds the closed interval [start, end] to the set.
+     */
+    fn insert(&mut self, start: i64, end: i64) {
+        self.inner.insert(Interval::new(start, end));
+    }
+
+    fn intervals(&self) -> Vec<(i64, i64)> {
+        self.inner
+            .intervals()
+            .iter()
+            .map(|int| (int.start, int.end))
+            .collect()
+    }
+
+    /**
+     * The number of integers in the set.
+     */
+    fn count(&self) -> i64 {
+        self.inner.len()
+    }
+
+    fn __contains__(&self, value: i64) -> bool {
+        self.inner.contains(value)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("IntervalSet({:?})", self.intervals())
+    }
+}
+
+/**
+ * A rectangular grid of characters, read one row per line. Positions are
+ * (row, col) tuples with (0, 0) at the top left.
+ */
+#[pyclass(name = "Grid2D")]
+struct PyGrid2D {
+    inner: Grid2D<char>,
+}
+
+#[pymethods]
+impl PyGrid2D {
+    #[new]
+    fn new(text: &str) -> PyResult<Self> {
+        let inner = Grid2D::parse(text, |c| c)
+            .ok_or_else(|| PyValueError::new_err("rows have different lengths"))?;
+        Ok(Self { inner })
+    }
+
+    #[getter]
+    fn width(&self) -> usize {
+        self.inner.width()
+    }
+
+    #[getter]
+    fn height(&self) -> usize {
+        self.inner.height()
+    }
+
+    /**
+     * The character at (row, col), or None if that's outside the grid.
+     */
+    fn get(&self, row: usize, col: usize) -> Option<char> {
+        self.inner.get(row, col).copied()
+    }
+
+    fn set(&mut self, row: usize, col: usize, value: char) -> PyResult<()> {
+        let cell = self
+            .inner
+            .get_mut(row, col)
+            .ok_or_else(|| PyValueError::new_err(format!("({row}, {col}) is outside the grid")))?;
+        *cell = value;
+        Ok(())
+    }
+
+    /**
+     * The positions next to (row, col), not counting diagonals.
+     */
+    fn neighbors(&self, row: usize, col: usize) -> Vec<(usize, usize)> {
+        self.inner.neighbors(row, col).collect()
+    }
+
+    /**
+     * Every position holding the given character, in reading order.
+     */
+    fn find(&self, value: char) -> Vec<(usize, usize)> {
+        self.inner
+            .positions()
+            .filter(|&(row, col)| self.inner.get(row, col) == Some(&value))
+            .collect()
+    }
+
+    fn __str__(&self) -> String {
+        self.inner
+            .rows()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Grid2D({:?})", self.__str__())
+    }
+}
+
+#[pymodule]
+fn advent_of_code_2022(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(solve, m)?)?;
+    m.add_class::<PyIntervalSet>()?;
+    m.add_class::<PyGrid2D>()?;
+    Ok(())
+}
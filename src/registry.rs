@@ -0,0 +1,245 @@
+/*
+ * A table of every solution in the crate, so that they can be run by
+ * (day, part) from places other than cargo-aoc: the golden tests, the
+ * WASM bindings, and so on.
+ *
+ * Each solution takes the raw puzzle input and returns its Answer, running
+ * the day's generator first if it has one. Input that the generator rejects
+ * comes back as its AocError, so that the bindings can report it rather
+ * than panicking.
+ *
+ * Later years' days are registered at the end of the table by new-day
+ * (see aoc-cli), tagged with their year; find() only looks at 2022.
+ */
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::utils::trace::enter_span;
+use crate::y2022::*;
+
+#[cfg(feature = "alloc-metrics")]
+use crate::alloc_metrics::{self, AllocStats};
+#[cfg(feature = "serde")]
+pub(crate) use crate::cache::cached;
+
+/*
+ * Without serde there's nothing to store the parsed input with, so
+ * "cached" generators just run every time.
+ */
+#[cfg(not(feature = "serde"))]
+pub(crate) fn cached<T, E>(
+    _day: u8,
+    input: &str,
+    generator: impl FnOnce(&str) -> Result<T, E>,
+) -> Result<T, E> {
+    generator(input)
+}
+
+pub struct Solution {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    solve: fn(&str) -> Result<Answer, AocError>,
+}
+
+impl Solution {
+    pub fn run(&self, input: &str) -> Result<Answer, AocError> {
+        (self.solve)(input)
+    }
+
+    /**
+     * Like run(), but also reports how much memory the solution allocated.
+     */
+    #[cfg(feature = "alloc-metrics")]
+    pub fn run_measured(&self, input: &str) -> (Result<Answer, AocError>, AllocStats) {
+        alloc_metrics::measure(|| self.run(input))
+    }
+}
+
+macro_rules! solution {
+    // Later years' days (see new-day), which take the raw input.
+    ($year:literal, $day:literal, $part:literal, $year_module:ident::$module:ident::$solver:ident) => {
+        Solution {
+            year: $year,
+            day: $day,
+            part: $part,
+            solve: |input: &str| {
+                enter_span!("solve", day = $day, part = $part);
+                Ok(Answer::from(crate::$year_module::$module::$solver(input)))
+            },
+        }
+    };
+    ($day:literal, $part:literal, $module:ident::$solver:ident) => {
+        Solution {
+            year: 2022,
+            day: $day,
+            part: $part,
+            solve: |input: &str| {
+                enter_span!("solve", day = $day, part = $part);
+                Ok(Answer::from($module::$solver(input.as_ref())))
+            },
+        }
+    };
+    // For solvers that check their input themselves and return a Result.
+    ($day:literal, $part:literal, $module:ident::$solver:ident?) => {
+        Solution {
+            year: 2022,
+            day: $day,
+            part: $part,
+            solve: |input: &str| {
+                enter_span!("solve", day = $day, part = $part);
+                Ok(Answer::from($module::$solver(input.as_ref())?))
+            },
+        }
+    };
+    ($day:literal, $part:literal, $module:ident::$generator:ident => $solver:ident) => {
+        Solution {
+            year: 2022,
+            day: $day,
+            part: $part,
+            solve: |input: &str| {
+                let parsed = {
+                    enter_span!("generate", day = $day);
+                    $module::$generator(input.as_ref())
+                };
+                enter_span!("solve", day = $day, part = $part);
+                Ok(Answer::from($module::$solver(&parsed)))
+            },
+        }
+    };
+    // For slow generators: the parsed input can be cached on disk (see cache.rs).
+    ($day:literal, $part:literal, $module:ident::$generator:ident => $solver:ident, cached) => {
+        Solution {
+            year: 2022,
+            day: $day,
+            part: $part,
+            solve: |input: &str| {
+                let parsed = {
+                    enter_span!("generate", day = $day);
+                    cached($day, input, |input| {
+                        Ok::<_, AocError>($module::$generator(input))
+                    })?
+                };
+                enter_span!("solve", day = $day, part = $part);
+                Ok(Answer::from($module::$solver(&parsed)))
+            },
+        }
+    };
+    // The same two, for generators that return a Result.
+    ($day:literal, $part:literal, $module:ident::$generator:ident? => $solver:ident) => {
+        Solution {
+            year: 2022,
+            day: $day,
+            part: $part,
+            solve: |input: &str| {
+                let parsed = {
+                    enter_span!("generate", day = $day);
+                    $module::$generator(input.as_ref())?
+                };
+                enter_span!("solve", day = $day, part = $part);
+                Ok(Answer::from($module::$solver(&parsed)))
+            },
+        }
+    };
+    // Only parsed inputs are cached, so errors needn't be serializable.
+    ($day:literal, $part:literal, $module:ident::$generator:ident? => $solver:ident, cached) => {
+        Solution {
+            year: 2022,
+            day: $day,
+            part: $part,
+            solve: |input: &str| {
+                let parsed = {
+                    enter_span!("generate", day = $day);
+                    cached($day, input, |input| $module::$generator(input))?
+                };
+                enter_span!("solve", day = $day, part = $part);
+                Ok(Answer::from($module::$solver(&parsed)))
+            },
+        }
+    };
+}
+
+pub static SOLUTIONS: &[Solution] = &[
+    solution!(1, 1, day1::generator => part1),
+    solution!(1, 2, day1::generator => part2),
+    solution!(2, 1, day2::part1),
+    solution!(2, 2, day2::part2),
+    solution!(3, 1, day3::part1?),
+    solution!(3, 2, day3::part2?),
+    solution!(4, 1, day4::generator => part1),
+    solution!(4, 2, day4::generator => part2),
+    solution!(5, 1, day5::generator? => part1),
+    solution!(5, 2, day5::generator? => part2),
+    solution!(6, 1, day6::part1?),
+    solution!(6, 2, day6::part2?),
+    solution!(7, 1, day7::generator? => part1),
+    solution!(7, 2, day7::generator? => part2),
+    solution!(8, 1, day8::generator? => part1),
+    solution!(8, 2, day8::generator? => part2),
+    solution!(9, 1, day9::generator => part1),
+    solution!(9, 2, day9::generator => part2),
+    solution!(10, 1, day10::generator => part1),
+    solution!(10, 2, day10::generator => part2),
+    solution!(11, 1, day11::part1),
+    solution!(11, 2, day11::part2),
+    solution!(12, 1, day12::generator? => part1),
+    solution!(12, 2, day12::generator? => part2),
+    solution!(13, 1, day13::pairs? => part1),
+    solution!(13, 2, day13::pairs? => part2),
+    solution!(14, 1, day14::generator => part1),
+    solution!(14, 2, day14::generator => part2),
+    solution!(15, 1, day15::generator => part1),
+    solution!(15, 2, day15::generator => part2),
+    solution!(16, 1, day16::generator? => part1, cached),
+    solution!(16, 2, day16::generator? => part2, cached),
+    solution!(17, 1, day17::generator? => part1),
+    solution!(17, 2, day17::generator? => part2),
+    solution!(18, 1, day18::generator => part1),
+    solution!(18, 2, day18::generator => part2),
+    solution!(19, 1, day19::create_factories? => part1),
+    solution!(19, 2, day19::create_factories? => part2),
+    solution!(20, 1, day20::generator => part1),
+    solution!(20, 2, day20::generator => part2),
+    solution!(21, 1, day21::generator => part1),
+    solution!(21, 2, day21::generator => part2),
+    solution!(22, 1, day22::generator => part1, cached),
+    solution!(22, 2, day22::generator => part2, cached),
+    solution!(23, 1, day23::generator => part1),
+    solution!(23, 2, day23::generator => part2),
+    solution!(24, 1, day24::generator? => part1),
+    solution!(24, 2, day24::generator? => part2),
+    solution!(25, 1, day25::generator? => part1),
+];
+
+/**
+ * Looks up the solution for a single day and part of 2022.
+ */
+pub fn find(day: u8, part: u8) -> Option<&'static Solution> {
+    find_in(2022, day, part)
+}
+
+/**
+ * The same, for any year with solutions registered.
+ */
+pub fn find_in(year: u16, day: u8, part: u8) -> Option<&'static Solution> {
+    SOLUTIONS
+        .iter()
+        .find(|s| s.year == year && s.day == day && s.part == part)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find, find_in, SOLUTIONS};
+    use crate::error::AocError;
+
+    #[test]
+    fn test_find() {
+        assert_eq!(SOLUTIONS.iter().filter(|s| s.year == 2022).count(), 49);
+        assert_eq!(find_in(2022, 2, 1).unwrap().day, 2);
+        assert_eq!(find(2, 1).unwrap().run("A Y\nB X\nC Z\n").unwrap(), "15");
+        assert_eq!(
+            find(7, 1).unwrap().run("$ cd /\n$ frobnicate\n"),
+            Err(AocError::parse(2, "unknown command \"$ frobnicate\""))
+        );
+        assert!(find(25, 2).is_none());
+    }
+}
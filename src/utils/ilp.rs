@@ -0,0 +1,370 @@
+/*
+ * A small integer linear programming solver, for problems that are easier
+ * to state as constraints than to search directly (see day 19's ILP
+ * alternative).
+ *
+ * Problems are always "maximize c . x subject to A x <= b, x >= 0, x
+ * integer". The LP relaxations are solved with a dense two-phase simplex
+ * (using Bland's rule, since these problems tend to be very degenerate),
+ * and integrality comes from a depth-first branch and bound on top of that.
+ *
+ * This is meant for problems with at most a few hundred variables and
+ * constraints. It's nowhere near as fast as a real solver, but it doesn't
+ * need one.
+ */
+
+const EPSILON: f64 = 1e-9;
+
+pub struct Problem {
+    objective: Vec<f64>,
+    constraints: Vec<(Vec<f64>, f64)>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Solution {
+    pub value: f64,
+    pub values: Vec<f64>,
+}
+
+enum Lp {
+    Infeasible,
+    Unbounded,
+    Optimal(Solution),
+}
+
+impl Problem {
+    /**
+     * A problem maximizing `objective` . x, with no constraints yet.
+     */
+    pub fn new(objective: Vec<f64>) -> Self {
+        Problem {
+            objective,
+            constraints: Vec::new(),
+        }
+    }
+
+    pub fn variables(&self) -> usize {
+        self.objective.len()
+    }
+
+    /**
+     * Adds the constraint `coefficients` . x <= `bound`.
+     */
+    pub fn add_constraint(&mut self, coefficients: Vec<f64>, bound: f64) {
+        assert_eq!(coefficients.len(), self.variables());
+        self.constraints.push((coefficients, bound));
+    }
+
+    /**
+     * Finds the best integer solution, or None if there isn't one (or the
+     * objective is unbounded).
+     *
+     * Branches are pruned by rounding the relaxation's value down, so the
+     * objective's coefficients need to be integers.
+     */
+    pub fn maximize(&self) -> Option<Solution> {
+        let mut bounds = vec![(0.0, f64::INFINITY); self.variables()];
+        let mut best = None;
+        self.branch(&mut bounds, &mut best);
+        best
+    }
+
+    fn branch(&self, bounds: &mut [(f64, f64)], best: &mut Option<Solution>) {
+        let Lp::Optimal(relaxed) = self.relaxation(bounds) else {
+            return;
+        };
+
+        if let Some(best) = best {
+            if (relaxed.value + 1e-6).floor() <= best.value {
+                return;
+            }
+        }
+
+        // Branch on the first fractional variable. When the variables are
+        // ordered by time, as in day 19, this settles the early decisions
+        // first, which constrains the rest of the relaxation much more than
+        // picking the most fractional one.
+        let fractional = relaxed
+            .values
+            .iter()
+            .position(|value| (value - value.round()).abs() > 1e-6);
+
+        let Some(var) = fractional else {
+            let values: Vec<f64> = relaxed.values.iter().map(|v| v.round()).collect();
+            let value = values.iter().zip(&self.objective).map(|(x, c)| x * c).sum();
+            *best = Some(Solution { value, values });
+            return;
+        };
+
+        // Trying the larger value first tends to find good solutions sooner
+        // when the variables are yes/no decisions.
+        let value = relaxed.values[var];
+        let saved = bounds[var];
+        bounds[var] = (value.ceil(), saved.1);
+        self.branch(bounds, best);
+        bounds[var] = (saved.0, value.floor());
+        self.branch(bounds, best);
+        bounds[var] = saved;
+    }
+
+    /**
+     * Solves the problem without the integer constraints, but with each
+     * variable limited to (lower, upper) bounds.
+     */
+    fn relaxation(&self, bounds: &[(f64, f64)]) -> Lp {
+        let n = self.variables();
+        let mut constraints = self.constraints.clone();
+        for (var, &(lower, upper)) in bounds.iter().enumerate() {
+            if lower > 0.0 {
+                let mut row = vec![0.0; n];
+                row[var] = -1.0;
+                constraints.push((row, -lower));
+            }
+            if upper.is_finite() {
+                let mut row = vec![0.0; n];
+                row[var] = 1.0;
+                constraints.push((row, upper));
+            }
+        }
+
+        Tableau::new(&self.objective, &constraints).solve()
+    }
+}
+
+/**
+ * The simplex tableau. Columns are the problem's variables, then one slack
+ * variable per constraint, then the artificial variables needed for phase 1,
+ * and finally the right hand side.
+ */
+struct Tableau {
+    rows: Vec<Vec<f64>>,
+    // Reduced costs (positive = improving), with the negated objective value
+    // in the right hand side column.
+    objective: Vec<f64>,
+    // The problem's own objective, for phase 2.
+    costs: Vec<f64>,
+    basis: Vec<usize>,
+    variables: usize,
+    first_artificial: usize,
+}
+
+impl Tableau {
+    fn new(objective: &[f64], constraints: &[(Vec<f64>, f64)]) -> Self {
+        let variables = objective.len();
+        let m = constraints.len();
+        let first_artificial = variables + m;
+        let artificials = constraints.iter().filter(|(_, b)| *b < 0.0).count();
+        let width = first_artificial + artificials + 1;
+
+        let mut rows = Vec::with_capacity(m);
+        let mut basis = Vec::with_capacity(m);
+        let mut next_artificial = first_artificial;
+        for (i, (coefficients, bound)) in constraints.iter().enumerate() {
+            let mut row = vec![0.0; width];
+            // A row with a negative bound is flipped to have a positive one,
+            // which makes its slack variable negative, so it needs an
+            // artificial variable to start off the basis.
+            let sign = if *bound < 0.0 { -1.0 } else { 1.0 };
+            for (j, a) in coefficients.iter().enumerate() {
+                row[j] = sign * a;
+            }
+            row[variables + i] = sign;
+            row[width - 1] = sign * bound;
+
+            if *bound < 0.0 {
+                row[next_artificial] = 1.0;
+                basis.push(next_artificial);
+                next_artificial += 1;
+            } else {
+                basis.push(variables + i);
+            }
+            rows.push(row);
+        }
+
+        Tableau {
+            rows,
+            objective: vec![0.0; width],
+            costs: objective.to_vec(),
+            basis,
+            variables,
+            first_artificial,
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.objective.len()
+    }
+
+    fn solve(mut self) -> Lp {
+        let width = self.width();
+        let rhs = width - 1;
+
+        // Phase 1: minimize the sum of the artificial variables, to find a
+        // feasible starting point.
+        if width - 1 > self.first_artificial {
+            self.objective = vec![0.0; width];
+            for j in self.first_artificial..rhs {
+                self.objective[j] = -1.0;
+            }
+            self.price_out();
+            if !self.optimize(width - 1) || -self.objective[rhs] < -1e-7 {
+                return Lp::Infeasible;
+            }
+
+            // Any artificial variables left in the basis are zero. Swap them
+            // out where possible; rows where that isn't possible are
+            // redundant, and stay that way.
+            for row in 0..self.rows.len() {
+                if self.basis[row] >= self.first_artificial {
+                    if let Some(col) =
+                        (0..self.first_artificial).find(|&j| self.rows[row][j].abs() > EPSILON)
+                    {
+                        self.pivot(row, col);
+                    }
+                }
+            }
+        }
+
+        // Phase 2: the real objective, never letting artificial variables back in.
+        self.objective = vec![0.0; width];
+        self.objective[..self.variables].copy_from_slice(&self.costs);
+        self.price_out();
+        if !self.optimize(self.first_artificial) {
+            return Lp::Unbounded;
+        }
+
+        let mut values = vec![0.0; self.variables];
+        for (row, &var) in self.basis.iter().enumerate() {
+            if var < self.variables {
+                values[var] = self.rows[row][rhs];
+            }
+        }
+        Lp::Optimal(Solution {
+            value: -self.objective[rhs],
+            values,
+        })
+    }
+
+    /**
+     * Makes the objective's reduced costs zero for the basic variables.
+     */
+    fn price_out(&mut self) {
+        for row in 0..self.rows.len() {
+            let factor = self.objective[self.basis[row]];
+            if factor != 0.0 {
+                for j in 0..self.objective.len() {
+                    self.objective[j] -= factor * self.rows[row][j];
+                }
+            }
+        }
+    }
+
+    /**
+     * Pivots until the objective can't be improved using the first
+     * `columns` columns. Returns false if it's unbounded.
+     */
+    fn optimize(&mut self, columns: usize) -> bool {
+        let rhs = self.width() - 1;
+        loop {
+            // Bland's rule: the first improving column, and the first
+            // (by basic variable) of the tightest rows.
+            let Some(col) = (0..columns).find(|&j| self.objective[j] > EPSILON) else {
+                return true;
+            };
+
+            let mut leaving: Option<(usize, f64)> = None;
+            for row in 0..self.rows.len() {
+                let a = self.rows[row][col];
+                if a <= EPSILON {
+                    continue;
+                }
+                let ratio = self.rows[row][rhs] / a;
+                let better = match leaving {
+                    None => true,
+                    Some((best, best_ratio)) => {
+                        ratio < best_ratio - EPSILON
+                            || (ratio < best_ratio + EPSILON && self.basis[row] < self.basis[best])
+                    }
+                };
+                if better {
+                    leaving = Some((row, ratio));
+                }
+            }
+
+            let Some((row, _)) = leaving else {
+                return false;
+            };
+            self.pivot(row, col);
+        }
+    }
+
+    fn pivot(&mut self, row: usize, col: usize) {
+        let pivot = self.rows[row][col];
+        for value in self.rows[row].iter_mut() {
+            *value /= pivot;
+        }
+
+        let pivot_row = self.rows[row].clone();
+        for (i, other) in self.rows.iter_mut().enumerate() {
+            let factor = other[col];
+            if i != row && factor != 0.0 {
+                for (value, p) in other.iter_mut().zip(&pivot_row) {
+                    *value -= factor * p;
+                }
+            }
+        }
+
+        let factor = self.objective[col];
+        if factor != 0.0 {
+            for (value, p) in self.objective.iter_mut().zip(&pivot_row) {
+                *value -= factor * p;
+            }
+        }
+
+        self.basis[row] = col;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relaxation() {
+        // max x + y with x + 2y <= 4 and 3x + y <= 6: (8/5, 6/5).
+        let mut problem = Problem::new(vec![1.0, 1.0]);
+        problem.add_constraint(vec![1.0, 2.0], 4.0);
+        problem.add_constraint(vec![3.0, 1.0], 6.0);
+        let Lp::Optimal(solution) = problem.relaxation(&[(0.0, f64::INFINITY); 2]) else {
+            panic!("expected a solution");
+        };
+        assert!((solution.value - 2.8).abs() < 1e-9);
+
+        // Integer solutions: (1, 1), (2, 0) and (0, 2) all give 2.
+        assert_eq!(problem.maximize().unwrap().value, 2.0);
+
+        // Lower bounds that can't be met.
+        assert!(matches!(
+            problem.relaxation(&[(3.0, f64::INFINITY), (0.0, f64::INFINITY)]),
+            Lp::Infeasible
+        ));
+    }
+
+    #[test]
+    fn test_knapsack() {
+        // Weights 6, 5, 5 and values 12, 9, 9, with room for 10. The
+        // relaxation takes all of the first item and most of another (19.2),
+        // but the best whole choice is the last two.
+        let mut problem = Problem::new(vec![12.0, 9.0, 9.0]);
+        problem.add_constraint(vec![6.0, 5.0, 5.0], 10.0);
+        for i in 0..3 {
+            let mut row = vec![0.0; 3];
+            row[i] = 1.0;
+            problem.add_constraint(row, 1.0);
+        }
+
+        let solution = problem.maximize().unwrap();
+        assert_eq!(solution.value, 18.0);
+        assert_eq!(solution.values, vec![0.0, 1.0, 1.0]);
+    }
+}
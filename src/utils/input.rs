@@ -0,0 +1,39 @@
+/*
+ * The input helpers live in aoc-utils; reading input from a file stays here,
+ * since it reports failures as an AocError.
+ */
+pub use aoc_utils::input::*;
+
+/**
+ * Reads the puzzle input from a file, or from stdin if `path` is "-".
+ */
+#[cfg(feature = "std")]
+pub fn read_input(path: &str) -> Result<alloc::string::String, crate::error::AocError> {
+    use std::io::Read;
+
+    let mut input = alloc::string::String::new();
+    let result = if path == "-" {
+        std::io::stdin().read_to_string(&mut input).map(|_| ())
+    } else {
+        std::fs::read_to_string(path).map(|text| input = text)
+    };
+    result.map_err(|err| crate::error::AocError::Io {
+        path: if path == "-" { "<stdin>" } else { path }.to_string(),
+        message: err.to_string(),
+    })?;
+    Ok(input)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::read_input;
+
+    #[test]
+    fn test_read_input() {
+        assert!(read_input("Cargo.toml").unwrap().starts_with("[package]"));
+        assert!(matches!(
+            read_input("no/such/input.txt"),
+            Err(crate::error::AocError::Io { path, .. }) if path == "no/such/input.txt"
+        ));
+    }
+}
@@ -0,0 +1,26 @@
+/*
+ * Shared helpers that turned out to be useful for more than one day.
+ *
+ * The ones that aren't tied to this crate live in the aoc-utils crate, and
+ * are re-exported here so the days can keep using `crate::utils::...`.
+ */
+pub use aoc_utils::{
+    bitset, cancel, geometry, grid, heatmap, interval, neighbors, parse, progress,
+};
+#[cfg(feature = "std")]
+pub use aoc_utils::{hash, memo, search};
+
+#[cfg(feature = "ilp")]
+pub mod ilp;
+pub mod input;
+#[cfg(feature = "std")]
+pub mod paths;
+#[cfg(feature = "serde")]
+pub mod serde_pairs;
+#[cfg(feature = "std")]
+pub mod toml_lite;
+#[cfg(feature = "std")]
+pub mod trace;
+
+#[cfg(all(test, feature = "std"))]
+pub mod testing;
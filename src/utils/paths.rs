@@ -0,0 +1,69 @@
+/*
+ * The searches from the pathfinding crate that the days use, with the same
+ * signatures. With the `pathfinding` feature they're pathfinding's own;
+ * without it, they're thin wrappers around utils::search, so that minimal
+ * builds don't have to compile pathfinding and its dependencies.
+ */
+#[cfg(feature = "pathfinding")]
+pub use pathfinding::directed::{
+    bfs::{bfs, bfs_reach},
+    dijkstra::dijkstra,
+};
+
+#[cfg(not(feature = "pathfinding"))]
+pub use fallback::*;
+
+#[cfg(not(feature = "pathfinding"))]
+mod fallback {
+    use std::hash::Hash;
+    use std::ops::Add;
+
+    use crate::utils::search;
+
+    /**
+     * The shortest path from `start` to a node for which `success` returns
+     * true, including both ends.
+     */
+    pub fn bfs<N, I>(
+        start: &N,
+        successors: impl FnMut(&N) -> I,
+        success: impl FnMut(&N) -> bool,
+    ) -> Option<Vec<N>>
+    where
+        N: Clone + Eq + Hash,
+        I: IntoIterator<Item = N>,
+    {
+        search::bfs(start.clone(), successors, success).0
+    }
+
+    /**
+     * Every node reachable from `start`, including itself. Unlike
+     * pathfinding's, this finds them all before returning any.
+     */
+    pub fn bfs_reach<N, I>(start: N, successors: impl FnMut(&N) -> I) -> impl Iterator<Item = N>
+    where
+        N: Clone + Eq + Hash,
+        I: IntoIterator<Item = N>,
+    {
+        search::bfs_reach(start, successors, |_| false)
+            .0
+            .into_iter()
+    }
+
+    /**
+     * The cheapest path from `start` to a node for which `success` returns
+     * true, and its cost. This is A* with no heuristic.
+     */
+    pub fn dijkstra<N, C, I>(
+        start: &N,
+        successors: impl FnMut(&N) -> I,
+        success: impl FnMut(&N) -> bool,
+    ) -> Option<(Vec<N>, C)>
+    where
+        N: Clone + Eq + Hash,
+        C: Copy + Ord + Default + Add<Output = C>,
+        I: IntoIterator<Item = (N, C)>,
+    {
+        search::astar(start.clone(), successors, |_| C::default(), success).0
+    }
+}
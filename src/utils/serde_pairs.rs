@@ -0,0 +1,54 @@
+/*
+ * JSON (and a few other formats) only allow strings as map keys, but a lot
+ * of the parsed inputs are maps keyed by coordinates. Fields like that can
+ * use this module to be written as a list of [key, value] pairs instead:
+ *
+ *     #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_pairs"))]
+ *     points: HashMap<(i32, i32), u8>,
+ */
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub fn serialize<K, V, H, S>(map: &HashMap<K, V, H>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    K: Serialize,
+    V: Serialize,
+    S: Serializer,
+{
+    serializer.collect_seq(map.iter())
+}
+
+pub fn deserialize<'de, K, V, H, D>(deserializer: D) -> Result<HashMap<K, V, H>, D::Error>
+where
+    K: Deserialize<'de> + Eq + Hash,
+    V: Deserialize<'de>,
+    H: BuildHasher + Default,
+    D: Deserializer<'de>,
+{
+    let pairs: Vec<(K, V)> = Vec::deserialize(deserializer)?;
+    Ok(pairs.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Grid {
+        #[serde(with = "super")]
+        points: HashMap<(i32, i32), char>,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let grid = Grid {
+            points: HashMap::from([((0, 1), '#')]),
+        };
+        let json = serde_json::to_string(&grid).unwrap();
+        assert_eq!(json, r##"{"points":[[[0,1],"#"]]}"##);
+        assert_eq!(serde_json::from_str::<Grid>(&json).unwrap(), grid);
+    }
+}
@@ -0,0 +1,20 @@
+use std::{fs, path::Path};
+
+const EXAMPLE_DIR: &str = "input/2022/test";
+
+/**
+ * Reads an example input from the test input directory.
+ *
+ * The example inputs aren't checked in for every day, so a missing file
+ * is reported and skipped instead of failing the test.
+ */
+pub fn read_example(file: &str) -> Option<String> {
+    let path = Path::new(EXAMPLE_DIR).join(file);
+    match fs::read_to_string(&path) {
+        Ok(input) => Some(input),
+        Err(_) => {
+            eprintln!("skipping: example input {} not found", path.display());
+            None
+        }
+    }
+}
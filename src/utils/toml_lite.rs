@@ -0,0 +1,76 @@
+use crate::error::AocError;
+
+/*
+ * A parser for the small subset of TOML that our config and answer files
+ * use: `[section]` headers, `key = value` pairs, blank lines and comments.
+ * Values are returned as strings with any surrounding quotes removed.
+ *
+ * This isn't worth pulling in a real TOML parser for.
+ */
+#[derive(Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub line: usize,
+    pub section: String,
+    pub key: String,
+    pub value: String,
+}
+
+pub fn parse(text: &str) -> Result<Vec<Entry>, AocError> {
+    let mut entries = Vec::new();
+    let mut section = String::new();
+
+    for (index, line) in text.lines().map(str::trim).enumerate() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.trim().to_string();
+        } else if let Some((key, value)) = line.split_once('=') {
+            entries.push(Entry {
+                line: index + 1,
+                section: section.clone(),
+                key: key.trim().to_string(),
+                value: value.trim().trim_matches('"').to_string(),
+            });
+        } else {
+            return Err(AocError::parse(
+                index + 1,
+                format!("malformed line: {line}"),
+            ));
+        }
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let entries = parse("# comment\n[day1]\npart1 = 12\n\npart2 = \"AB\"\n").unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                Entry {
+                    line: 3,
+                    section: "day1".to_string(),
+                    key: "part1".to_string(),
+                    value: "12".to_string(),
+                },
+                Entry {
+                    line: 5,
+                    section: "day1".to_string(),
+                    key: "part2".to_string(),
+                    value: "AB".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            parse("[day1]\noops\n").unwrap_err(),
+            AocError::parse(2, "malformed line: oops")
+        );
+    }
+}
@@ -0,0 +1,77 @@
+/*
+ * Thin wrappers around `tracing`, so the solutions can be instrumented
+ * without a cfg attribute on every line. Without the `tracing` feature,
+ * all of these compile away to (almost) nothing.
+ */
+#[cfg(feature = "tracing")]
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/*
+ * Enters a debug-level span that lasts until the end of the enclosing block:
+ *
+ *     enter_span!("parse", day = 16);
+ */
+macro_rules! enter_span {
+    ($name:literal $(, $field:ident = $value:expr)* $(,)?) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!($name $(, $field = $value)*).entered();
+    };
+}
+
+/*
+ * Emits a debug-level event with some fields:
+ *
+ *     debug_event!("search finished", states_explored = explored.get());
+ *
+ * The field values are still evaluated without the feature (so that the
+ * things they reference don't look unused), so keep them cheap.
+ */
+macro_rules! debug_event {
+    ($message:literal $(, $field:ident = $value:expr)* $(,)?) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($field = $value,)* $message);
+        #[cfg(not(feature = "tracing"))]
+        {
+            $(let _ = &$value;)*
+        }
+    };
+}
+
+pub(crate) use debug_event;
+pub(crate) use enter_span;
+
+/**
+ * A statistics counter for the search loops (states explored, cache hits,
+ * and so on). It's zero-sized and does nothing without the `tracing`
+ * feature, so the hot loops don't pay for it.
+ *
+ * It's atomic so that it can be shared with the parallel solutions.
+ */
+#[derive(Debug, Default)]
+pub struct Counter {
+    #[cfg(feature = "tracing")]
+    count: AtomicU64,
+}
+
+impl Counter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline(always)]
+    pub fn increment(&self) {
+        #[cfg(feature = "tracing")]
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /**
+     * The current count; always 0 without the `tracing` feature.
+     */
+    pub fn get(&self) -> u64 {
+        #[cfg(feature = "tracing")]
+        return self.count.load(Ordering::Relaxed);
+
+        #[cfg(not(feature = "tracing"))]
+        0
+    }
+}
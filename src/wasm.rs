@@ -0,0 +1,54 @@
+/*
+ * Bindings for calling the solutions from JavaScript. Build with:
+ *
+ *     cargo rustc --lib --release --target wasm32-unknown-unknown \
+ *         --features wasm --crate-type cdylib
+ *     wasm-bindgen --target web --out-dir pkg \
+ *         target/wasm32-unknown-unknown/release/advent_of_code_2022.wasm
+ *
+ * and see www/index.html for a minimal page that uses them.
+ */
+use wasm_bindgen::prelude::*;
+
+use crate::registry;
+
+#[wasm_bindgen]
+pub struct SolveResult {
+    answer: String,
+    elapsed_ms: f64,
+}
+
+#[wasm_bindgen]
+impl SolveResult {
+    #[wasm_bindgen(getter)]
+    pub fn answer(&self) -> String {
+        self.answer.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn elapsed_ms(&self) -> f64 {
+        self.elapsed_ms
+    }
+}
+
+/**
+ * Runs one part of one day against the given input. An input that the day
+ * can't parse is an error, rather than a panic that kills the instance.
+ *
+ * std::time::Instant isn't available in the browser, so the timing comes
+ * from JavaScript's clock instead (which has millisecond resolution).
+ */
+#[wasm_bindgen]
+pub fn solve(day: u8, part: u8, input: &str) -> Result<SolveResult, JsError> {
+    let solution = registry::find(day, part)
+        .ok_or_else(|| JsError::new(&format!("no solution for day {day} part {part}")))?;
+
+    let start = js_sys::Date::now();
+    let answer = solution
+        .run(input)
+        .map_err(|err| JsError::new(&format!("day {day}: {err}")))?
+        .to_string();
+    let elapsed_ms = js_sys::Date::now() - start;
+
+    Ok(SolveResult { answer, elapsed_ms })
+}
@@ -0,0 +1,262 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use crate::error::AocError;
+use crate::utils::input::{normalize, normalize_bytes};
+
+/**
+ * The items one elf is carrying, in the order they were listed.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ElfInventory {
+    pub items: Vec<u32>,
+}
+
+impl ElfInventory {
+    pub fn total(&self) -> u32 {
+        self.items.iter().sum()
+    }
+
+    /**
+     * The elf's biggest single snack, or None if they aren't carrying any.
+     */
+    pub fn max_item(&self) -> Option<u32> {
+        self.items.iter().copied().max()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+#[aoc_generator(day1)]
+pub fn generator(input: &str) -> Vec<ElfInventory> {
+    let input = normalize(input);
+    input
+        // Each elf's stack of cookies is separated by an empty line, so
+        // splitting on \n\n gives us chunks of data separated by elf.
+        .split("\n\n")
+        .map(|group| ElfInventory {
+            // Take this elf's data, split it into lines, and convert each
+            // line to an integer.
+            items: group
+                .lines()
+                .filter_map(|line| line.parse::<u32>().ok())
+                .collect(),
+        })
+        .collect()
+}
+
+pub fn totals(elves: &[ElfInventory]) -> Vec<u32> {
+    elves.iter().map(ElfInventory::total).collect()
+}
+
+/**
+ * A strict version of generator(), which reports a line that isn't a number
+ * (with its line number) instead of skipping it.
+ *
+ * Any run of blank lines separates two elves, so stray extra blank lines
+ * (or blank lines at the start or end) don't create elves carrying nothing.
+ * Lines with only whitespace count as blank, and CRLF line endings are fine.
+ */
+pub fn parse_inventories(input: &str) -> Result<Vec<ElfInventory>, AocError> {
+    let input = normalize(input);
+    let mut elves = Vec::new();
+    let mut items = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            if !items.is_empty() {
+                elves.push(ElfInventory {
+                    items: std::mem::take(&mut items),
+                });
+            }
+            continue;
+        }
+
+        let calories = line.parse().map_err(|_| {
+            AocError::parse(
+                index + 1,
+                format!("expected a number of calories but got {line:?}"),
+            )
+        })?;
+        items.push(calories);
+    }
+    if !items.is_empty() {
+        elves.push(ElfInventory { items });
+    }
+
+    Ok(elves)
+}
+
+/*
+ * The same as generator(), but scanning the input bytes directly instead
+ * of splitting it into strs and parsing each line.
+ */
+#[aoc_generator(day1, part1, Bytes)]
+pub fn generator_bytes(input: &[u8]) -> Vec<u32> {
+    let input = normalize_bytes(input);
+    let mut totals = Vec::new();
+    let mut group_total = 0;
+    let mut value = 0;
+    let mut line_is_empty = true;
+
+    for &byte in input.iter() {
+        if byte.is_ascii_digit() {
+            value = value * 10 + (byte - b'0') as u32;
+            line_is_empty = false;
+        } else if byte == b'\n' {
+            if line_is_empty {
+                // A blank line ends this elf's group.
+                totals.push(group_total);
+                group_total = 0;
+            } else {
+                group_total += value;
+                value = 0;
+                line_is_empty = true;
+            }
+        }
+    }
+    totals.push(group_total);
+
+    totals
+}
+
+// cargo-aoc looks up generators per part, so part 2 needs its own entry.
+#[aoc_generator(day1, part2, Bytes)]
+fn generator_bytes_part2(input: &[u8]) -> Vec<u32> {
+    generator_bytes(input)
+}
+
+/**
+ * Returns the `k` largest per-elf totals, largest first.
+ *
+ * Rather than sorting every total, this keeps a min-heap of the best `k`
+ * seen so far and evicts the smallest whenever it grows too large.
+ */
+pub fn top_k_calories(totals: &[u32], k: usize) -> Vec<u32> {
+    let mut heap: BinaryHeap<Reverse<u32>> = BinaryHeap::with_capacity(k + 1);
+    for &total in totals {
+        heap.push(Reverse(total));
+        if heap.len() > k {
+            heap.pop();
+        }
+    }
+
+    // into_sorted_vec() is ascending, which for Reverse<> means largest first.
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(total)| total)
+        .collect()
+}
+
+#[aoc(day1, part1)]
+pub fn part1(elves: &[ElfInventory]) -> u32 {
+    part1_bytes(&totals(elves))
+}
+
+#[aoc(day1, part2)]
+pub fn part2(elves: &[ElfInventory]) -> u32 {
+    part2_bytes(&totals(elves))
+}
+
+#[aoc(day1, part1, Bytes)]
+pub fn part1_bytes(totals: &[u32]) -> u32 {
+    top_k_calories(totals, 1)[0]
+}
+
+#[aoc(day1, part2, Bytes)]
+pub fn part2_bytes(totals: &[u32]) -> u32 {
+    top_k_calories(totals, 3).iter().sum()
+}
+
+/**
+ * The single largest snack any elf is carrying.
+ */
+pub fn largest_snack(elves: &[ElfInventory]) -> Option<u32> {
+    elves.iter().filter_map(ElfInventory::max_item).max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1000\n\
+                           2000\n\
+                           3000\n\
+                               \n\
+                           4000\n\
+                               \n\
+                           5000\n\
+                           6000\n\
+                               \n\
+                           7000\n\
+                           8000\n\
+                           9000\n\
+                               \n\
+                           10000";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&generator(EXAMPLE)), 24000);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&generator(EXAMPLE)), 45000);
+    }
+
+    #[test]
+    fn test_top_k_calories() {
+        let totals = totals(&generator(EXAMPLE));
+        assert_eq!(top_k_calories(&totals, 2), vec![24000, 11000]);
+        assert_eq!(top_k_calories(&totals, 0), Vec::<u32>::new());
+        assert_eq!(top_k_calories(&totals, 10).len(), 5);
+    }
+
+    #[test]
+    fn test_parse_inventories() {
+        let elves = parse_inventories(EXAMPLE).unwrap();
+        assert_eq!(elves[0].items, vec![1000, 2000, 3000]);
+        assert_eq!(elves, generator(EXAMPLE));
+
+        // Extra blank lines (some with spaces) and Windows line endings.
+        let messy = "\r\n1000\r\n2000\r\n\r\n  \r\n\r\n3000\r\n\r\n";
+        let elves = parse_inventories(messy).unwrap();
+        assert_eq!(elves.len(), 2);
+        assert_eq!(elves[1].items, vec![3000]);
+
+        assert_eq!(
+            parse_inventories("1000\n\n20O0\n"),
+            Err(AocError::parse(
+                3,
+                "expected a number of calories but got \"20O0\""
+            ))
+        );
+    }
+
+    #[test]
+    fn test_inventory() {
+        let elves = generator(EXAMPLE);
+        assert_eq!(
+            elves.iter().map(ElfInventory::len).collect::<Vec<_>>(),
+            [3, 1, 2, 3, 1]
+        );
+        assert_eq!(elves[2].max_item(), Some(6000));
+        assert_eq!(largest_snack(&elves), Some(10000));
+        assert_eq!(ElfInventory { items: vec![] }.max_item(), None);
+    }
+
+    #[test]
+    fn test_generator_bytes() {
+        let expected = totals(&generator(EXAMPLE));
+        assert_eq!(generator_bytes(EXAMPLE.as_bytes()), expected);
+
+        let windows = EXAMPLE.replace('\n', "\r\n");
+        assert_eq!(generator_bytes(windows.as_bytes()), expected);
+    }
+}
@@ -0,0 +1,221 @@
+use crate::utils::input::normalize;
+
+/**
+ * Unusually, basically all of the work happens in the parse step.
+ *
+ * This function returns a vector containing the value of `x` at each
+ * time step.
+ */
+#[aoc_generator(day10)]
+pub fn generator(input: &str) -> Vec<i32> {
+    let input = normalize(input);
+    let mut state: Vec<i32> = Vec::new();
+    let mut x = 1;
+    for line in input.lines() {
+        match &line[..4] {
+            "noop" => {
+                // A no-op means that the next time step has the current
+                // value of x, and no change is needed.
+                state.push(x);
+            }
+            "addx" => {
+                // Get the value to be added to x.
+                let (_, value_str) = line.split_once(' ').unwrap();
+                let value = value_str.parse::<i32>().unwrap();
+
+                // An addx takes two cycles. For those cycles, x keeps its current
+                // value; afterwards, the addx completes and we update x.
+                state.push(x);
+                state.push(x);
+                x += value;
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    state
+}
+
+#[aoc(day10, part1)]
+pub fn part1(input: &[i32]) -> i32 {
+    // For part 1, we want the sum of `cycle * x` at cycles 20, 60, 100, ...
+    // We have to switch to AoC's 1-indexed cycles, but otherwise this is easy.
+    input
+        .iter()
+        .enumerate()
+        .skip(19)
+        .step_by(40)
+        .map(|(cycle_minus_1, x)| (1 + cycle_minus_1 as i32) * x)
+        .sum()
+}
+
+pub const CRT_WIDTH: usize = 40;
+pub const CRT_HEIGHT: usize = 6;
+
+/*
+ * The letters the puzzle draws are four pixels wide, with a dark column
+ * after each, so the screen holds eight of them. These are the ones that
+ * are known to turn up.
+ */
+const LETTER_WIDTH: usize = 5;
+const LETTERS: [(char, [&str; CRT_HEIGHT]); 16] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/**
+ * The CRT's frame buffer: true for a lit pixel, false for a dark one.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Crt {
+    pixels: [[bool; CRT_WIDTH]; CRT_HEIGHT],
+}
+
+impl Default for Crt {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Crt {
+    pub fn new() -> Self {
+        Crt {
+            pixels: [[false; CRT_WIDTH]; CRT_HEIGHT],
+        }
+    }
+
+    pub fn set_pixel(&mut self, x: usize, y: usize, lit: bool) {
+        self.pixels[y][x] = lit;
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> bool {
+        self.pixels[y][x]
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[bool; CRT_WIDTH]> {
+        self.pixels.iter()
+    }
+
+    /**
+     * Draws the screen as text, with '#' for lit pixels and ' ' for dark
+     * ones, one line per row.
+     */
+    pub fn render(&self) -> String {
+        self.iter_rows()
+            .map(|row| {
+                let mut line: String = row.iter().map(|&lit| if lit { '#' } else { ' ' }).collect();
+                line.push('\n');
+                line
+            })
+            .collect()
+    }
+
+    /**
+     * Reads the letters off the screen, or None if any of them isn't one
+     * of the known shapes.
+     */
+    pub fn letters(&self) -> Option<String> {
+        (0..CRT_WIDTH / LETTER_WIDTH)
+            .map(|index| {
+                let left = index * LETTER_WIDTH;
+                LETTERS.iter().find_map(|(letter, rows)| {
+                    let matches = rows.iter().zip(&self.pixels).all(|(glyph, pixels)| {
+                        glyph
+                            .bytes()
+                            .zip(&pixels[left..])
+                            .all(|(glyph, &lit)| (glyph == b'#') == lit)
+                    });
+                    matches.then_some(*letter)
+                })
+            })
+            .collect()
+    }
+}
+
+/**
+ * Runs the CRT for the whole program, given the value of `x` at each cycle.
+ */
+pub fn draw(input: &[i32]) -> Crt {
+    let mut crt = Crt::new();
+
+    for (cycle_minus_1, x) in input.iter().enumerate().take(CRT_WIDTH * CRT_HEIGHT) {
+        // The pixel being painted during this cycle.
+        let pixel_x = cycle_minus_1 % CRT_WIDTH;
+        let pixel_y = cycle_minus_1 / CRT_WIDTH;
+
+        // If x is within 1 pixel of the current one, it gets painted.
+        crt.set_pixel(pixel_x, pixel_y, (x - pixel_x as i32).abs() <= 1);
+    }
+
+    crt
+}
+
+/**
+ * The letters on the screen. If they aren't all ones Crt::letters() knows,
+ * this gives back the whole screen instead, to be read by eye.
+ */
+#[aoc(day10, part2)]
+pub fn part2(input: &[i32]) -> String {
+    let crt = draw(input);
+    crt.letters().unwrap_or_else(|| crt.render())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::testing::read_example;
+
+    #[test]
+    fn test_draw() {
+        let Some(input) = read_example("day10.txt") else {
+            return;
+        };
+        let crt = draw(&generator(&input));
+
+        // The example draws a pattern of stripes, getting wider on each row.
+        let rows: Vec<String> = crt
+            .iter_rows()
+            .map(|row| row.iter().map(|&lit| if lit { '#' } else { '.' }).collect())
+            .collect();
+        assert_eq!(rows[0], "##..##..##..##..##..##..##..##..##..##..");
+        assert_eq!(rows[5], "#######.......#######.......#######.....");
+
+        assert!(crt.pixel(0, 0));
+        assert!(!crt.pixel(2, 0));
+        assert!(crt.render().starts_with("##  ##  "));
+        assert_eq!(part2(&generator(&input)), crt.render());
+
+        // Stripes aren't letters.
+        assert_eq!(crt.letters(), None);
+    }
+
+    #[test]
+    fn test_letters() {
+        let mut crt = Crt::new();
+        for (index, (_, rows)) in LETTERS.iter().cycle().skip(3).take(8).enumerate() {
+            for (y, row) in rows.iter().enumerate() {
+                for (x, glyph) in row.bytes().enumerate() {
+                    crt.set_pixel(index * LETTER_WIDTH + x, y, glyph == b'#');
+                }
+            }
+        }
+        assert_eq!(crt.letters().as_deref(), Some("EFGHJKLO"));
+
+        crt.set_pixel(38, 5, true);
+        assert_eq!(crt.letters(), None);
+    }
+}
@@ -0,0 +1,665 @@
+use std::{collections::VecDeque, fmt, fmt::Write};
+
+#[cfg(feature = "nom")]
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{newline, space0, u64},
+    combinator::map,
+    multi::{many0, many1, separated_list0},
+    sequence::{delimited, pair, preceded, tuple},
+    IResult,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::utils::input::normalize;
+#[cfg(not(feature = "nom"))]
+use crate::utils::parse::blocks;
+
+/**
+ * Each monkey performs some mathematical operation.
+ *
+ * Each operation has an operator (add or multiply).
+ *
+ * Each operand has two parameters: the current value,
+ * and either "old" (the current value), or an integer.
+ *
+ * These enums just capture this structure.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Operand {
+    Old,
+    Value(u64),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Operator {
+    Add,
+    Multiply,
+}
+
+impl Operator {
+    fn evaluate(&self, lhs: u64, rhs: u64) -> u64 {
+        match self {
+            Operator::Add => lhs + rhs,
+            Operator::Multiply => lhs * rhs,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Operation {
+    pub operator: Operator,
+    pub operand: Operand,
+}
+
+impl Operation {
+    /**
+     * "new = old + value"
+     */
+    pub fn add(value: u64) -> Self {
+        Operation {
+            operator: Operator::Add,
+            operand: Operand::Value(value),
+        }
+    }
+
+    /**
+     * "new = old * value"
+     */
+    pub fn multiply(value: u64) -> Self {
+        Operation {
+            operator: Operator::Multiply,
+            operand: Operand::Value(value),
+        }
+    }
+
+    /**
+     * "new = old * old"
+     */
+    pub fn square() -> Self {
+        Operation {
+            operator: Operator::Multiply,
+            operand: Operand::Old,
+        }
+    }
+
+    pub fn evaluate(&self, lhs: u64) -> u64 {
+        let rhs = match self.operand {
+            Operand::Old => lhs,
+            Operand::Value(x) => x,
+        };
+
+        self.operator.evaluate(lhs, rhs)
+    }
+
+    /**
+     * Describes the operation the way the puzzle's walkthrough does, e.g.
+     * "Worry level is multiplied by 19 to 1501."
+     */
+    fn describe(&self, result: u64) -> String {
+        let operand = match self.operand {
+            Operand::Old => String::from("itself"),
+            Operand::Value(x) => x.to_string(),
+        };
+        match self.operator {
+            Operator::Add => format!("Worry level increases by {operand} to {result}."),
+            Operator::Multiply => format!("Worry level is multiplied by {operand} to {result}."),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Monkey {
+    id: u64,
+    items: VecDeque<u64>,
+    operation: Operation,
+    divisor: u64,
+    if_true: u64,
+    if_false: u64,
+    inspections: u64,
+}
+
+impl Monkey {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /**
+     * The worry levels of the items the monkey is holding, in the order it
+     * will inspect them.
+     */
+    pub fn items(&self) -> impl Iterator<Item = u64> + '_ {
+        self.items.iter().copied()
+    }
+
+    pub fn operation(&self) -> Operation {
+        self.operation
+    }
+
+    /**
+     * The monkey's test: the divisor, then who it throws to if the worry
+     * level is divisible by it, and who it throws to if it isn't.
+     */
+    pub fn test(&self) -> (u64, u64, u64) {
+        (self.divisor, self.if_true, self.if_false)
+    }
+
+    /**
+     * How many items the monkey has inspected so far.
+     */
+    pub fn inspections(&self) -> u64 {
+        self.inspections
+    }
+}
+
+/**
+ * Builds a Monkey without going through the text format, e.g. the example's
+ * first monkey is MonkeyBuilder::new(0).items([79, 98])
+ * .operation(Operation::multiply(19)).test(23, 2, 3).build().
+ *
+ * The items default to none and the operation to "new = old + 0"; the test
+ * has to be given.
+ */
+#[derive(Clone, Debug)]
+pub struct MonkeyBuilder {
+    id: u64,
+    items: VecDeque<u64>,
+    operation: Operation,
+    test: Option<(u64, u64, u64)>,
+}
+
+impl MonkeyBuilder {
+    pub fn new(id: u64) -> Self {
+        MonkeyBuilder {
+            id,
+            items: VecDeque::new(),
+            operation: Operation::add(0),
+            test: None,
+        }
+    }
+
+    pub fn items(mut self, items: impl IntoIterator<Item = u64>) -> Self {
+        self.items = items.into_iter().collect();
+        self
+    }
+
+    pub fn operation(mut self, operation: Operation) -> Self {
+        self.operation = operation;
+        self
+    }
+
+    /**
+     * Throw to `if_true` if the worry level is divisible by `divisor`, or
+     * to `if_false` if it isn't.
+     */
+    pub fn test(mut self, divisor: u64, if_true: u64, if_false: u64) -> Self {
+        self.test = Some((divisor, if_true, if_false));
+        self
+    }
+
+    /**
+     * Panics if test() wasn't called.
+     */
+    pub fn build(self) -> Monkey {
+        let (divisor, if_true, if_false) = self
+            .test
+            .unwrap_or_else(|| panic!("monkey {} has no test", self.id));
+        Monkey {
+            id: self.id,
+            items: self.items,
+            operation: self.operation,
+            divisor,
+            if_true,
+            if_false,
+            inspections: 0,
+        }
+    }
+}
+
+/**
+ * Shows the items a monkey is holding, as in the walkthrough's "After
+ * round 1" lists: "Monkey 0: 20, 23, 27, 26".
+ */
+impl fmt::Display for Monkey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let items: Vec<String> = self.items.iter().map(|item| item.to_string()).collect();
+        write!(f, "Monkey {}: {}", self.id, items.join(", "))
+    }
+}
+
+/************
+ * Parsers! *
+ ************/
+
+#[cfg(feature = "nom")]
+fn parse_operator(input: &str) -> IResult<&str, Operator> {
+    alt((
+        map(tag("+"), |_| Operator::Add),
+        map(tag("*"), |_| Operator::Multiply),
+    ))(input)
+}
+
+#[cfg(feature = "nom")]
+fn parse_operand(input: &str) -> IResult<&str, Operand> {
+    alt((map(tag("old"), |_| Operand::Old), map(u64, Operand::Value)))(input)
+}
+
+#[cfg(feature = "nom")]
+pub fn parse_operation(input: &str) -> IResult<&str, Operation> {
+    map(
+        preceded(
+            tag("new = old "),
+            tuple((parse_operator, preceded(space0, parse_operand))),
+        ),
+        |(operator, operand)| Operation { operator, operand },
+    )(input)
+}
+
+#[cfg(feature = "nom")]
+pub fn parse_monkey(input: &str) -> IResult<&str, Monkey> {
+    map(
+        tuple((
+            delimited(tag("Monkey "), u64, tag(":\n")),
+            delimited(
+                pair(space0, tag("Starting items: ")),
+                separated_list0(tag(", "), u64),
+                newline,
+            ),
+            delimited(pair(space0, tag("Operation: ")), parse_operation, newline),
+            delimited(pair(space0, tag("Test: divisible by ")), u64, newline),
+            delimited(pair(space0, tag("If true: throw to monkey ")), u64, newline),
+            delimited(
+                pair(space0, tag("If false: throw to monkey ")),
+                u64,
+                many0(newline),
+            ),
+        )),
+        |(id, item_vec, operation, divisor, if_true, if_false)| {
+            // nom can only produce a Vec<>; convert to a VecDeque<> for ease of use later.
+            let items = VecDeque::from(item_vec);
+            let inspections = 0;
+            Monkey {
+                id,
+                items,
+                operation,
+                divisor,
+                if_true,
+                if_false,
+                inspections,
+            }
+        },
+    )(input)
+}
+
+/*
+ * Without nom, the same parsers by hand, a line at a time.
+ */
+#[cfg(not(feature = "nom"))]
+fn parse_operation(input: &str) -> Option<Operation> {
+    let rest = input.strip_prefix("new = old ")?;
+    let operator = match rest.get(..1)? {
+        "+" => Operator::Add,
+        "*" => Operator::Multiply,
+        _ => return None,
+    };
+    let operand = match rest[1..].trim_start() {
+        "old" => Operand::Old,
+        value => Operand::Value(value.parse().ok()?),
+    };
+    Some(Operation { operator, operand })
+}
+
+#[cfg(not(feature = "nom"))]
+fn parse_monkey(block: &str) -> Option<Monkey> {
+    let mut lines = block.lines().map(str::trim);
+    let mut field = |prefix: &str| lines.next()?.strip_prefix(prefix);
+
+    let id = field("Monkey ")?.strip_suffix(':')?.parse().ok()?;
+    let items = field("Starting items:")?
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| item.parse().ok())
+        .collect::<Option<VecDeque<u64>>>()?;
+    let operation = parse_operation(field("Operation: ")?)?;
+    let divisor = field("Test: divisible by ")?.parse().ok()?;
+    let if_true = field("If true: throw to monkey ")?.parse().ok()?;
+    let if_false = field("If false: throw to monkey ")?.parse().ok()?;
+
+    Some(Monkey {
+        id,
+        items,
+        operation,
+        divisor,
+        if_true,
+        if_false,
+        inspections: 0,
+    })
+}
+
+/*************************
+ * The actual solutions! *
+ *************************/
+
+/*
+ * What happened when a monkey inspected an item; see trace().
+ */
+struct Inspection {
+    item: u64,
+    operated: u64,
+    worry: u64,
+    catcher: u64,
+}
+
+/**
+ * Take a turn. Returns a list of pairs (item, target_monkey_idx).
+ *
+ * The second parameter here is the "worry reducer". For part 1, it's |x| x / 3.
+ * Part 2 asks us to figure something else out.
+ *
+ * If `log` is given, each inspection is also recorded there.
+ */
+fn turn(
+    monkey: &mut Monkey,
+    worry_reducer: impl Fn(u64) -> u64,
+    mut log: Option<&mut Vec<Inspection>>,
+) -> Vec<(u64, u64)> {
+    monkey
+        .items
+        .drain(..)
+        .map(|item| {
+            monkey.inspections += 1;
+
+            // Update the worry level for this item
+            let operated: u64 = monkey.operation.evaluate(item);
+
+            // Monkey loses interest
+            let worry = worry_reducer(operated);
+
+            // Figure out which monkey to throw the item to.
+            let catcher = if worry.is_multiple_of(monkey.divisor) {
+                monkey.if_true
+            } else {
+                monkey.if_false
+            };
+
+            if let Some(log) = log.as_deref_mut() {
+                log.push(Inspection {
+                    item,
+                    operated,
+                    worry,
+                    catcher,
+                });
+            }
+
+            (worry, catcher)
+        })
+        .collect()
+}
+
+/**
+ * Does a whole round of monkey business: each monkey takes a single turn.
+ */
+fn round(monkeys: &mut [Monkey], worry_reducer: &impl Fn(u64) -> u64) {
+    for idx in 0..monkeys.len() {
+        // What items are being thrown, and to whom?
+        let moves = turn(&mut monkeys[idx], worry_reducer, None);
+
+        // Throw the items to each catching monkey in turn.
+        for (item, to) in moves {
+            monkeys[to as usize].items.push_back(item);
+        }
+    }
+}
+
+/**
+ * Finds the two monkeys with the highest number of items inspected,
+ * and multiplies their inspection counts.
+ */
+fn monkey_business(monkeys: &Vec<Monkey>) -> u64 {
+    // This is a little clunky, but it's a bit faster than sorting
+    // and taking the top two.
+    let mut most: u64 = 0;
+    let mut next: u64 = 0;
+
+    for monkey in monkeys {
+        if monkey.inspections > most {
+            next = most;
+            most = monkey.inspections;
+        } else if monkey.inspections > next {
+            next = monkey.inspections;
+        }
+    }
+
+    most * next
+}
+
+/**
+ * Parses the monkeys' notes.
+ */
+#[cfg(feature = "nom")]
+pub fn parse_monkeys(input: &str) -> Vec<Monkey> {
+    let input = normalize(input);
+    let (_, monkeys) = many1(parse_monkey)(&input).expect("parse error!");
+    monkeys
+}
+
+#[cfg(not(feature = "nom"))]
+pub fn parse_monkeys(input: &str) -> Vec<Monkey> {
+    let input = normalize(input);
+    blocks(&input)
+        .map(|block| parse_monkey(block).expect("parse error!"))
+        .collect()
+}
+
+#[aoc(day11, part1)]
+pub fn part1(input: &str) -> u64 {
+    part1_with(input, 20)
+}
+
+/**
+ * Part 1, but with a different number of rounds.
+ */
+pub fn part1_with(input: &str, rounds: usize) -> u64 {
+    part1_from_monkeys(&parse_monkeys(input), rounds)
+}
+
+/**
+ * Part 1 for monkeys from anywhere (e.g. MonkeyBuilder). The monkeys are
+ * left as they were, so they can be reused.
+ */
+pub fn part1_from_monkeys(monkeys: &[Monkey], rounds: usize) -> u64 {
+    let mut monkeys = monkeys.to_vec();
+    let worry_reducer = |n| n / 3;
+
+    for _ in 0..rounds {
+        round(&mut monkeys, &worry_reducer);
+    }
+
+    monkey_business(&monkeys)
+}
+
+/**
+ * Plays out the first `rounds` rounds of part 1, narrating every
+ * inspection the way the puzzle's walkthrough does ("Monkey inspects an
+ * item with a worry level of 79." and so on), and listing what each monkey
+ * is holding after each round.
+ */
+pub fn trace(input: &str, rounds: usize) -> String {
+    let mut monkeys = parse_monkeys(input);
+    let mut out = String::new();
+
+    // Writing to a String can't fail, so the results of writeln! are ignored.
+    for round in 1..=rounds {
+        for idx in 0..monkeys.len() {
+            let mut log = Vec::new();
+            let moves = turn(&mut monkeys[idx], |n| n / 3, Some(&mut log));
+            for (item, to) in moves {
+                monkeys[to as usize].items.push_back(item);
+            }
+
+            let monkey = &monkeys[idx];
+            let _ = writeln!(out, "Monkey {}:", monkey.id);
+            for inspection in log {
+                let divisible = if inspection.worry % monkey.divisor == 0 {
+                    "is"
+                } else {
+                    "is not"
+                };
+                let _ = writeln!(
+                    out,
+                    "  Monkey inspects an item with a worry level of {}.\n    \
+                     {}\n    \
+                     Monkey gets bored with item. Worry level is divided by 3 to {}.\n    \
+                     Current worry level {divisible} divisible by {}.\n    \
+                     Item with worry level {} is thrown to monkey {}.",
+                    inspection.item,
+                    monkey.operation.describe(inspection.operated),
+                    inspection.worry,
+                    monkey.divisor,
+                    inspection.worry,
+                    inspection.catcher,
+                );
+            }
+        }
+
+        let _ = writeln!(
+            out,
+            "\nAfter round {round}, the monkeys are holding items with these worry levels:"
+        );
+        for monkey in monkeys.iter() {
+            let _ = writeln!(out, "{monkey}");
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+#[aoc(day11, part2)]
+pub fn part2(input: &str) -> u64 {
+    part2_with(input, 10_000)
+}
+
+/**
+ * Part 2, but with a different number of rounds.
+ */
+pub fn part2_with(input: &str, rounds: usize) -> u64 {
+    part2_from_monkeys(&parse_monkeys(input), rounds)
+}
+
+/**
+ * Part 2 for monkeys from anywhere; see part1_from_monkeys().
+ */
+pub fn part2_from_monkeys(monkeys: &[Monkey], rounds: usize) -> u64 {
+    let mut monkeys = monkeys.to_vec();
+
+    // Stupid math trick alert!
+    //
+    // Each monkey cares about computing an item's worry value modulo some prime.
+    // Those remainders don't change if we first take the worry value modulo some multiple
+    // of that prime. By picking the LCM of all of the monkeys' primes, we get a modulus
+    // that has this property for every monkey simultaneously.
+    //
+    // We can then make our worry-reducing function `|n| n % modulus`, which guarantees that
+    // an item's worry value cannot ever be above our modulus.
+    let modulus: u64 = monkeys
+        .iter()
+        .map(|m| m.divisor)
+        .fold(1u64, num::integer::lcm);
+
+    let worry_reducer = |n| n % modulus;
+
+    // There might be a cycle-finding trick in here to reduce runtime, but just simulating
+    // finishes pretty quickly.
+    for _ in 0..rounds {
+        round(&mut monkeys, &worry_reducer);
+    }
+
+    monkey_business(&monkeys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::testing::read_example;
+
+    #[test]
+    fn test_trace() {
+        let Some(input) = read_example("day11.txt") else {
+            return;
+        };
+        let trace = trace(&input, 2);
+
+        // The start of the walkthrough, word for word.
+        let walkthrough = "\
+Monkey 0:
+  Monkey inspects an item with a worry level of 79.
+    Worry level is multiplied by 19 to 1501.
+    Monkey gets bored with item. Worry level is divided by 3 to 500.
+    Current worry level is not divisible by 23.
+    Item with worry level 500 is thrown to monkey 3.
+  Monkey inspects an item with a worry level of 98.
+    Worry level is multiplied by 19 to 1862.
+    Monkey gets bored with item. Worry level is divided by 3 to 620.
+    Current worry level is not divisible by 23.
+    Item with worry level 620 is thrown to monkey 3.
+Monkey 1:
+  Monkey inspects an item with a worry level of 54.
+    Worry level increases by 6 to 60.
+";
+        assert!(trace.starts_with(walkthrough));
+        assert!(trace.contains("    Worry level is multiplied by itself to 6241.\n"));
+        assert!(trace.contains(
+            "\n\
+After round 1, the monkeys are holding items with these worry levels:
+Monkey 0: 20, 23, 27, 26
+Monkey 1: 2080, 25, 167, 207, 401, 1046
+Monkey 2: \nMonkey 3: \n\n"
+        ));
+        assert!(trace.contains("\nAfter round 2, the monkeys are holding"));
+    }
+
+    #[test]
+    fn test_monkey_builder() {
+        let Some(input) = read_example("day11.txt") else {
+            return;
+        };
+        let built = vec![
+            MonkeyBuilder::new(0)
+                .items([79, 98])
+                .operation(Operation::multiply(19))
+                .test(23, 2, 3)
+                .build(),
+            MonkeyBuilder::new(1)
+                .items([54, 65, 75, 74])
+                .operation(Operation::add(6))
+                .test(19, 2, 0)
+                .build(),
+            MonkeyBuilder::new(2)
+                .items([79, 60, 97])
+                .operation(Operation::square())
+                .test(13, 1, 3)
+                .build(),
+            MonkeyBuilder::new(3)
+                .items([74])
+                .operation(Operation::add(3))
+                .test(17, 0, 1)
+                .build(),
+        ];
+        assert_eq!(built, parse_monkeys(&input));
+
+        // The monkeys can be reused after a simulation.
+        assert_eq!(part1_from_monkeys(&built, 20), 10605);
+        assert_eq!(part2_from_monkeys(&built, 10_000), 2713310158);
+        assert_eq!(built[0].inspections(), 0);
+        assert_eq!(built[0].items().collect::<Vec<_>>(), vec![79, 98]);
+    }
+}
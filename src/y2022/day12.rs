@@ -0,0 +1,385 @@
+use std::collections::{HashMap, HashSet};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::AocError;
+use crate::utils::input::normalize_bytes;
+use crate::utils::paths::{bfs_reach, dijkstra};
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HeightMap {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_pairs"))]
+    points: HashMap<(i32, i32), u8>,
+    width: i32,
+    height: i32,
+
+    starts: Vec<(i32, i32)>,
+    ends: Vec<(i32, i32)>,
+}
+
+impl HeightMap {
+    fn new() -> Self {
+        HeightMap {
+            points: HashMap::new(),
+            width: 0,
+            height: 0,
+            starts: Vec::new(),
+            ends: Vec::new(),
+        }
+    }
+
+    /**
+     * Every point marked as a start, in reading order.
+     */
+    pub fn starts(&self) -> &[(i32, i32)] {
+        &self.starts
+    }
+
+    /**
+     * Every point marked as an end (a summit), in reading order.
+     */
+    pub fn ends(&self) -> &[(i32, i32)] {
+        &self.ends
+    }
+}
+
+/*
+ * The searches below are parameterized by two functions of the heights on
+ * either side of a step:
+ *   - can_step(from, to), whether the step is allowed at all, and
+ *   - cost(from, to), how much the step costs (which must not be negative).
+ *
+ * The puzzle's rules are standard_step() and unit_cost().
+ */
+
+/**
+ * You can climb at most one unit at a time, but drop any distance.
+ */
+pub fn standard_step(from: u8, to: u8) -> bool {
+    to <= from + 1
+}
+
+pub fn unit_cost(_from: u8, _to: u8) -> i32 {
+    1
+}
+
+fn neighbors(from: (i32, i32)) -> Vec<(i32, i32)> {
+    vec![
+        (from.0 - 1, from.1),
+        (from.0 + 1, from.1),
+        (from.0, from.1 - 1),
+        (from.0, from.1 + 1),
+    ]
+}
+
+// Returns a list of the points in the grid you could step to from `from`.
+// To make working with the dijkstra implementation easier, it returns
+// a pair (point, cost).
+fn next_steps(
+    map: &HeightMap,
+    from: (i32, i32),
+    can_step: &impl Fn(u8, u8) -> bool,
+    cost: &impl Fn(u8, u8) -> i32,
+) -> Vec<((i32, i32), i32)> {
+    let start_height = map.points[&from];
+
+    neighbors(from)
+        .into_iter()
+        .filter_map(|to| {
+            let height = *map.points.get(&to)?;
+            can_step(start_height, height).then(|| (to, cost(start_height, height)))
+        })
+        .collect()
+}
+
+// Returns a list of the points in the grid from which you could step to `to`.
+fn prev_steps(
+    map: &HeightMap,
+    to: (i32, i32),
+    can_step: &impl Fn(u8, u8) -> bool,
+    cost: &impl Fn(u8, u8) -> i32,
+) -> Vec<((i32, i32), i32)> {
+    let end_height = map.points[&to];
+
+    neighbors(to)
+        .into_iter()
+        .filter_map(|from| {
+            let height = *map.points.get(&from)?;
+            can_step(height, end_height).then(|| (from, cost(height, end_height)))
+        })
+        .collect()
+}
+
+/*
+ * Dijkstra from several points at once: a made-up node (None) sits in front
+ * of all of them, at no cost.
+ */
+fn cheapest<S, G>(starts: &[(i32, i32)], successors: S, is_goal: G) -> Option<i32>
+where
+    S: Fn((i32, i32)) -> Vec<((i32, i32), i32)>,
+    G: Fn((i32, i32)) -> bool,
+{
+    let (_, length) = dijkstra(
+        &None,
+        |p| match p {
+            None => starts.iter().map(|&s| (Some(s), 0)).collect(),
+            Some(p) => successors(*p)
+                .into_iter()
+                .map(|(to, cost)| (Some(to), cost))
+                .collect::<Vec<_>>(),
+        },
+        |p| p.is_some_and(&is_goal),
+    )?;
+
+    Some(length)
+}
+
+/**
+ * The cheapest way from any start to any end, or None if no end can be
+ * reached.
+ */
+pub fn shortest_path(
+    map: &HeightMap,
+    can_step: impl Fn(u8, u8) -> bool,
+    cost: impl Fn(u8, u8) -> i32,
+) -> Option<i32> {
+    shortest_path_to(map, |p| map.ends.contains(&p), can_step, cost)
+}
+
+/**
+ * The cheapest way from any start to any point satisfying `is_goal`, or
+ * None if there's no such point within reach.
+ */
+pub fn shortest_path_to(
+    map: &HeightMap,
+    is_goal: impl Fn((i32, i32)) -> bool,
+    can_step: impl Fn(u8, u8) -> bool,
+    cost: impl Fn(u8, u8) -> i32,
+) -> Option<i32> {
+    cheapest(
+        &map.starts,
+        |p| next_steps(map, p, &can_step, &cost),
+        is_goal,
+    )
+}
+
+/**
+ * The cheapest way to any end from any of the lowest points (height 0),
+ * or None if none of them can reach one.
+ */
+pub fn shortest_path_from_lowest(
+    map: &HeightMap,
+    can_step: impl Fn(u8, u8) -> bool,
+    cost: impl Fn(u8, u8) -> i32,
+) -> Option<i32> {
+    // Walking backwards, find the shortest path from the end points to *any* point with height 0.
+    cheapest(
+        &map.ends,
+        |p| prev_steps(map, p, &can_step, &cost),
+        |p| map.points[&p] == 0,
+    )
+}
+
+/**
+ * Every point that can be reached from `from` (including itself).
+ */
+pub fn reachable_from(
+    map: &HeightMap,
+    from: (i32, i32),
+    can_step: impl Fn(u8, u8) -> bool,
+) -> HashSet<(i32, i32)> {
+    let no_cost = |_: u8, _: u8| 0;
+    bfs_reach(from, |p| {
+        next_steps(map, *p, &can_step, &no_cost)
+            .into_iter()
+            .map(|(to, _)| to)
+    })
+    .collect()
+}
+
+/**
+ * For every point, the set of points reachable from it. This grows with
+ * the square of the map's size, so it's really only meant for small maps.
+ */
+pub fn all_pairs_reachability(
+    map: &HeightMap,
+    can_step: impl Fn(u8, u8) -> bool,
+) -> HashMap<(i32, i32), HashSet<(i32, i32)>> {
+    map.points
+        .keys()
+        .map(|&from| (from, reachable_from(map, from, &can_step)))
+        .collect()
+}
+
+/**
+ * Parses a height map where `start` marks the starting points (at height a)
+ * and `end` marks the summits (at height z). There can be any number of
+ * each, but at least one; anything other than those and a-z is an error.
+ */
+pub fn parse_with(input: &[u8], start: u8, end: u8) -> Result<HeightMap, AocError> {
+    let input = normalize_bytes(input);
+    let mut row: i32 = 0;
+    let mut col: i32 = 0;
+
+    let mut result = HeightMap::new();
+
+    for &c in input.iter() {
+        if c == b'\n' {
+            result.width = col;
+            row += 1;
+            col = 0;
+            continue;
+        }
+
+        let height = if c == start {
+            result.starts.push((row, col));
+            0
+        } else if c == end {
+            result.ends.push((row, col));
+            25
+        } else if c.is_ascii_lowercase() {
+            c - b'a'
+        } else {
+            return Err(AocError::parse(
+                row as usize + 1,
+                format!("unexpected {:?} in column {}", c as char, col + 1),
+            ));
+        };
+        result.points.insert((row, col), height);
+        col += 1;
+    }
+    result.height = row;
+
+    // normalize_bytes() ends the input with a newline, so `row` is the
+    // number of lines, and the last of them is as good a place as any.
+    let missing = |what: &str, marker: u8| {
+        AocError::parse(
+            (row as usize).max(1),
+            format!("there's no {what} (marked {:?})", marker as char),
+        )
+    };
+    if result.starts.is_empty() {
+        return Err(missing("start", start));
+    }
+    if result.ends.is_empty() {
+        return Err(missing("end", end));
+    }
+
+    Ok(result)
+}
+
+#[aoc_generator(day12)]
+pub fn generator(input: &[u8]) -> Result<HeightMap, AocError> {
+    parse_with(input, b'S', b'E')
+}
+
+#[aoc(day12, part1)]
+pub fn part1(input: &HeightMap) -> i32 {
+    shortest_path(input, standard_step, unit_cost).expect("no path found")
+}
+
+#[aoc(day12, part2)]
+pub fn part2(input: &HeightMap) -> i32 {
+    shortest_path_from_lowest(input, standard_step, unit_cost).expect("no path found")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Sabqponm\n\
+                           abcryxxl\n\
+                           accszExk\n\
+                           acctuvwj\n\
+                           abdefghi\n";
+
+    #[test]
+    fn test_part1() {
+        let input = generator(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(part1(&input), 31);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = generator(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(part2(&input), 29);
+    }
+
+    #[test]
+    fn test_other_rules() {
+        let input = generator(EXAMPLE.as_bytes()).unwrap();
+
+        // Climbing costs an extra unit, so the best path just has to
+        // climb all the way from a to z.
+        let climbing_costs_2 = |from: u8, to: u8| if to > from { 2 } else { 1 };
+        assert_eq!(
+            shortest_path(&input, standard_step, climbing_costs_2),
+            Some(56)
+        );
+
+        // If you can only ever climb, there's no way back down to an 'a'.
+        let only_up = |from: u8, to: u8| to == from + 1;
+        assert_eq!(shortest_path(&input, only_up, unit_cost), None);
+        assert_eq!(shortest_path_from_lowest(&input, only_up, unit_cost), None);
+    }
+
+    #[test]
+    fn test_reachability() {
+        let input = generator(b"Sbc\nazE\n").unwrap();
+        let reachable = all_pairs_reachability(&input, standard_step);
+
+        // From S you can get to the b and the a, but not up the cliff to z.
+        assert_eq!(
+            reachable[&(0, 0)],
+            HashSet::from([(0, 0), (0, 1), (1, 0), (0, 2)])
+        );
+        assert_eq!(reachable[&(1, 1)].len(), 6);
+    }
+
+    #[test]
+    fn test_crlf_input() {
+        let input = generator(EXAMPLE.replace('\n', "\r\n").as_bytes()).unwrap();
+        assert_eq!(part1(&input), 31);
+        assert_eq!(part2(&input), 29);
+    }
+
+    #[test]
+    fn test_several_starts_and_ends() {
+        // The second start is only two steps from the second summit (if
+        // you're allowed to climb straight up it).
+        let map = parse_with(b"*aaa!\naa*a!\n", b'*', b'!').unwrap();
+        assert_eq!(map.starts(), &[(0, 0), (1, 2)]);
+        assert_eq!(map.ends(), &[(0, 4), (1, 4)]);
+        assert_eq!(shortest_path(&map, |_, _| true, unit_cost), Some(2));
+
+        // The summit here is out of reach, but a custom goal needn't be.
+        let map = generator(b"Sbcd\nabcE\n").unwrap();
+        assert_eq!(shortest_path(&map, standard_step, unit_cost), None);
+        let is_c = |p| map.points[&p] == 2;
+        assert_eq!(
+            shortest_path_to(&map, is_c, standard_step, unit_cost),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_malformed_maps() {
+        assert_eq!(
+            generator(b"abc\nabE\n").err(),
+            Some(AocError::parse(2, "there's no start (marked 'S')"))
+        );
+        assert_eq!(
+            generator(b"Sbc\nabc\n").err(),
+            Some(AocError::parse(2, "there's no end (marked 'E')"))
+        );
+        assert_eq!(
+            generator(b"Sbc\na#E\n").err(),
+            Some(AocError::parse(2, "unexpected '#' in column 2"))
+        );
+        assert_eq!(
+            generator(b"").err(),
+            Some(AocError::parse(1, "there's no start (marked 'S')"))
+        );
+    }
+}
@@ -0,0 +1,371 @@
+use std::{cmp::Ordering, fmt, iter::zip, slice, str::FromStr};
+
+#[cfg(feature = "nom")]
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::i32,
+    combinator::{all_consuming, map},
+    multi::separated_list0,
+    sequence::delimited,
+    IResult,
+};
+
+use crate::error::AocError;
+use crate::utils::input::normalize;
+
+#[derive(PartialEq, Debug)]
+pub enum Data {
+    Int(i32),
+    List(Vec<Data>),
+}
+
+impl Data {
+    /**
+     * An empty list, `[]`.
+     */
+    pub fn empty() -> Self {
+        Data::List(Vec::new())
+    }
+
+    /**
+     * A list containing a single integer, like the divider packets' `[2]`.
+     */
+    pub fn list_of(value: i32) -> Self {
+        Data::List(vec![Data::Int(value)])
+    }
+
+    /**
+     * Wraps this in another list: `[1]` becomes `[[1]]`.
+     */
+    pub fn nested(self) -> Self {
+        Data::List(vec![self])
+    }
+
+    /**
+     * Appends an item to this list. An integer is first turned into a list
+     * of just itself, the same way comparisons treat it, so pushing 2 onto
+     * 1 gives `[1,2]`.
+     */
+    pub fn push(&mut self, item: Data) {
+        match self {
+            Data::List(items) => items.push(item),
+            Data::Int(value) => *self = Data::List(vec![Data::Int(*value), item]),
+        }
+    }
+
+    /**
+     * How many lists deep the innermost value is: 0 for an integer, 1 for
+     * `[]` or `[1,2]`, 2 for `[[1],2]`, and so on.
+     */
+    pub fn depth(&self) -> usize {
+        match self {
+            Data::Int(_) => 0,
+            Data::List(items) => 1 + items.iter().map(Data::depth).max().unwrap_or(0),
+        }
+    }
+
+    /**
+     * Every integer in the packet, in order, with the lists removed.
+     */
+    pub fn flatten(&self) -> Vec<i32> {
+        let mut values = Vec::new();
+        self.flatten_into(&mut values);
+        values
+    }
+
+    fn flatten_into(&self, values: &mut Vec<i32>) {
+        match self {
+            Data::Int(value) => values.push(*value),
+            Data::List(items) => items.iter().for_each(|item| item.flatten_into(values)),
+        }
+    }
+
+    /**
+     * The sum of every integer in the packet, however deeply nested.
+     */
+    pub fn sum(&self) -> i64 {
+        match self {
+            Data::Int(value) => *value as i64,
+            Data::List(items) => items.iter().map(Data::sum).sum(),
+        }
+    }
+}
+
+impl From<i32> for Data {
+    fn from(value: i32) -> Self {
+        Data::Int(value)
+    }
+}
+
+impl FromIterator<Data> for Data {
+    fn from_iter<T: IntoIterator<Item = Data>>(iter: T) -> Self {
+        Data::List(iter.into_iter().collect())
+    }
+}
+
+/**
+ * Parses a single packet, in the puzzle's bracket syntax.
+ */
+impl FromStr for Data {
+    type Err = AocError;
+
+    fn from_str(packet: &str) -> Result<Self, Self::Err> {
+        parse_packet(packet).ok_or_else(|| AocError::parse(1, "invalid packet"))
+    }
+}
+
+/**
+ * Writes the packet back out in the puzzle's syntax, without spaces, so
+ * that parsing a line and printing it gives back the same line.
+ */
+impl fmt::Display for Data {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Data::Int(value) => write!(f, "{value}"),
+            Data::List(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+impl PartialOrd for Data {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            // If both packets are just numbers, compare them directly.
+            (Data::Int(lhs), Data::Int(rhs)) => lhs.partial_cmp(rhs),
+
+            // If one packet is a number and one a list, treat the number
+            // as a single-element list, and compare lists. A slice of one
+            // element does that without allocating a new list.
+            (Data::Int(_), Data::List(rhs)) => compare_lists(slice::from_ref(self), rhs),
+            (Data::List(lhs), Data::Int(_)) => compare_lists(lhs, slice::from_ref(other)),
+
+            // If both packets are lists, compare elementwise.
+            (Data::List(lhs), Data::List(rhs)) => compare_lists(lhs, rhs),
+        }
+    }
+}
+
+fn compare_lists(lhs: &[Data], rhs: &[Data]) -> Option<Ordering> {
+    for (l, r) in zip(lhs, rhs) {
+        match l.partial_cmp(r) {
+            Some(Ordering::Less) => return Some(Ordering::Less),
+            Some(Ordering::Greater) => return Some(Ordering::Greater),
+            _ => {}
+        }
+    }
+
+    // We got to the end of one of the lists.
+    // Compare the lengths of the lists to finish this element.
+    lhs.len().partial_cmp(&rhs.len())
+}
+
+/**
+ * Parse a single packet into a Data enum.
+ */
+#[cfg(feature = "nom")]
+fn parse_data(data: &str) -> IResult<&str, Data> {
+    alt((
+        // Packets are either integers...
+        map(i32, Data::Int),
+        // ... or comma-separated lists, delimited by [].
+        map(
+            delimited(tag("["), separated_list0(tag(","), parse_data), tag("]")),
+            Data::List,
+        ),
+    ))(data)
+}
+
+#[cfg(feature = "nom")]
+fn parse_packet(packet: &str) -> Option<Data> {
+    all_consuming(parse_data)(packet).ok().map(|(_, data)| data)
+}
+
+/*
+ * Without nom, the same parser by hand: the packet at the start of `data`,
+ * and whatever comes after it.
+ */
+#[cfg(not(feature = "nom"))]
+fn parse_data(data: &str) -> Option<(&str, Data)> {
+    if let Some(mut rest) = data.strip_prefix('[') {
+        let mut list = Vec::new();
+        if let Some(rest) = rest.strip_prefix(']') {
+            return Some((rest, Data::List(list)));
+        }
+        loop {
+            let (after, item) = parse_data(rest)?;
+            list.push(item);
+            match after.as_bytes().first()? {
+                b',' => rest = &after[1..],
+                b']' => return Some((&after[1..], Data::List(list))),
+                _ => return None,
+            }
+        }
+    }
+
+    // Otherwise it's an integer, maybe with a sign.
+    let sign = usize::from(data.starts_with(['-', '+']));
+    let end = data[sign..]
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or(data.len(), |digits| sign + digits);
+    let value = data[..end].parse().ok()?;
+    Some((&data[end..], Data::Int(value)))
+}
+
+#[cfg(not(feature = "nom"))]
+fn parse_packet(packet: &str) -> Option<Data> {
+    match parse_data(packet)? {
+        ("", data) => Some(data),
+        _ => None,
+    }
+}
+
+/**
+ * Parses the input as a list of pairs of packets. Each pair is two lines,
+ * and pairs are separated by a single blank line.
+ */
+#[aoc_generator(day13)]
+pub fn pairs(input: &str) -> Result<Vec<(Data, Data)>, AocError> {
+    let input = normalize(input);
+    let mut pairs = Vec::new();
+
+    // Line numbers are 1-indexed, and each pair takes up 3 lines (including the blank).
+    for (index, block) in input.trim_end().split("\n\n").enumerate() {
+        let first_line = 1 + 3 * index;
+        let lines: Vec<&str> = block.lines().collect();
+        if lines.len() != 2 {
+            return Err(AocError::parse(
+                first_line,
+                format!("expected a pair of packets, found {} line(s)", lines.len()),
+            ));
+        }
+
+        let packet = |offset: usize| {
+            lines[offset]
+                .parse::<Data>()
+                .map_err(|_| AocError::parse(first_line + offset, "invalid packet"))
+        };
+        pairs.push((packet(0)?, packet(1)?));
+    }
+
+    Ok(pairs)
+}
+
+/**
+ * Returns the (1-indexed) numbers of the pairs that are in the right order.
+ */
+pub fn ordered_pairs(input: &str) -> Result<Vec<usize>, AocError> {
+    Ok(ordered(&pairs(input)?).collect())
+}
+
+fn ordered(pairs: &[(Data, Data)]) -> impl Iterator<Item = usize> + '_ {
+    pairs
+        .iter()
+        .enumerate()
+        .filter(|(_, (left, right))| left < right)
+        .map(|(i, _)| i + 1)
+}
+
+#[aoc(day13, part1)]
+pub fn part1(pairs: &[(Data, Data)]) -> usize {
+    ordered(pairs).sum()
+}
+
+#[aoc(day13, part2)]
+pub fn part2(pairs: &[(Data, Data)]) -> usize {
+    let packets = pairs.iter().flat_map(|(left, right)| [left, right]);
+
+    // We can avoid sorting by comparing each divider against every packet.
+    // This is an O(N) operation rather than O(N log N).
+    let divider0 = Data::list_of(2);
+    let divider1 = Data::list_of(6);
+
+    let mut less_than_first = 0;
+    let mut less_than_second = 0;
+
+    for packet in packets {
+        if *packet < divider0 {
+            less_than_first += 1;
+
+            // Optimization! We know [[2]] < [[6]], so if this packet is
+            // less than [[2]] it is definitely less than [[6]] as well.
+            less_than_second += 1;
+        } else if *packet < divider1 {
+            less_than_second += 1;
+        }
+    }
+
+    // The +1 is because the list of packets is 1-indexed.
+    // The +2 is because it's 1-indexed, and we need to count the first divider.
+    (less_than_first + 1) * (less_than_second + 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::testing::read_example;
+
+    #[test]
+    fn test_ordered_pairs() {
+        let Some(input) = read_example("day13.txt") else {
+            return;
+        };
+        assert_eq!(ordered_pairs(&input), Ok(vec![1, 2, 4, 6]));
+    }
+
+    #[test]
+    fn test_pairs_errors() {
+        assert_eq!(
+            pairs("[1]\n[2]\n\n[3]\n").unwrap_err(),
+            AocError::parse(4, "expected a pair of packets, found 1 line(s)")
+        );
+        assert_eq!(
+            pairs("[1]\n[2]\n\n[3]\n[4\n").unwrap_err(),
+            AocError::parse(5, "invalid packet")
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let Some(input) = read_example("day13.txt") else {
+            return;
+        };
+        for line in input.lines().filter(|line| !line.is_empty()) {
+            let packet: Data = line.parse().unwrap();
+            assert_eq!(packet.to_string(), line);
+        }
+
+        assert_eq!(
+            "[1, 2]".parse::<Data>(),
+            Err(AocError::parse(1, "invalid packet"))
+        );
+        assert_eq!("[[]]".parse::<Data>(), Ok(Data::empty().nested()));
+    }
+
+    #[test]
+    fn test_combinators() {
+        let mut packet: Data = [Data::from(1), Data::list_of(2).nested()]
+            .into_iter()
+            .collect();
+        packet.push(Data::empty());
+        assert_eq!(packet.to_string(), "[1,[[2]],[]]");
+        assert_eq!(packet.depth(), 3);
+        assert_eq!(packet.flatten(), vec![1, 2]);
+        assert_eq!(packet.sum(), 3);
+
+        let mut number = Data::from(7);
+        assert_eq!(number.depth(), 0);
+        number.push(Data::from(8));
+        assert_eq!(number, "[7,8]".parse().unwrap());
+        assert_eq!(Data::empty().depth(), 1);
+    }
+}
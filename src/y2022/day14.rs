@@ -0,0 +1,529 @@
+use std::{
+    cmp::{max, min},
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::utils::input::{ints_from_bytes, normalize, normalize_bytes};
+
+/**
+ * The various materials used in this problem.
+ *
+ * It doesn't really matter when simulating whether a grain of sand
+ * is stopped by rock or by more sand, but this makes for a nicer picture.
+ */
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+enum Material {
+    Rock,
+    Air,
+    Sand,
+}
+
+/**
+ * Where the sand comes from in the puzzle.
+ */
+pub const SOURCE: (i32, i32) = (500, 0);
+
+/**
+ * The cave into which we're dropping sand.
+ *
+ * Uses a sparse map of (x,y) -> material, and tracks the bounding
+ * box of all of the walls in the cave so we can tell when sand
+ * starts to escape.
+ *
+ * Sand falls from each of the sources in turn, skipping any that have
+ * been buried.
+ */
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Cave {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_pairs"))]
+    map: HashMap<(i32, i32), Material>,
+    bounds: BoundingBox,
+    sources: Vec<(i32, i32)>,
+    next_source: usize,
+}
+
+/**
+ * A simple 2D bounding box.
+ */
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BoundingBox {
+    x_min: i32,
+    x_max: i32,
+    y_min: i32,
+    y_max: i32,
+}
+
+impl BoundingBox {
+    /**
+     * Extends the bounding box to contain a new point.
+     */
+    fn extend(&mut self, x: i32, y: i32) {
+        self.x_min = min(self.x_min, x);
+        self.x_max = max(self.x_max, x);
+        self.y_min = min(self.y_min, y);
+        self.y_max = max(self.y_max, y);
+    }
+
+    /**
+     * Checks whether this bounding box contains a point.
+     */
+    fn contains(&self, x: i32, y: i32) -> bool {
+        self.x_min <= x && x <= self.x_max && self.y_min <= y && y <= self.y_max
+    }
+}
+
+impl Cave {
+    /**
+     * Creates a new cave with no contents, and sand falling from each of
+     * `sources`.
+     */
+    fn new(sources: &[(i32, i32)]) -> Self {
+        assert!(!sources.is_empty(), "the cave needs a source of sand");
+
+        // The bounding box needs to include the source points,
+        // or the sand would escape as soon as it's dropped.
+        let (x, y) = sources[0];
+        let mut bounds = BoundingBox {
+            x_min: x,
+            x_max: x,
+            y_min: y,
+            y_max: y,
+        };
+        for &(x, y) in sources {
+            bounds.extend(x, y);
+        }
+
+        Cave {
+            map: HashMap::new(),
+            bounds,
+            sources: sources.to_vec(),
+            next_source: 0,
+        }
+    }
+
+    pub fn sources(&self) -> &[(i32, i32)] {
+        &self.sources
+    }
+
+    /**
+     * Gets the material at (x,y), defaulting to Air.
+     */
+    fn get(&self, x: i32, y: i32) -> Material {
+        self.map.get(&(x, y)).copied().unwrap_or(Material::Air)
+    }
+
+    /**
+     * Adds a wall at (x,y), extending the bounding box to include that point.
+     */
+    fn add_wall(&mut self, x: i32, y: i32) {
+        self.map.insert((x, y), Material::Rock);
+        self.bounds.extend(x, y);
+    }
+
+    /**
+     * Finds the next source (in round-robin order) that isn't buried in sand.
+     */
+    fn take_source(&mut self) -> Option<(i32, i32)> {
+        for _ in 0..self.sources.len() {
+            let source = self.sources[self.next_source];
+            self.next_source = (self.next_source + 1) % self.sources.len();
+            if self.get(source.0, source.1) == Material::Air {
+                return Some(source);
+            }
+        }
+        None
+    }
+
+    /**
+     * Simulates dropping a grain of sand from the next source point.
+     *
+     * If the sand stops falling at a point (x,y) within the cave, returns Some((x,y)).
+     * If the sand exits the cave's bounding box, or every source is buried, returns None.
+     */
+    fn add_sand(&mut self) -> Option<(i32, i32)> {
+        let (mut x, mut y) = self.take_source()?;
+
+        loop {
+            // If we've broken out of the bounding box, bail.
+            if !self.bounds.contains(x, y) {
+                return Option::None;
+            }
+
+            // First, try to go straight down.
+            // Note the inverted coordinate system.
+            if self.get(x, y + 1) == Material::Air {
+                y += 1;
+            } else if self.get(x - 1, y + 1) == Material::Air {
+                // Try down-and-left.
+                (x, y) = (x - 1, y + 1);
+            } else if self.get(x + 1, y + 1) == Material::Air {
+                // Try down-and-right.
+                (x, y) = (x + 1, y + 1);
+            } else {
+                // We got stuck! This sand has completed falling.
+                self.map.insert((x, y), Material::Sand);
+                return Option::Some((x, y));
+            }
+        }
+    }
+}
+
+impl fmt::Display for Cave {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for y in self.bounds.y_min..=self.bounds.y_max {
+            for x in self.bounds.x_min..=self.bounds.x_max {
+                match self.get(x, y) {
+                    Material::Rock => {
+                        write!(f, "#")?;
+                    }
+                    Material::Sand => {
+                        write!(f, "o")?;
+                    }
+                    Material::Air => {
+                        write!(f, " ")?;
+                    }
+                }
+            }
+            writeln!(f)?;
+        }
+        fmt::Result::Ok(())
+    }
+}
+
+fn parse_coords(s: &str) -> (i32, i32) {
+    let (x, y) = s.split_once(',').unwrap();
+    (x.parse::<i32>().unwrap(), y.parse::<i32>().unwrap())
+}
+
+/**
+ * Adds a wall that runs through each of `corners` in turn.
+ */
+fn add_walls(cave: &mut Cave, corners: impl IntoIterator<Item = (i32, i32)>) {
+    let mut corners = corners.into_iter();
+    let mut current = corners.next().unwrap();
+
+    // For each set of coordinates, draw a wall from the current point to that coordinate
+    for corner in corners {
+        // Only one of these loops will do something useful.
+        for x in min(current.0, corner.0)..=max(current.0, corner.0) {
+            cave.add_wall(x, current.1);
+        }
+
+        for y in min(current.1, corner.1)..=max(current.1, corner.1) {
+            cave.add_wall(current.0, y);
+        }
+
+        // Update the current point for the next wall segment.
+        current = corner;
+    }
+}
+
+#[aoc_generator(day14)]
+pub fn generator(input: &str) -> Cave {
+    generator_with_sources(input, &[SOURCE])
+}
+
+/**
+ * Reads the cave like generator(), but with sand falling from each of
+ * `sources` in turn instead of just from (500, 0).
+ */
+pub fn generator_with_sources(input: &str, sources: &[(i32, i32)]) -> Cave {
+    let input = normalize(input);
+    let mut cave = Cave::new(sources);
+
+    for line in input.lines() {
+        add_walls(&mut cave, line.split(" -> ").map(parse_coords));
+    }
+
+    cave
+}
+
+/*
+ * The same as generator(), but pulling the coordinates straight out of the
+ * input bytes instead of splitting and parsing strs.
+ */
+#[aoc_generator(day14, part1, Bytes)]
+pub fn generator_bytes(input: &[u8]) -> Cave {
+    let input = normalize_bytes(input);
+    let mut cave = Cave::new(&[SOURCE]);
+
+    for line in input.split(|&b| b == b'\n').filter(|line| !line.is_empty()) {
+        let mut coords = ints_from_bytes(line).map(|n| n as i32);
+        let corners = std::iter::from_fn(|| Some((coords.next()?, coords.next()?)));
+        add_walls(&mut cave, corners);
+    }
+
+    cave
+}
+
+// cargo-aoc looks up generators per part, so part 2 needs its own entry.
+#[aoc_generator(day14, part2, Bytes)]
+fn generator_bytes_part2(input: &[u8]) -> Cave {
+    generator_bytes(input)
+}
+
+impl Cave {
+    /**
+     * Adds an "infinite" floor (i.e., wide enough so that sand must
+     * reach the source points before it falls off the floor).
+     */
+    fn add_floor(&mut self) {
+        let floor_height = self.bounds.y_max + 2;
+        for x in self.bounds.x_min - 500..self.bounds.x_max + 500 {
+            self.add_wall(x, floor_height);
+        }
+    }
+
+    /**
+     * Drops sand until a grain falls out of the cave or every source is
+     * buried, and returns how many grains came to rest.
+     */
+    fn fill(&mut self) -> u32 {
+        let mut count = 0;
+        while self.add_sand().is_some() {
+            count += 1;
+        }
+        count
+    }
+}
+
+/**
+ * The cave after all of the sand has fallen: as it is at the end of part 1,
+ * or with the floor from part 2 if `with_floor` is set.
+ */
+pub fn final_state(input: &Cave, with_floor: bool) -> Cave {
+    let mut cave = input.clone();
+    if with_floor {
+        cave.add_floor();
+    }
+    cave.fill();
+    cave
+}
+
+/**
+ * Some statistics about where the sand ended up in a cave.
+ */
+#[derive(Debug)]
+pub struct SandStats {
+    pub grains: usize,
+
+    // The largest y coordinate of any grain of sand.
+    pub max_depth: i32,
+
+    // The row whose sand spans the most columns, as (y, width).
+    // If there's a tie, it's the highest one.
+    pub widest_row: (i32, i32),
+
+    // Air cells that sand could never get to, because they're sheltered
+    // by rock. Only cells below the sources, within the diagonals that
+    // sand can spread along, count (otherwise all of the air off to the
+    // sides would too).
+    pub sheltered: HashSet<(i32, i32)>,
+}
+
+/**
+ * Collects the statistics for a cave (normally a final_state()).
+ */
+pub fn analyze(cave: &Cave) -> SandStats {
+    let mut grains = 0;
+    let mut max_depth = i32::MIN;
+    let mut rows: HashMap<i32, (i32, i32)> = HashMap::new();
+    for (&(x, y), material) in cave.map.iter() {
+        if *material != Material::Sand {
+            continue;
+        }
+        grains += 1;
+        max_depth = max(max_depth, y);
+        let row = rows.entry(y).or_insert((x, x));
+        *row = (min(row.0, x), max(row.1, x));
+    }
+
+    let widest_row = rows
+        .iter()
+        .map(|(&y, &(left, right))| (y, right - left + 1))
+        .min_by_key(|&(y, width)| (-width, y))
+        .unwrap_or((0, 0));
+
+    SandStats {
+        grains,
+        max_depth,
+        widest_row,
+        sheltered: sheltered_cells(cave),
+    }
+}
+
+/**
+ * Flood fills from the sources, moving the same ways that sand can (and
+ * passing through sand as well as air), then returns the air cells below
+ * the sources that the fill didn't reach.
+ */
+fn sheltered_cells(cave: &Cave) -> HashSet<(i32, i32)> {
+    let mut reachable: HashSet<(i32, i32)> = HashSet::new();
+    let mut stack: Vec<(i32, i32)> = cave.sources.clone();
+    while let Some((x, y)) = stack.pop() {
+        if !cave.bounds.contains(x, y)
+            || cave.get(x, y) == Material::Rock
+            || !reachable.insert((x, y))
+        {
+            continue;
+        }
+        stack.extend([(x - 1, y + 1), (x, y + 1), (x + 1, y + 1)]);
+    }
+
+    let mut sheltered = HashSet::new();
+    for y in cave.bounds.y_min..=cave.bounds.y_max {
+        for x in cave.bounds.x_min..=cave.bounds.x_max {
+            let below_source = cave
+                .sources
+                .iter()
+                .any(|&(sx, sy)| y >= sy && (x - sx).abs() <= y - sy);
+            if below_source && cave.get(x, y) == Material::Air && !reachable.contains(&(x, y)) {
+                sheltered.insert((x, y));
+            }
+        }
+    }
+    sheltered
+}
+
+#[aoc(day14, part1)]
+pub fn part1(input: &Cave) -> u32 {
+    // Simulate until we try to drop a grain of sand and it falls out of the cave.
+    input.clone().fill()
+}
+
+#[aoc(day14, part2)]
+pub fn part2(input: &Cave) -> i32 {
+    let mut cave = input.clone();
+    cave.add_floor();
+
+    // Simulate until the sand has buried every source.
+    cave.fill() as i32
+}
+
+#[aoc(day14, part1, Bytes)]
+pub fn part1_bytes(input: &Cave) -> u32 {
+    part1(input)
+}
+
+#[aoc(day14, part2, Bytes)]
+pub fn part2_bytes(input: &Cave) -> i32 {
+    part2(input)
+}
+
+/**
+ * Part 2 again, but without dropping any sand. Once the floor is in, every
+ * cell that sand can reach ends up full of it, and sand can reach a cell
+ * when it isn't rock and the cell above it (or one of the two diagonally
+ * above it) can be reached. So this sweeps down a row at a time from the
+ * sources to the floor, counting the reachable cells.
+ */
+#[aoc(day14, part2, FloodFill)]
+pub fn part2_flood_fill(input: &Cave) -> i32 {
+    let top = input.bounds.y_min;
+    let floor = input.bounds.y_max + 2;
+
+    // Sand spreads at most one column either way per row, so it can't get
+    // further than this from the rock and the sources.
+    let x_min = input.bounds.x_min - (floor - top);
+    let width = (input.bounds.x_max - input.bounds.x_min + 1 + 2 * (floor - top)) as usize;
+
+    let mut above = vec![false; width];
+    let mut row = vec![false; width];
+    let mut count = 0;
+    for y in top..floor {
+        for (i, reachable) in row.iter_mut().enumerate() {
+            let x = x_min + i as i32;
+            *reachable = input.get(x, y) != Material::Rock
+                && (above[i]
+                    || (i > 0 && above[i - 1])
+                    || above.get(i + 1) == Some(&true)
+                    || input.sources.contains(&(x, y)));
+        }
+        count += row.iter().filter(|&&reachable| reachable).count();
+        std::mem::swap(&mut above, &mut row);
+    }
+    count as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "498,4 -> 498,6 -> 496,6\n\
+                           503,4 -> 502,4 -> 502,9 -> 494,9";
+
+    #[test]
+    fn test_part1() {
+        let input = generator(EXAMPLE);
+        assert_eq!(part1(&input), 24);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = generator(EXAMPLE);
+        assert_eq!(part2(&input), 93);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let json = serde_json::to_string(&generator(EXAMPLE)).unwrap();
+        let cave: super::Cave = serde_json::from_str(&json).unwrap();
+        assert_eq!(part1(&cave), 24);
+    }
+
+    #[test]
+    fn test_multiple_sources() {
+        let mut cave = generator_with_sources(EXAMPLE, &[SOURCE, (497, 0)]);
+        assert_eq!(cave.add_sand(), Some((500, 8)));
+        assert_eq!(cave.add_sand(), Some((497, 5)));
+        assert_eq!(cave.add_sand(), Some((499, 8)));
+
+        // A buried source is skipped, and filling only stops once every
+        // source is buried, so a repeated source changes nothing.
+        let cave = generator_with_sources(EXAMPLE, &[SOURCE, SOURCE]);
+        assert_eq!(part1(&cave), 24);
+        assert_eq!(part2(&cave), 93);
+    }
+
+    #[test]
+    fn test_analyze() {
+        let cave = final_state(&generator(EXAMPLE), true);
+        let stats = analyze(&cave);
+        assert_eq!(stats.grains, 93);
+        assert_eq!(stats.max_depth, 10);
+        assert_eq!(stats.widest_row, (10, 21));
+
+        // Within the 11 rows above the floor, every cell below the source
+        // is either sand, rock, or sheltered under the rock.
+        assert!(stats.sheltered.contains(&(497, 7)));
+        assert_eq!(stats.sheltered.len(), 8);
+        let rock = (0..=10)
+            .flat_map(|y: i32| (-y..=y).map(move |dx| (500 + dx, y)))
+            .filter(|&(x, y)| cave.get(x, y) == Material::Rock)
+            .count();
+        assert_eq!(stats.grains + rock + stats.sheltered.len(), 121);
+    }
+
+    #[test]
+    fn test_generator_bytes() {
+        let input = generator_bytes(EXAMPLE.as_bytes());
+        assert_eq!(part1(&input), 24);
+        assert_eq!(part2(&input), 93);
+    }
+
+    #[test]
+    fn test_part2_flood_fill() {
+        assert_eq!(part2_flood_fill(&generator(EXAMPLE)), 93);
+
+        let cave = generator_with_sources(EXAMPLE, &[SOURCE, (497, 0), (520, 3)]);
+        assert_eq!(part2_flood_fill(&cave), part2(&cave));
+    }
+}
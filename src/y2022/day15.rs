@@ -0,0 +1,287 @@
+use std::collections::HashSet;
+
+#[cfg(feature = "nom")]
+use nom::{
+    bytes::complete::tag,
+    character::complete::i32,
+    character::complete::multispace0,
+    combinator::map,
+    multi::many1,
+    sequence::{delimited, pair, preceded, tuple},
+    IResult,
+};
+
+use crate::utils::input::normalize;
+use crate::utils::interval::{self, IntervalSet};
+use crate::utils::progress::{NoProgress, Progress};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+type Interval = interval::Interval<i32>;
+
+#[cfg(feature = "nom")]
+fn parse_point(input: &str) -> IResult<&str, Point> {
+    map(
+        pair(preceded(tag("x="), i32), preceded(tag(", y="), i32)),
+        |(x, y)| Point { x, y },
+    )(input)
+}
+
+#[cfg(feature = "nom")]
+fn parse_line(input: &str) -> IResult<&str, (Point, Point)> {
+    tuple((
+        preceded(tag("Sensor at "), parse_point),
+        delimited(tag(": closest beacon is at "), parse_point, multispace0),
+    ))(input)
+}
+
+#[cfg(feature = "nom")]
+fn parse_lines(input: &str) -> Vec<(Point, Point)> {
+    let (_, lines) = many1(parse_line)(input).expect("parsing error");
+    lines
+}
+
+/*
+ * Without nom, the same parsers as above, written out by hand.
+ */
+#[cfg(not(feature = "nom"))]
+fn parse_point(input: &str) -> Option<Point> {
+    let (x, y) = input.strip_prefix("x=")?.split_once(", y=")?;
+    Some(Point {
+        x: x.parse().ok()?,
+        y: y.parse().ok()?,
+    })
+}
+
+#[cfg(not(feature = "nom"))]
+fn parse_line(input: &str) -> Option<(Point, Point)> {
+    let (sensor, beacon) = input
+        .strip_prefix("Sensor at ")?
+        .split_once(": closest beacon is at ")?;
+    Some((parse_point(sensor)?, parse_point(beacon)?))
+}
+
+#[cfg(not(feature = "nom"))]
+fn parse_lines(input: &str) -> Vec<(Point, Point)> {
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| parse_line(line).expect("parsing error"))
+        .collect()
+}
+
+fn get_covered_intervals(points_and_beacons: &[(Point, Point)], target_y: i32) -> IntervalSet<i32> {
+    // For part 1 we want to find the number of points at y=2_000_000 that
+    // *cannot* be the location of another beacon.
+    //
+    // The approach is to consider each point in turn. Since we know which beacon
+    // is closest to that point, it has a sort of zone of exclusion where a new
+    // beacon cannot be placed. We'll generate a list of (possibly-empty) intervals
+    // where those exclusion zones intersect with the line y=2_000_000, then count
+    // the total number of points in those intervals.
+    //
+    // To avoid double-counting, the IntervalSet merges the intervals into
+    // non-overlapping ones as they're collected.
+    points_and_beacons
+        .iter()
+        .filter_map(|(point, beacon)| {
+            // The distance to the nearest beacon. Any other beacon must be further than this.
+            let distance = (beacon.x - point.x).abs() + (beacon.y - point.y).abs();
+
+            // If we travel directly from the point to the line at y = target_y, we'll
+            // use some amount of our distance "budget" to get there.
+            let distance_to_line = (target_y - point.y).abs();
+
+            // The remaining distance budget can be spent spreading horizontally along the line.
+            let spread = distance - distance_to_line;
+            if spread >= 0 {
+                Some(Interval::new(point.x - spread, point.x + spread))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/**
+ * Parses each sensor's position, along with its closest beacon's.
+ */
+#[aoc_generator(day15)]
+pub fn generator(input: &str) -> Vec<(Point, Point)> {
+    parse_lines(&normalize(input))
+}
+
+#[aoc(day15, part1)]
+pub fn part1(points_and_beacons: &[(Point, Point)]) -> i32 {
+    part1_with(points_and_beacons, 2_000_000)
+}
+
+/**
+ * Part 1, checking row `row` instead of y=2_000_000.
+ */
+pub fn part1_with(points_and_beacons: &[(Point, Point)], row: i32) -> i32 {
+    let intervals = get_covered_intervals(points_and_beacons, row);
+    let covered_points = intervals.len();
+
+    // The problem apparently wants us to avoid counting points that already have beacons.
+    let beacons: HashSet<&Point> = points_and_beacons
+        .iter()
+        .map(|(_, beacon)| beacon)
+        .collect();
+    let beacons_on_line = beacons.iter().filter(|beacon| beacon.y == row).count();
+
+    covered_points - (beacons_on_line as i32)
+}
+
+/********************
+ * Stuff for Part 2 *
+ ********************/
+
+fn tuning_frequency(point: &Point) -> usize {
+    (point.x as usize) * 4_000_000 + (point.y as usize)
+}
+
+/**
+ * Finds every point with x- and y-coordinates of at most max_coord that is not
+ * covered by any of the beacons identified. For a valid puzzle input there is
+ * exactly one; more than that means the input is ambiguous.
+ *
+ * This is a very brute-force approach: we just go one y-coordinate at a time and check
+ * whether there are any uncovered points with that y-coordinate.
+ */
+pub fn uncovered_points(points_and_beacons: &[(Point, Point)], max_coord: i32) -> Vec<Point> {
+    uncovered_points_with_progress(points_and_beacons, max_coord, &NoProgress)
+}
+
+fn uncovered_points_with_progress(
+    points_and_beacons: &[(Point, Point)],
+    max_coord: i32,
+    progress: &dyn Progress,
+) -> Vec<Point> {
+    let rows = max_coord as usize + 1;
+    (0..=max_coord)
+        .flat_map(|y| {
+            // Every row is quick on its own, so only report every so often.
+            let done = y as usize + 1;
+            if done.is_multiple_of(10_000) || done == rows {
+                progress.update("rows scanned", done, rows);
+            }
+            uncovered_points_in_row(points_and_beacons, max_coord, y)
+        })
+        .collect()
+}
+
+/*
+ * Same search as above, but with the rows split across threads.
+ */
+#[cfg(feature = "parallel")]
+fn uncovered_points_parallel(points_and_beacons: &[(Point, Point)], max_coord: i32) -> Vec<Point> {
+    use rayon::prelude::*;
+
+    // rayon only splits half-open ranges evenly, hence not 0..=max_coord.
+    (0..max_coord + 1)
+        .into_par_iter()
+        .with_min_len(1024)
+        .flat_map_iter(|y| uncovered_points_in_row(points_and_beacons, max_coord, y))
+        .collect()
+}
+
+fn uncovered_points_in_row(
+    points_and_beacons: &[(Point, Point)],
+    max_coord: i32,
+    y: i32,
+) -> Vec<Point> {
+    // Get rid of all points outside of [0, max_coord]
+    let bounds = Interval::new(0, max_coord);
+    let clamped = get_covered_intervals(points_and_beacons, y).clamp(&bounds);
+
+    // Count the points. Almost every row is fully covered.
+    if clamped.len() == max_coord + 1 {
+        return Vec::new();
+    }
+
+    // Go over the list of intervals to find the gaps, including any
+    // at the very start or end of the row.
+    let mut uncovered = Vec::new();
+    let mut next_x = 0;
+    for interval in clamped.intervals() {
+        uncovered.extend((next_x..interval.start).map(|x| Point { x, y }));
+        next_x = interval.end + 1;
+    }
+    uncovered.extend((next_x..=max_coord).map(|x| Point { x, y }));
+    uncovered
+}
+
+/**
+ * Returns the only point in the list, panicking if the input had no
+ * uncovered points or more than one.
+ */
+fn unique_point(points: Vec<Point>) -> Point {
+    match points[..] {
+        [point] => point,
+        [] => panic!("no uncovered points found"),
+        _ => panic!(
+            "input is ambiguous: found {} uncovered points",
+            points.len()
+        ),
+    }
+}
+
+#[aoc(day15, part2)]
+pub fn part2(points_and_beacons: &[(Point, Point)]) -> usize {
+    part2_with(points_and_beacons, 4_000_000)
+}
+
+/**
+ * Part 2, searching [0, max_coord] in each direction instead of [0, 4_000_000].
+ */
+pub fn part2_with(points_and_beacons: &[(Point, Point)], max_coord: i32) -> usize {
+    part2_with_progress(points_and_beacons, max_coord, &NoProgress)
+}
+
+/**
+ * Part 2, reporting how many rows have been scanned as it goes.
+ */
+pub fn part2_with_progress(
+    points_and_beacons: &[(Point, Point)],
+    max_coord: i32,
+    progress: &dyn Progress,
+) -> usize {
+    let uncovered = uncovered_points_with_progress(points_and_beacons, max_coord, progress);
+    tuning_frequency(&unique_point(uncovered))
+}
+
+#[cfg(feature = "parallel")]
+#[aoc(day15, part2, Parallel)]
+pub fn part2_parallel(points_and_beacons: &[(Point, Point)]) -> usize {
+    let new_beacon = unique_point(uncovered_points_parallel(points_and_beacons, 4_000_000));
+    tuning_frequency(&new_beacon)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::testing::read_example;
+
+    #[test]
+    fn test_uncovered_points() {
+        let Some(input) = read_example("day15.txt") else {
+            return;
+        };
+        assert_eq!(
+            uncovered_points(&generator(&input), 20),
+            vec![Point { x: 14, y: 11 }]
+        );
+
+        // Widening the search area leaves plenty of places the beacon could be.
+        let points = uncovered_points(&generator(&input), 30);
+        assert!(points.len() > 1);
+        assert!(points.contains(&Point { x: 14, y: 11 }));
+        assert!(points.contains(&Point { x: 30, y: 30 }));
+    }
+}
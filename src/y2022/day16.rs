@@ -0,0 +1,1057 @@
+#[cfg(feature = "nom")]
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, multispace0, u32},
+    combinator::all_consuming,
+    multi::separated_list1,
+    sequence::{delimited, preceded, tuple},
+    IResult,
+};
+use std::{cmp::max, collections::HashMap};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::AocError;
+use crate::utils::cancel::{CancelToken, Cancelled};
+use crate::utils::paths::dijkstra;
+use crate::utils::progress::{NoProgress, Progress};
+use crate::utils::trace::{debug_event, enter_span, Counter};
+use crate::utils::{bitset::Bitset, input::normalize};
+
+// For efficiency (and convenience!) we'll store room status in a bitset.
+// It's fine for this problem, since we only have ~15 relevant nodes.
+// The bitset holds 32, and the starting room needs one of them.
+const MAX_VALVES: usize = 31;
+
+/**
+ * A naive representation of the graph of rooms.
+ *
+ * Graph.nodes maps room labels to flow capacity;
+ * Graph.nodes map room labels to { labels of connected rooms }.
+ *
+ * This is quite inefficient, so this representation is mostly used
+ * as an intermediate step to producing a much more optimized version.
+ */
+struct Graph {
+    nodes: HashMap<String, u32>,
+    edges: HashMap<String, Vec<String>>,
+}
+
+/*
+ * One line of the input: the valve's label, its flow rate, and the valves
+ * its tunnels lead to.
+ */
+#[cfg(feature = "nom")]
+fn parse_room(input: &str) -> Option<(&str, u32, Vec<&str>)> {
+    let room = tuple((
+        preceded(tag("Valve "), alpha1),
+        preceded(tag(" has flow rate="), u32),
+        delimited(
+            alt((
+                tag("; tunnels lead to valves "),
+                tag("; tunnel leads to valve "),
+            )),
+            separated_list1(tag(", "), alpha1),
+            multispace0,
+        ),
+    ));
+    let result: IResult<&str, _> = all_consuming(room)(input);
+    result.ok().map(|(_, room)| room)
+}
+
+#[cfg(not(feature = "nom"))]
+fn parse_room(input: &str) -> Option<(&str, u32, Vec<&str>)> {
+    let (label, rest) = input
+        .strip_prefix("Valve ")?
+        .split_once(" has flow rate=")?;
+    let (flow, rest) = rest.split_once("; ")?;
+    let tunnels = rest
+        .strip_prefix("tunnels lead to valves ")
+        .or_else(|| rest.strip_prefix("tunnel leads to valve "))?;
+    let tunnels: Vec<&str> = tunnels.trim_end().split(", ").collect();
+
+    let is_label =
+        |label: &str| !label.is_empty() && label.bytes().all(|b| b.is_ascii_alphabetic());
+    if !is_label(label) || !tunnels.iter().all(|tunnel| is_label(tunnel)) {
+        return None;
+    }
+    Some((label, flow.parse().ok()?, tunnels))
+}
+
+/**
+ * Parses the rooms, checking that every tunnel leads to a room that's
+ * listed, that there's an AA to start from and that every valve with a
+ * nonzero flow can be reached from it, so that a typo is reported
+ * with its line number rather than turning up as a panic halfway through
+ * compress_graph().
+ */
+fn parse_graph(input: &str) -> Result<Graph, AocError> {
+    enter_span!("parse_graph");
+    let mut rooms = Vec::new();
+    for (index, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let room = parse_room(line.trim_start()).ok_or_else(|| {
+            AocError::parse(index + 1, format!("expected a valve but got {line:?}"))
+        })?;
+        rooms.push((index + 1, room));
+    }
+
+    let mut flows = HashMap::new();
+    let mut neighbors: HashMap<String, Vec<String>> = HashMap::new();
+
+    for (line, (label, flow, tunnels)) in rooms.iter() {
+        // CompressedGraph stores flows as u16s.
+        if *flow > u16::MAX as u32 {
+            return Err(AocError::parse(
+                *line,
+                format!("valve {label}'s flow rate of {flow} is too large"),
+            ));
+        }
+        if flows.insert(label.to_string(), *flow).is_some() {
+            return Err(AocError::parse(
+                *line,
+                format!("valve {label} is listed twice"),
+            ));
+        }
+        neighbors.insert(
+            label.to_string(),
+            tunnels.iter().map(|l| l.to_string()).collect(),
+        );
+    }
+
+    for (line, (label, _, tunnels)) in rooms.iter() {
+        if let Some(unknown) = tunnels.iter().find(|l| !flows.contains_key(**l)) {
+            return Err(AocError::parse(
+                *line,
+                format!("valve {label} has a tunnel to unknown valve {unknown}"),
+            ));
+        }
+    }
+    if !flows.contains_key("AA") {
+        return Err(AocError::parse(
+            input.lines().count() + 1,
+            "there's no valve AA to start from",
+        ));
+    }
+
+    // compress_graph() needs a path from AA to every valve worth opening.
+    let mut reached = vec!["AA"];
+    let mut frontier = vec!["AA"];
+    while let Some(label) = frontier.pop() {
+        for next in neighbors[label].iter() {
+            if !reached.contains(&next.as_str()) {
+                reached.push(next);
+                frontier.push(next);
+            }
+        }
+    }
+    if let Some((line, (label, _, _))) = rooms
+        .iter()
+        .find(|(_, (label, flow, _))| *flow > 0 && !reached.contains(label))
+    {
+        return Err(AocError::parse(
+            *line,
+            format!("valve {label} can't be reached from AA"),
+        ));
+    }
+
+    let valves = flows.values().filter(|flow| **flow > 0).count();
+    if valves > MAX_VALVES {
+        return Err(AocError::parse(
+            input.lines().count() + 1,
+            format!(
+                "{valves} valves have a nonzero flow rate, but at most {MAX_VALVES} are supported"
+            ),
+        ));
+    }
+
+    Ok(Graph {
+        nodes: flows,
+        edges: neighbors,
+    })
+}
+
+/* The full graph has a lot of nodes with value 0.
+ * We don't really care about those, so after reading in the full graph,
+ * we'll do some work to "compress" it:
+ *   1. Eliminate zero-valued nodes.
+ *   2. Precompute pairwise distances between all nonzero-valued nodes
+ *      (effectively converting it to a complete graph).
+ *   3. Represent the nodes with integers instead of strings
+ *   4. Store values and edge weights in arrays for fast lookup. These are
+ *      u16s, in one flat row-major Vec for the distances, so that the whole
+ *      graph fits in a handful of cache lines.
+ *
+ * The starting node is always mapped to the highest ID to help optimize some later stuff;
+ * specifically, its ID is always graph.size - 1.
+ *
+ * The original labels are kept (indexed by ID) so that results can be
+ * related back to the input.
+ */
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CompressedGraph {
+    flows: Vec<u16>,
+    distances: Vec<u16>,
+    labels: Vec<String>,
+    size: usize,
+}
+
+impl CompressedGraph {
+    /**
+     * The number of important rooms, including the starting room.
+     */
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /**
+     * The ID of the starting room (AA).
+     */
+    pub fn start_room(&self) -> usize {
+        self.size - 1
+    }
+
+    pub fn label(&self, room: usize) -> &str {
+        &self.labels[room]
+    }
+
+    /**
+     * Looks up a room's ID by its label. Only important rooms (AA and the
+     * ones with nonzero flow) have IDs.
+     */
+    pub fn room_id(&self, label: &str) -> Option<usize> {
+        self.labels.iter().position(|l| l == label)
+    }
+
+    #[inline]
+    pub fn flow(&self, room: usize) -> u32 {
+        self.flows[room] as u32
+    }
+
+    /**
+     * The number of minutes it takes to walk from one room to another.
+     */
+    #[inline]
+    pub fn distance(&self, from: usize, to: usize) -> u32 {
+        self.distances[from * self.size + to] as u32
+    }
+}
+
+fn compress_graph(graph: &Graph) -> CompressedGraph {
+    enter_span!("compress_graph");
+    // Find all of the rooms with nonzero flow.
+    // These (and AA) are the only ones we actually care about.
+    let important_rooms: Vec<String> = graph
+        .nodes
+        .iter()
+        .filter_map(|(label, flow)| {
+            if label == "AA" || *flow > 0 {
+                Some(label.to_owned())
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    // Find pairwise distances between each of the important rooms.
+    // This uses N^2 runs of Dijkstra, where N is the number of important rooms.
+    // There is probably a more efficient way - I thought about Floyd-Warshall
+    // but there are quite a lot of unimportant rooms. *shrug*
+    let mut pairwise_distances: HashMap<(String, String), u32> = HashMap::new();
+    for source in important_rooms.iter() {
+        for dest in important_rooms.iter() {
+            if source == dest {
+                continue;
+            }
+
+            let (_, distance) = dijkstra(
+                source,
+                |room| {
+                    // parse_graph() checked that every room has an entry.
+                    graph.edges[room].iter().map(|label| (label.clone(), 1))
+                },
+                |room| *room == *dest,
+            )
+            .expect("parse_graph() checked that every valve is reachable");
+
+            pairwise_distances.insert((source.to_owned(), dest.to_owned()), distance);
+        }
+    }
+
+    // Okay, we've reduced the graph to the nodes we care about.
+    // Let's produce an efficient representation of that smaller graph.
+
+    // First, convert string labels to numeric IDs.
+    // There is some hackiness to make sure that "AA" has the largest ID.
+    // This is messy now but greatly simplifies some things later.
+    let mut label_to_id: HashMap<String, usize> = HashMap::new();
+    for label in important_rooms.iter() {
+        if label != "AA" {
+            label_to_id.insert(label.to_owned(), label_to_id.len());
+        }
+    }
+    label_to_id.insert(String::from("AA"), label_to_id.len());
+
+    // Convert the HashMap of flows to a flat array, indexed by room ID.
+    // parse_graph() checked that the flows fit.
+    let size = important_rooms.len();
+    let mut flows = vec![0; size];
+    for label in important_rooms.iter() {
+        let room_id = label_to_id[label];
+        flows[room_id] = graph.nodes[label] as u16;
+    }
+
+    // Instead of using nested HashMaps to store distances between nodes,
+    // use a 2d array, indexed by source ID and destination ID.
+    // Paths never repeat a room, so there are far fewer than 2^16 steps.
+    let mut distances = vec![0; size * size];
+    for ((source, dest), distance) in pairwise_distances.iter() {
+        let source_id = label_to_id[source];
+        let dest_id = label_to_id[dest];
+        distances[source_id * size + dest_id] = *distance as u16;
+    }
+
+    let mut labels = vec![String::new(); important_rooms.len()];
+    for (label, id) in label_to_id {
+        labels[id] = label;
+    }
+
+    CompressedGraph {
+        flows,
+        distances,
+        labels,
+        size,
+    }
+}
+
+/**
+ * Uses backtracking to find the maximum release-able pressure.
+ * Inputs:
+ *   - the compressed graph we're computing over
+ *   - the time remaining
+ *   - the current room (represented as an ID)
+ *   - the set of rooms we could visit next (as a Bitset)
+ *   - a token to give up early with (the result is meaningless then)
+ *
+ * Returns:
+ *   - the maximum pressure releasable in the remaining time.
+ *
+ * Preconditions:
+ *   - the current room was an active room in the previous step.
+ *     Because we're working over a complete graph, this means that
+ *     after moving to a room, we *always* want to open its valve.
+ */
+fn backtrack(
+    graph: &CompressedGraph,
+    time_remaining: u32,
+    current_room: usize,
+    active_rooms: Bitset,
+    explored: &Counter,
+    cancel: &CancelToken,
+) -> u32 {
+    explored.increment();
+    if cancel.is_cancelled() {
+        return 0;
+    }
+
+    // If there's 0 minutes left, we're done.
+    // If there's 1 minute left, we can spend it by either
+    //   - opening the valve in the current room
+    //   - go to another room
+    //   - do nothing
+    // None of these release any pressure, so just do nothing.
+    if time_remaining <= 1 {
+        return 0;
+    }
+
+    // If there's exactly 2 minutes remaining, the only way to actually
+    // release any pressure is to open the valve in the current room.
+    // This takes one minute, and the last minute is spent releasing pressure.
+    if time_remaining == 2 {
+        return graph.flow(current_room);
+    }
+
+    // There are at least 3 minutes left. We have options!
+
+    // First, because of the precondition, we know that a previous step chose to
+    // visit this room next. That means that we should open the valve here!
+    // The only exception is on the very first move: the starting room may have
+    // flow 0, so there's no reason to open the valve.
+    let mut current_room_cost = 0;
+    let mut current_room_value: u32 = 0;
+
+    let current_flow = graph.flow(current_room);
+    if current_flow > 0 {
+        current_room_cost = 1;
+
+        // Multiply by time_remaining - 1 because it takes a minute to open the valve.
+        current_room_value = current_flow * (time_remaining - 1);
+    }
+
+    // Now we need to figure out the best room to visit next.
+    // Fortunately, we have a bitset of the possible options.
+    let mut best: u32 = current_room_value;
+
+    for next_room in active_rooms.iter() {
+        // Going to this next room will take some time.
+        // This might eliminate it as a possibility.
+        let movement_cost = graph.distance(current_room, next_room);
+        if movement_cost > time_remaining - 1 {
+            continue;
+        }
+
+        // Once we go to that room, there'll never be a reason to go back,
+        // so remove it from the list of active rooms.
+        let mut next_possibilities = active_rooms;
+        next_possibilities.remove(next_room);
+
+        // Recurse!
+        let next_room_value = backtrack(
+            graph,
+            time_remaining - current_room_cost - movement_cost,
+            next_room,
+            next_possibilities,
+            explored,
+            cancel,
+        );
+
+        best = max(best, current_room_value + next_room_value);
+    }
+
+    best
+}
+
+/**
+ * Generates all partitions of a set of n objects into 2 subsets.
+ * Returns a series of pairs of bitsets representing the subsets.
+ *
+ * The implementation relies heavily on the internal representation of a bitset.
+ * Specifically, it uses the fact that a bitset containing [0, 1, ..., n-1]
+ * is stored as 0b011...11 (n '1' bits). That means that we can
+ * generate partitions by simply counting from 0 up to 2^n - 1;
+ * the '0' bits correspond to elements in one partition, while the
+ * '1' bits correspond to elements in the other.
+ *
+ * Example: if n = 6 and the counter is 0b011001, then the partitions
+ * are {0, 3, 4} (the '1' bits) and {1, 2, 5} (the '0' bits).
+ * To get the next pair of partitions, add 1 to the counter to get
+ * 0b011010, representing {1, 3, 4} and {0, 2, 5}.
+ *
+ * Then because a bitset is just a u32, we can create bitsets representing
+ * the two partitions as just `counter` and `!counter`, modulo
+ * masking out some irrelevant high bits.
+ *
+ * As an additional optimization, we can use the fact that both
+ * partitions are processed identically to skip generating half of them.
+ * For example, ({0, 1, 3, 4}, {2, 5}) and ({2, 5}, {0, 1, 3, 4}) will
+ * give the same results, so don't bother checking both.
+ * This is implemented using a popcount, which limits the first partition
+ * to having <= half of its bits set. There is still some repeated work,
+ * but it cuts the number of pairs returned by ~1/2 and is fast enough.
+ */
+fn partitions(n: usize) -> impl Iterator<Item = (Bitset, Bitset)> {
+    (0..1u32 << n).filter_map(move |value| partition(value, n))
+}
+
+/**
+ * Returns the pair of partitions represented by `value` (see above),
+ * or None if it's one of the mirrored pairs we skip.
+ */
+fn partition(value: u32, n: usize) -> Option<(Bitset, Bitset)> {
+    let mask = (1u32 << n) - 1;
+    let max_bits = (n as u32) / 2;
+
+    if value.count_ones() > max_bits {
+        return None;
+    }
+
+    let inverted = mask & !value;
+    Some((Bitset::from_bits(value), Bitset::from_bits(inverted)))
+}
+
+/*
+ * Parsing and compressing the graph (including the all-pairs distances) is
+ * shared by both parts, so do it once up front.
+ */
+#[aoc_generator(day16)]
+pub fn generator(input: &str) -> Result<CompressedGraph, AocError> {
+    let input = normalize(input);
+    let full_graph = parse_graph(&input)?;
+    Ok(compress_graph(&full_graph))
+}
+
+#[aoc(day16, part1)]
+pub fn part1(graph: &CompressedGraph) -> u32 {
+    best_part1(graph, &CancelToken::new())
+}
+
+/**
+ * Part 1, giving up if `cancel` is cancelled before it's done.
+ */
+pub fn part1_cancellable(graph: &CompressedGraph, cancel: &CancelToken) -> Result<u32, Cancelled> {
+    let best = best_part1(graph, cancel);
+    cancel.check()?;
+    Ok(best)
+}
+
+fn best_part1(graph: &CompressedGraph, cancel: &CancelToken) -> u32 {
+    // At the start, all rooms are active except the starting room,
+    // which we already know has the highest ID.
+    let start_room = graph.size - 1;
+    let active_rooms = Bitset::from_bits((1u32 << start_room) - 1);
+
+    enter_span!("search");
+    let explored = Counter::new();
+    let best = backtrack(graph, 30, start_room, active_rooms, &explored, cancel);
+    debug_event!("search finished", states_explored = explored.get());
+    best
+}
+
+#[aoc(day16, part2)]
+pub fn part2(graph: &CompressedGraph) -> u32 {
+    best_part2(graph, &NoProgress, &CancelToken::new())
+}
+
+/**
+ * Part 2, reporting how many partitions of the valves have been tried as
+ * it goes, and giving up if `cancel` is cancelled before it's done.
+ */
+pub fn part2_cancellable(
+    graph: &CompressedGraph,
+    progress: &dyn Progress,
+    cancel: &CancelToken,
+) -> Result<u32, Cancelled> {
+    let best = best_part2(graph, progress, cancel);
+    cancel.check()?;
+    Ok(best)
+}
+
+fn best_part2(graph: &CompressedGraph, progress: &dyn Progress, cancel: &CancelToken) -> u32 {
+    let start_room = graph.size - 1;
+
+    // We'll handle some valves, and the elephant will handle others.
+    // There'll never be any reason for both us and the elephant to visit the same room.
+    // So, we'll generate every way to partition the set of active rooms into two subsets,
+    // and find the most pressure releasable for each subset in the time limit.
+    // The best result over all partitionings is our answer.
+
+    // There are 15 active nodes, so there will be 2^14 distinct partitionings.
+    // Better hope the backtracking code from part 1 is efficient!
+    enter_span!("search");
+    let explored = Counter::new();
+    let total = partitions(start_room).count();
+    let best = partitions(start_room)
+        .enumerate()
+        .map(|(done, (my_rooms, elephant_rooms))| {
+            progress.update("partitions evaluated", done + 1, total);
+            best_for_partition(
+                graph,
+                start_room,
+                my_rooms,
+                elephant_rooms,
+                &explored,
+                cancel,
+            )
+        })
+        .max()
+        .unwrap_or(0);
+    debug_event!("search finished", states_explored = explored.get());
+    best
+}
+
+fn best_for_partition(
+    graph: &CompressedGraph,
+    start_room: usize,
+    my_rooms: Bitset,
+    elephant_rooms: Bitset,
+    explored: &Counter,
+    cancel: &CancelToken,
+) -> u32 {
+    let my_best = backtrack(graph, 26, start_room, my_rooms, explored, cancel);
+    let elephant_best = backtrack(graph, 26, start_room, elephant_rooms, explored, cancel);
+    my_best + elephant_best
+}
+
+/*
+ * Every partition is handled independently, so it's trivial to spread them
+ * across threads. This is kept separate from part2() so that the two can be
+ * benchmarked against each other.
+ */
+#[cfg(feature = "parallel")]
+#[aoc(day16, part2, Parallel)]
+pub fn part2_parallel(graph: &CompressedGraph) -> u32 {
+    use rayon::prelude::*;
+
+    let start_room = graph.size - 1;
+
+    enter_span!("search");
+    let explored = Counter::new();
+    let cancel = CancelToken::new();
+    let best = (0..1u32 << start_room)
+        .into_par_iter()
+        .filter_map(|value| partition(value, start_room))
+        .map(|(my_rooms, elephant_rooms)| {
+            best_for_partition(
+                graph,
+                start_room,
+                my_rooms,
+                elephant_rooms,
+                &explored,
+                &cancel,
+            )
+        })
+        .max()
+        .unwrap_or(0);
+    debug_event!("search finished", states_explored = explored.get());
+    best
+}
+
+/*
+ * The searches above only return the best total pressure. To get the
+ * openings that achieve it, we re-derive the path: from each state, the
+ * optimal next room is one whose own best result accounts for the rest of
+ * the total. That costs one search per candidate room at each step, but the
+ * paths are short and each search is over an ever-smaller set of rooms.
+ */
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Agent {
+    You,
+    Elephant,
+}
+
+/**
+ * Opening a valve during the given minute (counting from 1, as the puzzle
+ * does). The valve releases pressure for every minute after that one.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Opening {
+    pub minute: u32,
+    pub room: usize,
+    pub agent: Agent,
+}
+
+/**
+ * The openings for the best solution to part 1, in the order they happen.
+ */
+pub fn schedule_part1(graph: &CompressedGraph) -> Vec<Opening> {
+    let start_room = graph.size - 1;
+    let active_rooms = Bitset::from_bits((1u32 << start_room) - 1);
+
+    let mut schedule = Vec::new();
+    reconstruct(graph, 30, active_rooms, Agent::You, &mut schedule);
+    schedule
+}
+
+/**
+ * The openings (by both you and the elephant) for the best solution to
+ * part 2, in the order they happen.
+ */
+pub fn schedule_part2(graph: &CompressedGraph) -> Vec<Opening> {
+    let start_room = graph.size - 1;
+    let explored = Counter::new();
+    let cancel = CancelToken::new();
+    let Some((_, my_rooms, elephant_rooms)) = partitions(start_room)
+        .map(|(my_rooms, elephant_rooms)| {
+            let best = best_for_partition(
+                graph,
+                start_room,
+                my_rooms,
+                elephant_rooms,
+                &explored,
+                &cancel,
+            );
+            (best, my_rooms, elephant_rooms)
+        })
+        .max_by_key(|(best, _, _)| *best)
+    else {
+        return Vec::new();
+    };
+
+    let mut schedule = Vec::new();
+    reconstruct(graph, 26, my_rooms, Agent::You, &mut schedule);
+    reconstruct(graph, 26, elephant_rooms, Agent::Elephant, &mut schedule);
+    schedule.sort_by_key(|opening| opening.minute);
+    schedule
+}
+
+/**
+ * Follows the best path through `rooms` from the starting room, adding
+ * each valve that gets opened along the way to the schedule.
+ */
+fn reconstruct(
+    graph: &CompressedGraph,
+    time_limit: u32,
+    rooms: Bitset,
+    agent: Agent,
+    schedule: &mut Vec<Opening>,
+) {
+    let explored = Counter::new();
+    let cancel = CancelToken::new();
+    let mut time_remaining = time_limit;
+    let mut current_room = graph.size - 1;
+    let mut active_rooms = rooms;
+
+    loop {
+        let best = backtrack(
+            graph,
+            time_remaining,
+            current_room,
+            active_rooms,
+            &explored,
+            &cancel,
+        );
+
+        // Mirrors the valve-opening logic at the top of backtrack().
+        let flow = graph.flow(current_room);
+        let (opening_cost, opening_value) = if flow > 0 && time_remaining >= 2 {
+            schedule.push(Opening {
+                minute: time_limit - time_remaining + 1,
+                room: current_room,
+                agent,
+            });
+            (1, flow * (time_remaining - 1))
+        } else {
+            (0, 0)
+        };
+
+        if best == opening_value {
+            return;
+        }
+
+        let next = active_rooms.iter().find_map(|next_room| {
+            let movement_cost = graph.distance(current_room, next_room);
+            if movement_cost > time_remaining - 1 {
+                return None;
+            }
+            let mut next_possibilities = active_rooms;
+            next_possibilities.remove(next_room);
+            let next_time = time_remaining - opening_cost - movement_cost;
+            let value = backtrack(
+                graph,
+                next_time,
+                next_room,
+                next_possibilities,
+                &explored,
+                &cancel,
+            );
+            (opening_value + value == best).then_some((next_room, next_time, next_possibilities))
+        });
+
+        (current_room, time_remaining, active_rooms) =
+            next.expect("the best path should be reachable");
+    }
+}
+
+/**
+ * Replays a schedule, returning the total pressure it releases within the
+ * time limit, or None if it isn't possible: a valve opened twice (or one
+ * with no flow), or an agent that couldn't have walked to a valve by the
+ * time it was opened.
+ */
+pub fn simulate(graph: &CompressedGraph, time_limit: u32, schedule: &[Opening]) -> Option<u32> {
+    let mut opened = Bitset::new();
+    let mut total = 0;
+
+    for agent in [Agent::You, Agent::Elephant] {
+        let mut openings: Vec<&Opening> = schedule.iter().filter(|o| o.agent == agent).collect();
+        openings.sort_by_key(|opening| opening.minute);
+
+        // (room, minute) of the last time this agent was free to move.
+        let mut position = (graph.size - 1, 0);
+        for opening in openings {
+            let (room, free_at) = position;
+            if opening.room >= graph.size {
+                return None;
+            }
+            let arrival = free_at + graph.distance(room, opening.room);
+            let valid = graph.flow(opening.room) > 0
+                && !opened.contains(opening.room)
+                && opening.minute > arrival
+                && opening.minute <= time_limit;
+            if !valid {
+                return None;
+            }
+
+            opened.insert(opening.room);
+            total += graph.flow(opening.room) * (time_limit - opening.minute);
+            position = (opening.room, opening.minute);
+        }
+    }
+
+    Some(total)
+}
+
+/**
+ * The total pressure a schedule has released by the end of each minute, for
+ * plotting or checking against the puzzle's walkthrough: the first entry is
+ * minute 1, and the last is the schedule's total. The schedule isn't checked;
+ * see simulate() for that.
+ */
+pub fn pressure_curve(graph: &CompressedGraph, time_limit: u32, schedule: &[Opening]) -> Vec<u32> {
+    let mut curve = Vec::with_capacity(time_limit as usize);
+    let (mut released, mut total) = (0, 0);
+    for minute in 1..=time_limit {
+        // Valves opened in earlier minutes release pressure during this one.
+        released += schedule
+            .iter()
+            .filter(|opening| opening.minute + 1 == minute)
+            .map(|opening| graph.flow(opening.room))
+            .sum::<u32>();
+        total += released;
+        curve.push(total);
+    }
+    curve
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::paths::bfs;
+    use crate::utils::testing::read_example;
+
+    /*
+     * An independent check on the schedules, which doesn't trust anything
+     * from the compressed graph: it plays the schedule out minute by minute
+     * on the full graph, walking each agent one tunnel at a time towards its
+     * next valve, and adds up the pressure released each minute. It returns
+     * None if an agent isn't in the right room when it's meant to open a
+     * valve, or a valve is opened twice.
+     */
+    fn simulate_full(
+        graph: &Graph,
+        time_limit: u32,
+        openings: &[(u32, &str, Agent)],
+    ) -> Option<u32> {
+        let mut queues: Vec<Vec<&(u32, &str, Agent)>> = [Agent::You, Agent::Elephant]
+            .iter()
+            .map(|agent| {
+                let mut queue: Vec<_> = openings.iter().filter(|o| o.2 == *agent).collect();
+                // Popped from the back, so latest first.
+                queue.sort_by_key(|o| std::cmp::Reverse(o.0));
+                queue
+            })
+            .collect();
+        let mut positions = ["AA", "AA"];
+        let mut open: Vec<&str> = Vec::new();
+        let mut total = 0;
+
+        for minute in 1..=time_limit {
+            // Valves opened in earlier minutes release pressure during this one.
+            total += open.iter().map(|valve| graph.nodes[*valve]).sum::<u32>();
+
+            for (queue, position) in queues.iter_mut().zip(positions.iter_mut()) {
+                let Some(&&(when, valve, _)) = queue.last() else {
+                    continue;
+                };
+                if when == minute {
+                    if *position != valve || open.contains(&valve) {
+                        return None;
+                    }
+                    open.push(valve);
+                    queue.pop();
+                } else if *position != valve {
+                    let path = bfs(
+                        &*position,
+                        |room| graph.edges[*room].iter().map(|next| next.as_str()),
+                        |room| *room == valve,
+                    )?;
+                    *position = path[1];
+                }
+            }
+        }
+
+        queues.iter().all(|queue| queue.is_empty()).then_some(total)
+    }
+
+    fn check_schedule(
+        input: &str,
+        graph: &CompressedGraph,
+        time_limit: u32,
+        schedule: &[Opening],
+    ) -> u32 {
+        let openings: Vec<_> = schedule
+            .iter()
+            .map(|o| (o.minute, graph.label(o.room), o.agent))
+            .collect();
+        let full = simulate_full(
+            &parse_graph(&normalize(input)).unwrap(),
+            time_limit,
+            &openings,
+        )
+        .expect("schedule should be possible on the full graph");
+        assert_eq!(simulate(graph, time_limit, schedule), Some(full));
+        full
+    }
+
+    #[test]
+    fn test_generator() {
+        let Some(input) = read_example("day16.txt") else {
+            return;
+        };
+        let graph = generator(&input).unwrap();
+
+        // AA plus the six valves with nonzero flow.
+        assert_eq!(graph.size(), 7);
+        assert_eq!(graph.label(graph.start_room()), "AA");
+
+        let bb = graph.room_id("BB").unwrap();
+        let jj = graph.room_id("JJ").unwrap();
+        assert_eq!(graph.flow(bb), 13);
+        assert_eq!(graph.distance(bb, jj), 3);
+        assert_eq!(graph.room_id("II"), None);
+    }
+
+    #[test]
+    fn test_schedules() {
+        let Some(input) = read_example("day16.txt") else {
+            return;
+        };
+        let graph = generator(&input).unwrap();
+
+        // The order from the puzzle's walkthrough.
+        let part1_schedule = schedule_part1(&graph);
+        let valves: Vec<_> = part1_schedule.iter().map(|o| graph.label(o.room)).collect();
+        assert_eq!(valves, vec!["DD", "BB", "JJ", "HH", "EE", "CC"]);
+        assert_eq!(part1_schedule[0].minute, 2);
+        assert_eq!(check_schedule(&input, &graph, 30, &part1_schedule), 1651);
+
+        let part2_schedule = schedule_part2(&graph);
+        assert_eq!(check_schedule(&input, &graph, 26, &part2_schedule), 1707);
+        assert!(part2_schedule.iter().any(|o| o.agent == Agent::Elephant));
+
+        // Opening DD before we could have walked there.
+        let mut impossible = part1_schedule;
+        impossible[0].minute = 1;
+        assert_eq!(simulate(&graph, 30, &impossible), None);
+    }
+
+    #[test]
+    fn test_pressure_curve() {
+        let Some(input) = read_example("day16.txt") else {
+            return;
+        };
+        let graph = generator(&input).unwrap();
+
+        let curve = pressure_curve(&graph, 30, &schedule_part1(&graph));
+        assert_eq!(curve.len(), 30);
+        // Nothing is open for the first two minutes, then DD releases 20 a
+        // minute until BB joins it after minute 5.
+        assert_eq!(curve[..6], [0, 0, 20, 40, 60, 93]);
+        // "Valves BB, CC, DD, EE, HH, and JJ are open, releasing 81 pressure."
+        assert_eq!(curve[29] - curve[28], 81);
+        assert_eq!(curve.last(), Some(&1651));
+
+        let curve = pressure_curve(&graph, 26, &schedule_part2(&graph));
+        assert_eq!(curve.last(), Some(&1707));
+    }
+
+    #[test]
+    fn test_simulate_full() {
+        let Some(input) = read_example("day16.txt") else {
+            return;
+        };
+        let graph = parse_graph(&normalize(&input)).unwrap();
+
+        // The first two openings from the walkthrough: DD during minute 2
+        // then BB during minute 5, releasing 20 * 28 + 13 * 25.
+        let openings = [(2, "DD", Agent::You), (5, "BB", Agent::You)];
+        assert_eq!(simulate_full(&graph, 30, &openings), Some(885));
+
+        // BB is two tunnels from DD, so it can't be opened any sooner.
+        let openings = [(2, "DD", Agent::You), (4, "BB", Agent::You)];
+        assert_eq!(simulate_full(&graph, 30, &openings), None);
+    }
+
+    #[test]
+    fn test_invalid_graphs() {
+        let room = |label: &str, tunnels: &str| {
+            format!("Valve {label} has flow rate=1; tunnels lead to valves {tunnels}\n")
+        };
+
+        let typo = room("AA", "BB") + &room("BB", "AA, CC") + &room("CC", "BV");
+        assert_eq!(
+            generator(&typo).unwrap_err(),
+            AocError::parse(3, "valve CC has a tunnel to unknown valve BV")
+        );
+
+        let no_start = room("BB", "CC") + &room("CC", "BB");
+        assert_eq!(
+            generator(&no_start).unwrap_err(),
+            AocError::parse(3, "there's no valve AA to start from")
+        );
+
+        let twice = room("AA", "BB") + &room("BB", "AA") + &room("AA", "BB");
+        assert_eq!(
+            generator(&twice).unwrap_err(),
+            AocError::parse(3, "valve AA is listed twice")
+        );
+
+        let garbled = room("AA", "BB") + "Valve BB has flow rate=x; tunnel leads to valve AA\n";
+        assert!(matches!(
+            generator(&garbled),
+            Err(AocError::Parse { line: 2, .. })
+        ));
+
+        let gushing = room("AA", "BB") + "Valve BB has flow rate=70000; tunnel leads to valve AA\n";
+        assert_eq!(
+            generator(&gushing).unwrap_err(),
+            AocError::parse(2, "valve BB's flow rate of 70000 is too large")
+        );
+
+        let cut_off = room("AA", "BB") + &room("BB", "AA") + &room("CC", "DD") + &room("DD", "CC");
+        assert_eq!(
+            generator(&cut_off).unwrap_err(),
+            AocError::parse(3, "valve CC can't be reached from AA")
+        );
+    }
+
+    #[test]
+    fn test_many_valves() {
+        // A ring of AA plus `n` more valves, all with flow 1.
+        let ring = |n: usize| {
+            let labels: Vec<String> = std::iter::once("AA".to_string())
+                .chain(
+                    (0..n as u8)
+                        .map(|i| format!("{}{}", (b'B' + i / 26) as char, (b'A' + i % 26) as char)),
+                )
+                .collect();
+            (0..labels.len())
+                .map(|i| {
+                    let next = &labels[(i + 1) % labels.len()];
+                    let prev = &labels[(i + labels.len() - 1) % labels.len()];
+                    format!(
+                        "Valve {} has flow rate=1; tunnels lead to valves {prev}, {next}\n",
+                        labels[i]
+                    )
+                })
+                .collect::<String>()
+        };
+
+        // Twice the example's size is fine, and the distances wrap around.
+        let graph = generator(&ring(13)).unwrap();
+        assert_eq!(graph.size(), 14);
+        let (aa, ba, bm) = (
+            graph.start_room(),
+            graph.room_id("BA").unwrap(),
+            graph.room_id("BM").unwrap(),
+        );
+        assert_eq!((graph.distance(aa, ba), graph.distance(aa, bm)), (1, 1));
+        assert_eq!(graph.distance(ba, bm), 2);
+        // Open AA, then the rest one step apart: 29 + 27 + ... + 3.
+        assert_eq!(super::part1(&graph), 224);
+
+        assert_eq!(
+            generator(&ring(31)).unwrap_err(),
+            AocError::parse(
+                33,
+                "32 valves have a nonzero flow rate, but at most 31 are supported"
+            )
+        );
+    }
+}
@@ -0,0 +1,586 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::error::AocError;
+use crate::utils::input::normalize;
+
+/**
+ * Which way a jet of hot gas pushes the falling piece.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Jet {
+    Left,
+    Right,
+}
+
+/*
+ * The board and pieces both use a simple inverted coordinate system:
+ * board.data[0] is the lowest row in the board, board.data[1] is the
+ * second row, and so on. This makes iterating over rows straightforward.
+ *
+ * The data itself is stored in bitmaps: both the board and the pieces
+ * are just vectors of u8's. The board is only 7 columns wide, so only
+ * the low 7 bits of each u8 is actually used. This means that checking
+ * whether a piece intersects a spot on the board is just a bitwise-AND.
+ * It also makes it pretty easy to move pieces left and right with shifts.
+ */
+struct Board {
+    data: Vec<u8>,
+}
+
+impl Board {
+    fn height(&self) -> usize {
+        self.data.len()
+    }
+
+    /**
+     * Checks whether a piece can be placed at a particular height.
+     */
+    fn can_place(&self, piece: &Piece, base_height: usize) -> bool {
+        for row in 0..piece.height() {
+            // If we've gone off the top of the board, there's nothing
+            // for the piece to run into, so we're done.
+            if base_height + row >= self.height() {
+                break;
+            }
+
+            // If the piece and the board row have a 1 bit in common,
+            // then they intersect, and the piece can't be placed there.
+            if self.data[base_height + row] & piece.data[row] != 0 {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /**
+     * Adds a piece to the board at a given height, adding new rows
+     * to the board if needed to contain the added piece.
+     *
+     * Because we only add new rows to the board when needed, we
+     * can always find the height of the tower by just checking
+     * self.board.len().
+     */
+    fn add_piece(&mut self, piece: &Piece, base_height: usize) {
+        for row in 0..piece.height() {
+            if base_height + row >= self.height() {
+                self.data.push(0b0000000);
+            }
+
+            self.data[base_height + row] |= piece.data[row];
+        }
+    }
+
+    /**
+     * Simulates dropping a new piece into the board.
+     *
+     * Because wind is preserved across drops, this returns the updated wind index. ("windex"?)
+     */
+    fn drop(&mut self, initial_piece: &Piece, winds: &[Jet], initial_wind: usize) -> usize {
+        let mut piece = initial_piece.clone();
+
+        // Pieces always start at 3 above the highest point on the board.
+        let mut piece_y = self.height() + 3;
+
+        let mut wind = initial_wind;
+        loop {
+            let shifted = match winds[wind] {
+                Jet::Left => piece.shifted_left(),
+                Jet::Right => piece.shifted_right(),
+            };
+
+            wind = (wind + 1) % winds.len();
+
+            // Check whether we're able to move this piece in the
+            // direction of the wind, and update it if so.
+            if self.can_place(&shifted, piece_y) {
+                piece = shifted;
+            }
+
+            // Check whether moving the piece downwards would cause it to
+            // intersect with an already-placed piece. If so, we're done.
+            if piece_y == 0 || !self.can_place(&piece, piece_y - 1) {
+                self.add_piece(&piece, piece_y);
+                break;
+            }
+
+            // Otherwise, move the piece down one unit.
+            piece_y -= 1;
+        }
+
+        wind
+    }
+}
+
+/*
+ * A Piece is basically just a tiny Board: it's a vector of u8's with
+ * some utility functions attached.
+ */
+#[derive(Clone)]
+struct Piece {
+    data: Vec<u8>,
+}
+
+impl Piece {
+    fn height(&self) -> usize {
+        self.data.len()
+    }
+
+    /**
+     * Checks whether a given pixel/location on this piece is filled with rock.
+     * Naming this stuff is hard.
+     */
+    fn filled(&self, row: usize, col: usize) -> bool {
+        let mask = 1 << col;
+        self.data[row] & mask != 0
+    }
+
+    /**
+     * Tries to shift this piece to the left. This might not be possible if
+     * doing so would cause the piece to run into the wall of the board.
+     *
+     * Returns a new Piece representing the (possibly-)shifted original.
+     */
+    fn shifted_left(&self) -> Self {
+        // We cannot shift left if any part of the piece is
+        // already in the leftmost (6th) column.
+        for i in 0..self.height() {
+            if self.filled(i, 6) {
+                return self.clone();
+            }
+        }
+
+        Piece {
+            data: self.data.iter().map(|r| r << 1).collect(),
+        }
+    }
+
+    /**
+     * Tries to shift this piece to the right. This might not be possible if
+     * doing so would cause the piece to run into the wall of the board.
+     *
+     * Returns a new Piece representing the (possibly-)shifted original.
+     */
+    fn shifted_right(&self) -> Self {
+        // We cannot shift left if any part of the piece is
+        // already in the rightmost (0th) column.
+        for i in 0..self.height() {
+            if self.filled(i, 0) {
+                return self.clone();
+            }
+        }
+
+        Piece {
+            data: self.data.iter().map(|r| r >> 1).collect(),
+        }
+    }
+}
+
+/**
+ * Returns a list of the pieces as they first appear when dropped.
+ * 
+ * I wanted to make this a constant, but Rust didn't like that. Ah well.
+ * They're written out in a long format so it's easier to see the
+ * mapping between bits and piece shapes.
+ * 
+ * Two things to note:
+ *   - Because pieces always appear 2 units from the left wall, the high
+ *     two bits are always zero, and at least one row has the next bit set.
+ *   - The byte ordering is reversed from the visuals, since in our
+ *     coordinate system a low index means that the row appears *lower*.
+ *     This only matters for the L shape since the others are mirrored vertically.
+ */
+#[rustfmt::skip]
+fn base_pieces() -> Vec<Piece> {
+    vec![
+        Piece { data: vec![0b0011110] },
+        Piece { 
+            data: vec![
+                0b0001000, 
+                0b0011100, 
+                0b0001000
+            ] 
+        },
+        // Note that the L piece looks upside down!
+        // This is to match the coordinate system used by the board, where
+        // lower-indexed rows have lower y-coordinates.
+        Piece { 
+            data: vec![
+                0b0011100, 
+                0b0000100, 
+                0b0000100
+            ]   
+        },
+        Piece { 
+            data: vec![
+                0b0010000, 
+                0b0010000, 
+                0b0010000, 
+                0b0010000
+            ] 
+        },
+        Piece {     
+            data: vec![
+                0b0011000, 
+                0b0011000
+            ]
+        },
+    ]
+}
+
+/**
+ * Drops pieces forever, yielding the height of the tower after each one.
+ * Handy for plotting, or for checking the cycle detection below against
+ * brute force.
+ */
+pub fn heights(winds: &[Jet]) -> impl Iterator<Item = usize> + '_ {
+    let pieces = base_pieces();
+    let mut board = Board { data: Vec::new() };
+    let mut wind = 0;
+    let mut num_pieces = 0;
+
+    std::iter::from_fn(move || {
+        let piece = &pieces[num_pieces % pieces.len()];
+        wind = board.drop(piece, winds, wind);
+        num_pieces += 1;
+        Some(board.height())
+    })
+}
+
+/**
+ * The jet pattern. Line breaks are skipped, so a pattern wrapped over
+ * several lines is fine, but anything other than '<' or '>' is an error.
+ */
+#[aoc_generator(day17)]
+pub fn generator(input: &str) -> Result<Vec<Jet>, AocError> {
+    let mut winds = Vec::new();
+    for (row, line) in normalize(input).lines().enumerate() {
+        for (col, c) in line.chars().enumerate() {
+            winds.push(match c {
+                '<' => Jet::Left,
+                '>' => Jet::Right,
+                _ => {
+                    return Err(AocError::parse(
+                        row + 1,
+                        format!("expected '<' or '>' but got {c:?} in column {}", col + 1),
+                    ))
+                }
+            });
+        }
+    }
+
+    if winds.is_empty() {
+        return Err(AocError::parse(1, "the jet pattern is empty"));
+    }
+    Ok(winds)
+}
+
+/*
+ * Part 1 is pretty straightforward, given all the work we did above.
+ * We just have to simulate 2022 drops and check the height.
+ */
+#[aoc(day17, part1)]
+pub fn part1(winds: &[Jet]) -> usize {
+    heights(winds).nth(2021).unwrap()
+}
+
+/*
+ * For Part 2, we won't be able to simulate dropping a trillion pieces. So we
+ * need to take a shortcut.
+ *
+ * The key insight is that we're doing a lot of things repetitively: the pieces
+ * cycle every 5 drops, the winds cycle every so often, and so on. And, since
+ * where a piece ends up is determined entirely by the top few rows of the board,
+ * it's not too hard to imagine that the state of those rows might repeat as well.
+ *
+ * So, our goal is to find two points at which all of those things repeat. Then we
+ * can skip almost all of the actual simulation, and replace it with arithmetic!
+ *
+ * The steps will look something like this:
+ * 1. Drop a bunch of pieces until the first cycle starts.
+ * 2. Go through the cycle many many many times. Each repetition uses a known
+ *    number of blocks, and generates a known additional height.
+ * 3. The last cycle probably won't end right at 1 trillion blocks, so simulate
+ *    adding the last few blocks to see how much height we get.
+ */
+
+/**
+ * This is the state we store to check for repeats.
+ *
+ * It contains the current piece ID, the current wind state, and a copy of the top
+ * several rows of the board.
+ */
+#[derive(Hash, PartialEq, Eq)]
+struct State {
+    piece: usize,
+    gust: usize,
+    board: Vec<u8>,
+}
+
+/**
+ * What the cycle detection found for a jet pattern: where the cycle starts,
+ * how long it is, and how much taller each trip around it makes the tower.
+ * Piece counts are numbers of pieces dropped so far.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CycleReport {
+    // The number of gusts in the jet pattern.
+    pub jets: usize,
+    // The first time the state repeats is `length` pieces after `start`.
+    pub start: usize,
+    pub length: usize,
+    // The height of the tower after `start` pieces.
+    pub start_height: usize,
+    // Where in the jet pattern the next gust comes from at the start of
+    // each cycle.
+    pub start_jet: usize,
+    pub height_per_cycle: usize,
+}
+
+impl fmt::Display for CycleReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} jets; cycle starts after {} pieces (height {}, jet {}), repeats every {} pieces, adding {} rows each time",
+            self.jets,
+            self.start,
+            self.start_height,
+            self.start_jet,
+            self.length,
+            self.height_per_cycle
+        )
+    }
+}
+
+#[aoc(day17, part2)]
+pub fn part2(winds: &[Jet]) -> u64 {
+    part2_with(winds, 1_000_000_000_000)
+}
+
+/**
+ * Part 2 for any number of pieces. Panics if the tower ends up too tall to
+ * count in a u64, which takes somewhere north of 10^19 pieces.
+ */
+pub fn part2_with(winds: &[Jet], total_pieces: u64) -> u64 {
+    tower_height(winds, total_pieces)
+        .unwrap_or_else(|| panic!("the tower is too tall to count after {total_pieces} pieces"))
+}
+
+/**
+ * Drops pieces until the state repeats, and reports the cycle. Nothing
+ * promises that it ever will, so this gives up with None after
+ * `max_pieces` pieces.
+ */
+pub fn find_cycle(winds: &[Jet], max_pieces: u64) -> Option<CycleReport> {
+    detect_cycle(winds, max_pieces).0
+}
+
+/**
+ * The cycle, along with the height of the tower after each drop up to the
+ * point where the cycle was found (so `heights[i]` is the height after
+ * `i + 1` pieces). If there's no cycle within the first `max_pieces`
+ * pieces, the heights go up to there instead.
+ */
+fn detect_cycle(winds: &[Jet], max_pieces: u64) -> (Option<CycleReport>, Vec<usize>) {
+    let mut wind = 0;
+
+    let mut board = Board { data: Vec::new() };
+
+    let pieces = base_pieces();
+
+    // To find a cycle, we need to track our board states.
+    // This maps a State object to a pair (# pieces dropped, board height).
+    let mut visited_states: HashMap<State, (usize, usize)> = HashMap::new();
+    let mut heights = Vec::new();
+
+    let mut num_pieces = 0;
+    while (num_pieces as u64) < max_pieces {
+        let piece = &pieces[num_pieces % pieces.len()];
+
+        wind = board.drop(piece, winds, wind);
+        num_pieces += 1;
+        heights.push(board.height());
+
+        // We can't grab the board state if there's not enough board state to grab!
+        // It's *very* unlikely that the first cycle will start this early anyways.
+        if board.height() < 30 {
+            continue;
+        }
+
+        // Grab the board state.
+        let board_data = board
+            .data
+            .iter()
+            .skip(board.height() - 30)
+            .cloned()
+            .collect();
+        let state = State {
+            piece: num_pieces % pieces.len(),
+            gust: wind,
+            board: board_data,
+        };
+
+        if let Some((previous_num_pieces, previous_height)) =
+            visited_states.insert(state, (num_pieces, board.height()))
+        {
+            let report = CycleReport {
+                jets: winds.len(),
+                start: previous_num_pieces,
+                length: num_pieces - previous_num_pieces,
+                start_height: previous_height,
+                start_jet: wind,
+                height_per_cycle: board.height() - previous_height,
+            };
+            return (Some(report), heights);
+        }
+    }
+
+    (None, heights)
+}
+
+/**
+ * Finds the height of the tower after `total_pieces` drops, using the cycle
+ * detection described above to skip most of the simulation.
+ *
+ * Piece counts this large don't fit in a usize on 32-bit targets, so this
+ * works in u64, and returns None if the height doesn't fit in one either.
+ */
+pub fn tower_height(winds: &[Jet], total_pieces: u64) -> Option<u64> {
+    let (cycle, heights) = detect_cycle(winds, total_pieces);
+
+    // If we get there before going around the cycle (or without ever finding
+    // one), there's nothing to skip.
+    let Some(cycle) = cycle.filter(|_| total_pieces > heights.len() as u64) else {
+        return Some(match total_pieces {
+            0 => 0,
+            n => heights[n as usize - 1] as u64,
+        });
+    };
+
+    // By construction, the first cycle starts at `cycle.start`. We need to
+    // make sure that we don't count those first few drops when figuring out
+    // how many times the cycle repeated.
+    let after_start = total_pieces - cycle.start as u64;
+    let num_cycles = after_start / cycle.length as u64;
+    // Less than cycle.length, so this fits back in a usize.
+    let pieces_remaining = (after_start % cycle.length as u64) as usize;
+
+    // Every trip around the cycle adds the same height, and so do the last
+    // few pieces after the last full cycle: they grow the tower just like
+    // the same pieces did the first time around, which we've already seen.
+    let extra_height = heights[cycle.start + pieces_remaining - 1] - cycle.start_height;
+
+    // Put it all together! A u64 times anything that fits in a usize can't
+    // overflow a u128, but the total might not fit back in a u64.
+    let cycles_height = u128::from(num_cycles) * cycle.height_per_cycle as u128;
+    let height = cycles_height + cycle.start_height as u128 + extra_height as u128;
+    u64::try_from(height).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_cycle, generator, heights, part1, part2, part2_with, tower_height, Jet};
+    use crate::error::AocError;
+
+    const EXAMPLE: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&generator(EXAMPLE).unwrap()), 3068);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&generator(EXAMPLE).unwrap()), 1514285714288);
+    }
+
+    #[test]
+    fn test_tower_height_matches_simulation() {
+        let winds = &generator(EXAMPLE).unwrap();
+        let simulated: Vec<usize> = heights(winds).take(5000).collect();
+        assert_eq!(simulated[..5], [1, 4, 6, 7, 9]);
+
+        assert_eq!(tower_height(winds, 0), Some(0));
+        for total_pieces in [1, 10, 15, 100, 1000, 2022, 3001, 4999, 5000] {
+            assert_eq!(
+                part2_with(winds, total_pieces as u64),
+                simulated[total_pieces - 1] as u64
+            );
+        }
+    }
+
+    #[test]
+    fn test_tower_height_overflow() {
+        let winds = &generator(EXAMPLE).unwrap();
+        // The example adds 53 rows every 35 pieces, so the tower outgrows a
+        // u64 a while before the piece count does.
+        let height = tower_height(winds, u64::MAX / 2).unwrap();
+        assert!(height.abs_diff(u64::MAX / 2 / 35 * 53) < 100);
+        assert_eq!(tower_height(winds, u64::MAX), None);
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let winds = &generator(EXAMPLE).unwrap();
+        let cycle = find_cycle(winds, 10_000).unwrap();
+        assert_eq!(cycle.jets, 40);
+        assert_eq!(cycle.length, 35);
+        assert_eq!(cycle.height_per_cycle, 53);
+
+        // The cycle really does repeat, from wherever it was found.
+        let simulated: Vec<usize> = heights(winds)
+            .take(cycle.start + 3 * cycle.length)
+            .collect();
+        assert_eq!(simulated[cycle.start - 1], cycle.start_height);
+        for i in cycle.start..cycle.start + 2 * cycle.length {
+            assert_eq!(
+                simulated[i + cycle.length - 1] - simulated[i - 1],
+                cycle.height_per_cycle
+            );
+        }
+    }
+
+    #[test]
+    fn test_no_cycle_yet() {
+        let winds = &generator(EXAMPLE).unwrap();
+        // The board has to be 30 rows tall before we even start looking.
+        assert_eq!(find_cycle(winds, 10), None);
+        assert_eq!(tower_height(winds, 10), Some(17));
+
+        // Stopping just short of where the cycle shows up gives the same
+        // answer as simulating the whole way.
+        let cycle = find_cycle(winds, 10_000).unwrap();
+        let before_cycle = cycle.start + cycle.length - 1;
+        assert_eq!(find_cycle(winds, before_cycle as u64), None);
+        let simulated = heights(winds).nth(before_cycle - 1).unwrap();
+        assert_eq!(
+            tower_height(winds, before_cycle as u64),
+            Some(simulated as u64)
+        );
+    }
+
+    #[test]
+    fn test_bad_jets() {
+        // CRLF endings and a pattern wrapped over two lines both parse.
+        let (first, second) = EXAMPLE.split_at(20);
+        let wrapped = generator(&format!("{first}\r\n{second}\r\n")).unwrap();
+        assert_eq!(wrapped, generator(EXAMPLE).unwrap());
+        assert_eq!(
+            wrapped[..4],
+            [Jet::Right, Jet::Right, Jet::Right, Jet::Left]
+        );
+
+        assert_eq!(
+            generator(">><\n<x>\n"),
+            Err(AocError::parse(
+                2,
+                "expected '<' or '>' but got 'x' in column 2"
+            ))
+        );
+        assert_eq!(
+            generator("\n"),
+            Err(AocError::parse(1, "the jet pattern is empty"))
+        );
+    }
+}
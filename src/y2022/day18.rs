@@ -0,0 +1,661 @@
+use std::{
+    cmp::{max, min},
+    fmt::Write,
+    io::BufRead,
+};
+
+#[cfg(feature = "nom")]
+use nom::{
+    bytes::complete::tag,
+    character::complete::i32,
+    combinator::all_consuming,
+    sequence::{terminated, tuple},
+    IResult,
+};
+
+use crate::error::AocError;
+use crate::utils::hash::{FastMap, FastSet};
+use crate::utils::input::normalize;
+use crate::utils::neighbors::{neighbors3d, Connectivity, Point3};
+use crate::utils::search::{bfs, bfs_reach};
+
+type Point = Point3;
+type State = FastSet<Point>;
+
+#[cfg(feature = "nom")]
+fn parse_line(input: &str) -> IResult<&str, (i32, i32, i32)> {
+    all_consuming(tuple((
+        terminated(i32, tag(",")),
+        terminated(i32, tag(",")),
+        i32,
+    )))(input)
+}
+
+#[cfg(feature = "nom")]
+fn parse_point(line: &str) -> Option<Point> {
+    parse_line(line.trim()).ok().map(|(_, point)| point)
+}
+
+#[cfg(not(feature = "nom"))]
+fn parse_point(line: &str) -> Option<Point> {
+    let mut coords = line.trim().split(',').map(|coord| coord.parse().ok());
+    let point = (coords.next()??, coords.next()??, coords.next()??);
+    coords.next().is_none().then_some(point)
+}
+
+/**
+ * Reads points one line at a time, so that very large inputs never have to
+ * be held in memory as text. Blank lines are skipped; anything else that
+ * isn't an "x,y,z" triple is reported as an error with its line number.
+ */
+pub fn read_points<R: BufRead>(reader: R) -> impl Iterator<Item = Result<Point, AocError>> {
+    reader.lines().enumerate().filter_map(|(index, line)| {
+        let line = match line {
+            Ok(line) => line,
+            Err(err) => {
+                return Some(Err(AocError::Io {
+                    path: String::from("<input>"),
+                    message: err.to_string(),
+                }))
+            }
+        };
+        if line.trim().is_empty() {
+            return None;
+        }
+        Some(
+            parse_point(&line).ok_or_else(|| {
+                AocError::parse(index + 1, format!("expected x,y,z but got {line:?}"))
+            }),
+        )
+    })
+}
+
+fn neighbors(point: &Point) -> Vec<Point> {
+    neighbors3d(point, Connectivity::Face)
+}
+
+#[aoc_generator(day18)]
+pub fn generator(input: &str) -> State {
+    normalize(input)
+        .lines()
+        .map(|line| parse_point(line).expect("parse error"))
+        .collect()
+}
+
+/**
+ * Counts the number of (point, neighbor) pairs where the neighbor is not
+ * itself in the set of points. With face connectivity this is exactly the
+ * surface area of the shape; the other connectivities also count edges
+ * and corners that touch empty space.
+ */
+pub fn surface_area(points: &State, connectivity: Connectivity) -> usize {
+    points
+        .iter()
+        .map(|point| {
+            neighbors3d(point, connectivity)
+                .iter()
+                .filter(|n| !points.contains(n))
+                .count()
+        })
+        .sum()
+}
+
+#[aoc(day18, part1)]
+pub fn part1(lava: &State) -> usize {
+    // For part 1, we want to count all of the faces of lava blocks that aren't
+    // themselves facing another lava block.
+    surface_area(lava, Connectivity::Face)
+}
+
+/**
+ * Part 1 for points from any source (e.g. read_points()), without first
+ * collecting them into anything but the set the solver needs anyway.
+ */
+pub fn part1_from_points(points: impl IntoIterator<Item = Point>) -> usize {
+    part1(&points.into_iter().collect())
+}
+
+/*
+ * The next few functions implement a 3D bounding box for part 2.
+ */
+fn lower_bounds(lhs: &Point, rhs: &Point) -> Point {
+    (min(lhs.0, rhs.0), min(lhs.1, rhs.1), min(lhs.2, rhs.2))
+}
+
+fn upper_bounds(lhs: &Point, rhs: &Point) -> Point {
+    (max(lhs.0, rhs.0), max(lhs.1, rhs.1), max(lhs.2, rhs.2))
+}
+
+fn inside(point: &Point, lower_bound: &Point, upper_bound: &Point) -> bool {
+    lower_bound.0 <= point.0
+        && point.0 <= upper_bound.0
+        && lower_bound.1 <= point.1
+        && point.1 <= upper_bound.1
+        && lower_bound.2 <= point.2
+        && point.2 <= upper_bound.2
+}
+
+/**
+ * Finds the air outside the lava, within a bounding box one bigger than the
+ * lava's in every direction.
+ */
+fn exterior(lava: &State) -> State {
+    // Find the bounding box for the lava.
+    const SMALLEST_POINT: Point = (i32::MIN, i32::MIN, i32::MIN);
+    const LARGEST_POINT: Point = (i32::MAX, i32::MAX, i32::MAX);
+    let (mut lower_bounds, mut upper_bounds) = lava.iter().fold(
+        (LARGEST_POINT, SMALLEST_POINT),
+        |bounds: (Point, Point), point| {
+            (
+                lower_bounds(&bounds.0, point),
+                upper_bounds(&bounds.1, point),
+            )
+        },
+    );
+
+    // Extend the bounding box by 1 in each direction to make sure that there
+    // is a shell of "exterior" points outside the lava.
+    lower_bounds = (lower_bounds.0 - 1, lower_bounds.1 - 1, lower_bounds.2 - 1);
+    upper_bounds = (upper_bounds.0 + 1, upper_bounds.1 + 1, upper_bounds.2 + 1);
+
+    // Pick an arbitrary point in the (extended) bounding box that we *know* is air.
+    let start_point = (lower_bounds.0, lower_bounds.1, lower_bounds.2);
+
+    // Run BFS starting from that point to identify all points on the "outside" of the lava.
+    // Many thanks to zarvox for pointing out this approach!
+    let (exterior, _) = bfs_reach(start_point, neighbors, |p| {
+        lava.contains(p) || !inside(p, &lower_bounds, &upper_bounds)
+    });
+    exterior
+}
+
+#[aoc(day18, part2)]
+pub fn part2(input: &State) -> usize {
+    let lava = input.clone();
+    let exterior = exterior(&lava);
+
+    // Now copy/paste from part 1 to find the number of exposed faces.
+    // It's actually a little nicer now because we have an explicit list
+    // of all exterior points!
+    lava.iter()
+        .map(|point| {
+            neighbors(point)
+                .iter()
+                .filter(|n| exterior.contains(n))
+                .count()
+        })
+        .sum()
+}
+
+/**
+ * Part 2 for points from any source; see part1_from_points().
+ */
+pub fn part2_from_points(points: impl IntoIterator<Item = Point>) -> usize {
+    part2(&points.into_iter().collect())
+}
+
+/**
+ * Which of the droplet's faces to put in a mesh: the ones part 2 counts, or
+ * all of the ones part 1 counts (including those facing trapped air).
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Faces {
+    Exterior,
+    All,
+}
+
+/**
+ * The 3D formats to_mesh() can write. Both are plain text.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MeshFormat {
+    Obj,
+    Stl,
+}
+
+/**
+ * One side of a cube that's facing air. Its corners go counterclockwise
+ * when seen from the air, so the normal points out of the lava.
+ */
+struct Face {
+    corners: [Point; 4],
+    normal: Point,
+}
+
+impl Face {
+    /**
+     * The side of the unit cube at `cube` that faces `cube + normal`.
+     */
+    fn new(cube: &Point, normal: Point) -> Self {
+        let direction = [normal.0, normal.1, normal.2];
+        let axis = direction.iter().position(|&d| d != 0).unwrap();
+        let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+
+        let mut origin = [cube.0, cube.1, cube.2];
+        if direction[axis] > 0 {
+            origin[axis] += 1;
+        }
+        let corner = |du: i32, dv: i32| {
+            let mut corner = origin;
+            corner[u] += du;
+            corner[v] += dv;
+            (corner[0], corner[1], corner[2])
+        };
+
+        // Going around u then v is counterclockwise seen from the positive
+        // end of the axis, so flip it for the faces on the negative end.
+        let mut corners = [corner(0, 0), corner(1, 0), corner(1, 1), corner(0, 1)];
+        if direction[axis] < 0 {
+            corners.reverse();
+        }
+        Face { corners, normal }
+    }
+}
+
+/**
+ * The faces to draw, in a stable order.
+ */
+fn exposed_faces(lava: &State, faces: Faces) -> Vec<Face> {
+    let exterior = match faces {
+        Faces::Exterior => Some(exterior(lava)),
+        Faces::All => None,
+    };
+    let exposed = |point: &Point| match &exterior {
+        Some(exterior) => exterior.contains(point),
+        None => !lava.contains(point),
+    };
+
+    let mut cubes: Vec<&Point> = lava.iter().collect();
+    cubes.sort();
+    cubes
+        .into_iter()
+        .flat_map(|cube| {
+            neighbors(cube)
+                .into_iter()
+                .filter(|n| exposed(n))
+                .map(|n| Face::new(cube, (n.0 - cube.0, n.1 - cube.1, n.2 - cube.2)))
+        })
+        .collect()
+}
+
+/**
+ * Writes the droplet's surface as a mesh of unit squares that a 3D viewer
+ * can open: a Wavefront OBJ with shared vertices and one quad per face, or
+ * an ASCII STL with two triangles per face.
+ */
+pub fn to_mesh(lava: &State, faces: Faces, format: MeshFormat) -> String {
+    let faces = exposed_faces(lava, faces);
+    let mut mesh = String::new();
+    match format {
+        MeshFormat::Obj => {
+            // OBJ vertices are numbered from 1, in the order they're listed.
+            let mut vertices: FastMap<Point, usize> = FastMap::default();
+            let mut quads = Vec::new();
+            for face in &faces {
+                let quad = face.corners.map(|corner| {
+                    let next = vertices.len() + 1;
+                    *vertices.entry(corner).or_insert_with(|| {
+                        let (x, y, z) = corner;
+                        writeln!(mesh, "v {x} {y} {z}").unwrap();
+                        next
+                    })
+                });
+                quads.push(quad);
+            }
+            for [a, b, c, d] in quads {
+                writeln!(mesh, "f {a} {b} {c} {d}").unwrap();
+            }
+        }
+        MeshFormat::Stl => {
+            writeln!(mesh, "solid droplet").unwrap();
+            for face in &faces {
+                let (nx, ny, nz) = face.normal;
+                let [a, b, c, d] = face.corners;
+                for triangle in [[a, b, c], [a, c, d]] {
+                    writeln!(mesh, "  facet normal {nx} {ny} {nz}").unwrap();
+                    writeln!(mesh, "    outer loop").unwrap();
+                    for (x, y, z) in triangle {
+                        writeln!(mesh, "      vertex {x} {y} {z}").unwrap();
+                    }
+                    writeln!(mesh, "    endloop").unwrap();
+                    writeln!(mesh, "  endfacet").unwrap();
+                }
+            }
+            writeln!(mesh, "endsolid droplet").unwrap();
+        }
+    }
+    mesh
+}
+
+/**
+ * A droplet that cubes can be added to and removed from one at a time, with
+ * both surface areas kept up to date as it changes.
+ *
+ * Alongside the lava, this tracks which air cells are trapped inside it; any
+ * other air cell is outside. Adding or removing a cube only changes the faces
+ * around it, plus whatever pocket of air it seals off or opens up, so each
+ * update only has to flood fill that pocket rather than the whole bounding box.
+ */
+#[derive(Clone, Debug, Default)]
+pub struct LavaDroplet {
+    lava: State,
+    trapped: State,
+    // Grows to cover every cube ever added, but never shrinks. Anything
+    // outside it is definitely outside the droplet.
+    bounds: Option<(Point, Point)>,
+    surface_area: usize,
+    exterior_surface_area: usize,
+}
+
+impl LavaDroplet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        self.lava.contains(point)
+    }
+
+    pub fn len(&self) -> usize {
+        self.lava.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lava.is_empty()
+    }
+
+    /**
+     * The same as part1() on the current cubes.
+     */
+    pub fn surface_area(&self) -> usize {
+        self.surface_area
+    }
+
+    /**
+     * The same as part2() on the current cubes.
+     */
+    pub fn exterior_surface_area(&self) -> usize {
+        self.exterior_surface_area
+    }
+
+    fn is_outside(&self, point: &Point) -> bool {
+        !self.lava.contains(point) && !self.trapped.contains(point)
+    }
+
+    fn lava_neighbors(&self, point: &Point) -> usize {
+        neighbors(point)
+            .iter()
+            .filter(|n| self.lava.contains(n))
+            .count()
+    }
+
+    fn outside_neighbors(&self, point: &Point) -> usize {
+        neighbors(point)
+            .iter()
+            .filter(|n| self.is_outside(n))
+            .count()
+    }
+
+    /**
+     * Adds a cube, returning false if it was already there.
+     */
+    pub fn add_cube(&mut self, point: Point) -> bool {
+        if !self.lava.insert(point) {
+            return false;
+        }
+        let covered = self.lava_neighbors(&point);
+        self.surface_area = self.surface_area + 6 - 2 * covered;
+        self.bounds = Some(match self.bounds {
+            Some((lower, upper)) => (lower_bounds(&lower, &point), upper_bounds(&upper, &point)),
+            None => (point, point),
+        });
+
+        // Filling in part of a pocket leaves the rest of it trapped, and
+        // doesn't change the outside at all.
+        if self.trapped.remove(&point) {
+            return true;
+        }
+
+        // The faces next to the new cube aren't outside any more, but the
+        // new cube's own faces might be.
+        self.exterior_surface_area =
+            self.exterior_surface_area + self.outside_neighbors(&point) - covered;
+
+        // The new cube might have sealed off some of the air next to it.
+        let (lower, upper) = self.bounds.unwrap();
+        for start in neighbors(&point) {
+            if !self.is_outside(&start) {
+                continue;
+            }
+            let air = |p: &Point| {
+                neighbors(p)
+                    .into_iter()
+                    .filter(|n| !self.lava.contains(n))
+                    .collect::<Vec<_>>()
+            };
+            let (escape, _) = bfs(start, air, |p| !inside(p, &lower, &upper));
+            if escape.is_some() {
+                continue;
+            }
+            let (pocket, _) = bfs_reach(start, air, |_| false);
+            let sealed: usize = pocket.iter().map(|p| self.lava_neighbors(p)).sum();
+            self.exterior_surface_area -= sealed;
+            self.trapped.extend(pocket);
+        }
+        true
+    }
+
+    /**
+     * Removes a cube, returning false if it wasn't there.
+     */
+    pub fn remove_cube(&mut self, point: Point) -> bool {
+        if !self.lava.remove(&point) {
+            return false;
+        }
+        let covered = self.lava_neighbors(&point);
+        self.surface_area = self.surface_area + 2 * covered - 6;
+
+        // If none of the air next to the hole is outside, it's just made a
+        // pocket bigger (or made a new one). Either way, the outside hasn't
+        // changed.
+        let outside = self.outside_neighbors(&point);
+        if outside == 0 {
+            self.trapped.insert(point);
+            return true;
+        }
+
+        // Otherwise the hole is outside, and so is any pocket it opens up.
+        self.exterior_surface_area = self.exterior_surface_area + covered - outside;
+        for start in neighbors(&point) {
+            if !self.trapped.contains(&start) {
+                continue;
+            }
+            let (pocket, _) = bfs_reach(
+                start,
+                |p| {
+                    neighbors(p)
+                        .into_iter()
+                        .filter(|n| self.trapped.contains(n))
+                        .collect::<Vec<_>>()
+                },
+                |_| false,
+            );
+            let opened: usize = pocket.iter().map(|p| self.lava_neighbors(p)).sum();
+            self.exterior_surface_area += opened;
+            for p in &pocket {
+                self.trapped.remove(p);
+            }
+        }
+        true
+    }
+}
+
+impl FromIterator<Point> for LavaDroplet {
+    fn from_iter<I: IntoIterator<Item = Point>>(iter: I) -> Self {
+        let mut droplet = LavaDroplet::new();
+        for point in iter {
+            droplet.add_cube(point);
+        }
+        droplet
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "2,2,2\n\
+                           1,2,2\n\
+                           3,2,2\n\
+                           2,1,2\n\
+                           2,3,2\n\
+                           2,2,1\n\
+                           2,2,3\n\
+                           2,2,4\n\
+                           2,2,6\n\
+                           1,2,5\n\
+                           3,2,5\n\
+                           2,1,5\n\
+                           2,3,5";
+
+    #[test]
+    fn test_part1() {
+        let state = generator(EXAMPLE);
+        assert_eq!(part1(&state), 64);
+    }
+
+    #[test]
+    fn test_part2() {
+        let state = generator(EXAMPLE);
+        assert_eq!(part2(&state), 58);
+    }
+
+    #[test]
+    fn test_surface_area_connectivity() {
+        let state = generator("1,1,1\n2,1,1");
+        assert_eq!(surface_area(&state, Connectivity::Face), 10);
+        assert_eq!(surface_area(&state, Connectivity::Edge), 34);
+        assert_eq!(surface_area(&state, Connectivity::Vertex), 50);
+    }
+
+    #[test]
+    fn test_read_points() {
+        let points: Result<Vec<Point>, AocError> = read_points(EXAMPLE.as_bytes()).collect();
+        let points = points.unwrap();
+        assert_eq!(points.len(), 13);
+        assert_eq!(part1_from_points(points.iter().copied()), 64);
+        assert_eq!(part2_from_points(points), 58);
+
+        let errors: Vec<_> = read_points("1,1,1\n\n2,x,1\n".as_bytes())
+            .filter_map(Result::err)
+            .collect();
+        assert_eq!(
+            errors,
+            vec![AocError::parse(3, "expected x,y,z but got \"2,x,1\"")]
+        );
+    }
+
+    #[test]
+    fn test_lava_droplet() {
+        let points: Vec<Point> = read_points(EXAMPLE.as_bytes())
+            .map(Result::unwrap)
+            .collect();
+
+        // Building the example up one cube at a time, and then taking it
+        // apart again in a different order, should always agree with
+        // solving from scratch.
+        let mut droplet = LavaDroplet::new();
+        for (count, &point) in points.iter().enumerate() {
+            assert!(droplet.add_cube(point));
+            let state: State = points[..=count].iter().copied().collect();
+            assert_eq!(droplet.surface_area(), part1(&state));
+            assert_eq!(droplet.exterior_surface_area(), part2(&state));
+        }
+        assert!(!droplet.add_cube(points[0]));
+        assert_eq!(droplet.exterior_surface_area(), 58);
+
+        let mut state: State = points.iter().copied().collect();
+        for point in points.iter().rev().step_by(2).chain(&points) {
+            if !droplet.remove_cube(*point) {
+                continue;
+            }
+            state.remove(point);
+            assert_eq!(droplet.surface_area(), part1(&state));
+            assert_eq!(droplet.exterior_surface_area(), part2(&state));
+        }
+        assert!(droplet.is_empty());
+        assert_eq!(droplet.surface_area(), 0);
+    }
+
+    #[test]
+    fn test_lava_droplet_pockets() {
+        // A hollow 3x3x3 cube, with one cube of its middle face missing.
+        let mut droplet: LavaDroplet = (0..27)
+            .map(|i| (i % 3, i / 3 % 3, i / 9))
+            .filter(|&p| p != (1, 1, 1) && p != (1, 1, 0))
+            .collect();
+        // The outside, less the missing face, plus the hole's four sides and
+        // the five faces of the middle that can be seen through it.
+        assert_eq!(droplet.exterior_surface_area(), 54 - 1 + 4 + 5);
+
+        // Plugging the hole traps the air in the middle...
+        droplet.add_cube((1, 1, 0));
+        assert_eq!(droplet.surface_area(), 54 + 6);
+        assert_eq!(droplet.exterior_surface_area(), 54);
+
+        // ...and knocking out a different one lets it out again.
+        droplet.remove_cube((1, 0, 1));
+        assert_eq!(droplet.exterior_surface_area(), 54 - 1 + 4 + 5);
+        droplet.add_cube((1, 1, 1));
+        assert_eq!(droplet.surface_area(), 54 - 1 + 5);
+        assert_eq!(droplet.exterior_surface_area(), 54 - 1 + 5);
+    }
+
+    /**
+     * The volume inside a set of faces, by the divergence theorem. It only
+     * comes out right if every face is wound the right way round.
+     */
+    fn enclosed_volume(faces: &[Face]) -> i32 {
+        let det = |a: Point, b: Point, c: Point| {
+            a.0 * (b.1 * c.2 - b.2 * c.1) - a.1 * (b.0 * c.2 - b.2 * c.0)
+                + a.2 * (b.0 * c.1 - b.1 * c.0)
+        };
+        let sixfold: i32 = faces
+            .iter()
+            .map(|face| {
+                let [a, b, c, d] = face.corners;
+                det(a, b, c) + det(a, c, d)
+            })
+            .sum();
+        sixfold / 6
+    }
+
+    #[test]
+    fn test_exposed_faces() {
+        let state = generator(EXAMPLE);
+        let exterior = exposed_faces(&state, Faces::Exterior);
+        let all = exposed_faces(&state, Faces::All);
+        assert_eq!(exterior.len(), part2(&state));
+        assert_eq!(all.len(), part1(&state));
+
+        // The outside also encloses the trapped air cube, while the inside
+        // of the pocket faces inwards and takes it away again.
+        assert_eq!(enclosed_volume(&exterior), 14);
+        assert_eq!(enclosed_volume(&all), 13);
+    }
+
+    #[test]
+    fn test_to_mesh() {
+        let cube = generator("0,0,0");
+        let obj = to_mesh(&cube, Faces::All, MeshFormat::Obj);
+        assert_eq!(obj.lines().filter(|line| line.starts_with("v ")).count(), 8);
+        assert_eq!(obj.lines().filter(|line| line.starts_with("f ")).count(), 6);
+        assert!(obj.contains("v 1 1 1\n"));
+
+        let stl = to_mesh(&cube, Faces::Exterior, MeshFormat::Stl);
+        assert!(stl.starts_with("solid droplet\n"));
+        assert!(stl.ends_with("endsolid droplet\n"));
+        assert_eq!(stl.matches("facet normal").count(), 12);
+        assert!(stl.contains("facet normal 0 0 -1\n"));
+    }
+}
@@ -0,0 +1,1346 @@
+use std::{
+    fmt::{self, Debug},
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "nom")]
+use nom::{
+    bytes::complete::tag,
+    character::complete::{multispace1, space1, u32},
+    error::{context, VerboseError, VerboseErrorKind},
+    sequence::{delimited, pair, preceded, separated_pair, terminated},
+    IResult,
+};
+
+enum Material {
+    Ore(u32),
+    Clay(u32),
+    Obsidian(u32),
+    Geode(u32),
+}
+
+use Material::*;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::AocError;
+use crate::utils::cancel::{CancelToken, Cancelled};
+use crate::utils::input::normalize;
+use crate::utils::memo::Memo;
+use crate::utils::progress::{NoProgress, Progress};
+use crate::utils::trace::{debug_event, enter_span};
+
+/**
+ * A generic bag of one of more resources.
+ *
+ * This can be used in a lot of ways, which we will definitely (ab)use:
+ *  * how many of each resource does a factory have?
+ *  * how many of each type of robot does a factory have?
+ *  * how much does one type of robot cost?
+ *
+ * Since we'll never need to track more than ~30 resources of any type,
+ * this is internally represented as a 32-bit integer:
+ *32       24       16        8        0
+ * +--------+--------+--------+--------+
+ * |  geode |obsidian|  clay  |  ore   |
+ * +--------+--------+--------+--------+
+ *
+ * This makes comparisons, addition, and subtraction very fast.
+ *
+ * This implementation is pretty unnecessary but I thought it was
+ * an interesting micro-optimization. It probably would have been more
+ * worthwhile to spend the time thinking about better heuristics.
+ */
+#[derive(Clone, Copy, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Resources {
+    data: u32,
+}
+
+impl Resources {
+    /**
+     * Adds the contents of two bags of resources.
+     */
+    fn add(&self, other: Self) -> Self {
+        Self {
+            data: self.data + other.data,
+        }
+    }
+
+    /**
+     * Utility method to add some quantity of a single material.
+     * This comes up a lot (e.g. adding one Ore robot).
+     */
+    fn add_one(&self, material: Material) -> Self {
+        Self {
+            data: self.data + Resources::encode_material(&material),
+        }
+    }
+
+    /**
+     * Attempts to subtract one bag of resources from another.
+     * Returns None if there isn't enough of any material to subtract,
+     * or Some(difference) if there's enough of each material.
+     */
+    fn checked_sub(&self, other: Self) -> Option<Self> {
+        // We explicitly *want* an underflowing subtraction.
+        let difference = self.data.wrapping_sub(other.data);
+
+        // We're not really subtracting u32's, we're subtracting four u8's in parallel.
+        // Any of those u8 subtractions could have underflowed.
+        // Since we will never store large numbers in this struct, we know:
+        //   * the highest bit should *never* be set unless there's been an underflow;
+        //   * the largest possible underflow is < 128
+        // This means that a u8 subtraction has underflowed iff the high bit of any
+        // byte is set, which we can check in a single operation.
+        if difference & 0x80808080 == 0 {
+            Some(Self { data: difference })
+        } else {
+            None
+        }
+    }
+
+    /**
+     * Creates a new empty bag of materials.
+     */
+    fn new() -> Self {
+        Self { data: 0 }
+    }
+
+    /**
+     * Creates a bag of resources out of a list.
+     */
+    fn from(materials: &[Material]) -> Self {
+        let mut data = 0;
+        for material in materials {
+            data += Resources::encode_material(material);
+        }
+        Self { data }
+    }
+
+    /**
+     * Creates a bag of resources containing a single material.
+     */
+    fn from_one(material: Material) -> Self {
+        Self {
+            data: Self::encode_material(&material),
+        }
+    }
+
+    /**
+     * Converts a material to the internal representation.
+     */
+    fn encode_material(material: &Material) -> u32 {
+        match material {
+            Ore(count) => *count,
+            Clay(count) => *count << 8,
+            Obsidian(count) => *count << 16,
+            Geode(count) => *count << 24,
+        }
+    }
+
+    /*
+     * Getters for each resource type.
+     */
+
+    fn ore(&self) -> u32 {
+        self.data & 0x000000FF
+    }
+
+    fn clay(&self) -> u32 {
+        (self.data & 0x0000FF00) >> 8
+    }
+
+    fn obsidian(&self) -> u32 {
+        (self.data & 0x00FF0000) >> 16
+    }
+
+    // Included for completeness; we've optimized out all calls to this.
+    #[allow(dead_code)]
+    fn geode(&self) -> u32 {
+        (self.data & 0xFF000000) >> 24
+    }
+}
+
+impl fmt::Debug for Resources {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{ ore: {}, clay: {}, obsidian: {}, geode: {} }}",
+            self.data & 0x000000FF,
+            (self.data & 0x0000FF00) >> 8,
+            (self.data & 0x00FF0000) >> 16,
+            (self.data & 0xFF000000) >> 24
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RobotCosts {
+    ore: Resources,
+    clay: Resources,
+    obsidian: Resources,
+    geode: Resources,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RobotFactory {
+    id: u32,
+    resources: Resources,
+    robots: Resources,
+    costs: RobotCosts,
+}
+
+impl fmt::Debug for RobotFactory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "RobotFactory({}) {{ resources: {:?}, robots: {:?} }}",
+            self.id, self.resources, self.robots
+        )
+    }
+}
+
+/**
+ * Prints the costs back out the way the puzzle words them, e.g. "Each ore
+ * robot costs 4 ore. Each clay robot costs 2 ore. ..."
+ */
+impl fmt::Display for RobotCosts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Each ore robot costs {} ore. \
+             Each clay robot costs {} ore. \
+             Each obsidian robot costs {} ore and {} clay. \
+             Each geode robot costs {} ore and {} obsidian.",
+            self.ore.ore(),
+            self.clay.ore(),
+            self.obsidian.ore(),
+            self.obsidian.clay(),
+            self.geode.ore(),
+            self.geode.obsidian()
+        )
+    }
+}
+
+/**
+ * Prints the factory's blueprint as a line of puzzle input.
+ */
+impl fmt::Display for RobotFactory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Blueprint {}: {}", self.id, self.costs)
+    }
+}
+
+#[derive(Clone, Hash, PartialEq, Eq, Debug)]
+struct State {
+    time_remaining: u32,
+    resources: Resources,
+    robots: Resources,
+}
+
+impl RobotFactory {
+    fn new(id: u32, costs: RobotCosts) -> Self {
+        RobotFactory {
+            id,
+            resources: Resources::new(),
+            robots: Resources::from_one(Ore(1)),
+            costs,
+        }
+    }
+
+    /*
+     * The next several functions attempt to build a robot starting from an
+     * initial factory state, given the number of minutes remaining.
+     *
+     * Returns either:
+     *   * None, if the robot cannot be completed in the time remaining, or
+     *     if building the robot would not be useful;
+     *   * A pair containing the time remaining after the ore robot is
+     *     completed, and the state of the factory once it's finished building.
+     *
+     * Note that it might take multiple minutes to gather the necessary resources
+     * before building the robot!
+     */
+
+    fn build_ore_robot(&self, time_remaining: u32) -> Option<(u32, RobotFactory)> {
+        // Optimization: never, ever build more ore robots than the largest ore cost.
+        // This would cause us to generate more ore per minute than we can spend.
+        #[rustfmt::skip]
+        let highest_ore_cost = self.costs.ore.ore()
+            .max(self.costs.clay.ore())
+            .max(self.costs.obsidian.ore())
+            .max(self.costs.geode.ore());
+        if self.robots.ore() >= highest_ore_cost {
+            return None;
+        }
+
+        self.build_robot(Ore(1), self.costs.ore, time_remaining)
+    }
+
+    fn build_clay_robot(&self, time_remaining: u32) -> Option<(u32, RobotFactory)> {
+        // Never build more clay robots than the highest clay cost.
+        // Only obsidian robots cost clay, so this is thankfully easier than ore.
+        if self.robots.clay() >= self.costs.obsidian.clay() {
+            return None;
+        }
+
+        self.build_robot(Clay(1), self.costs.clay, time_remaining)
+    }
+
+    fn build_obsidian_robot(&self, time_remaining: u32) -> Option<(u32, RobotFactory)> {
+        // Never build more obsidian robots than the highest obsidian cost.
+        if self.robots.obsidian() >= self.costs.geode.obsidian() {
+            return None;
+        }
+
+        // We will always have at least one ore robot; we need at least one
+        // clay robot to be able to *eventually* produce an obsidian robot.
+        if self.robots.clay() == 0 {
+            return None;
+        }
+
+        self.build_robot(Obsidian(1), self.costs.obsidian, time_remaining)
+    }
+
+    fn build_geode_robot(&self, time_remaining: u32) -> Option<(u32, RobotFactory)> {
+        // Make sure there's an obsidian robot available to gather the resources
+        // necessary for this geode robot.
+        if self.robots.obsidian() == 0 {
+            return None;
+        }
+
+        // Optimization: we never need to track the number of geode robots, since
+        // we count up the geodes it opens as soon as it's built (in find_best()).
+        // So, add "zero" geode robots to our state to reduce the space of
+        // possible cache keys.
+        self.build_robot(Geode(0), self.costs.geode, time_remaining)
+    }
+
+    fn build_robot(
+        &self,
+        robot_type: Material,
+        cost: Resources,
+        time_remaining: u32,
+    ) -> Option<(u32, RobotFactory)> {
+        // It always takes 1 minute to build the robot.
+        let mut new_time_remaining = time_remaining - 1;
+
+        // Simulate minutes repeatedly until we either run out of time, or
+        // have enough resources to build the robot.
+        let mut resources = self.resources;
+        loop {
+            if new_time_remaining == 0 {
+                // Ran out of time! We can't usefully build this robot.
+                return None;
+            } else if let Some(after_build) = resources.checked_sub(cost) {
+                // We have enough resources!
+                // Gather resources for the minute we'll spend building the robot.
+                resources = after_build.add(self.robots);
+
+                // Return a factory state with updated resources robots.
+                return Some((
+                    new_time_remaining,
+                    RobotFactory {
+                        id: self.id,
+                        resources,
+                        robots: self.robots.add_one(robot_type),
+                        costs: self.costs.clone(),
+                    },
+                ));
+            } else {
+                // We don't have enough resources this minute; gather for another
+                // minute then try again.
+                new_time_remaining -= 1;
+                resources = resources.add(self.robots);
+            }
+        }
+    }
+}
+
+/**
+ * Returns the maximum number of geodes that can be opened by robots produced
+ * on or after the current time.
+ *
+ * This somewhat-awkward phrasing means that we no longer need to track the
+ * total number of geodes or geode robots; this reduces the number of states.
+ *
+ * Inputs:
+ *  * the current factory state
+ *  * the amount of time remaining
+ *  * a cache of visited states
+ *
+ * The general approach is to pick out a type of robot to build next and recurse
+ * to find how many geodes we can open given that choice, then return the best.
+ *
+ * My original code simulated each minute rather than each decision; this approach
+ * cuts down the number of branches we explore and is much faster.
+ */
+fn find_best(
+    factory: &RobotFactory,
+    time_remaining: u32,
+    memo: &mut Memo<State, u32>,
+    stats: &mut SearchStats,
+    cancel: &CancelToken,
+) -> u32 {
+    stats.states_explored += 1;
+
+    // Once cancelled, unwind as quickly as possible. The result is garbage,
+    // but nobody's going to look at it.
+    if cancel.is_cancelled() {
+        return 0;
+    }
+
+    // If there's no time left, we can neither open geodes nor build robots.
+    // If there's only one minute left, we can make some new robots, but
+    // they won't have time to produce anything.
+    // Either way, no new robots can open geodes, so return 0.
+    if time_remaining <= 1 {
+        return 0;
+    }
+
+    // If we've already explored this state, we know the answer.
+    let state = State {
+        time_remaining,
+        resources: factory.resources,
+        robots: factory.robots,
+    };
+
+    if let Some(&best) = memo.get(&state) {
+        return best;
+    }
+
+    // There are at least two minutes left, so we have options.
+    //  1. Figure out what robots the factory can build (possibly over several minutes!).
+    //  2. Generate the factory state and updated time remaining for each option.
+    //  3. Recurse with an updated factory state and time_remaining.
+    //  4. Find the best option.
+    let mut best: u32 = 0;
+
+    // build_geode_robot() returns (time remaining after build, factory state after build).
+    if let Some((time, after_build)) = factory.build_geode_robot(time_remaining) {
+        // The new geode robot will open 1 geode per minute after being built.
+        best = time;
+
+        // Figure out how many geodes can be opened by future robots we build.
+        best += find_best(&after_build, time, memo, stats, cancel);
+
+        // Optimization: if we *can* build a geode robot this minute, we should do so.
+        // No other options needs to be explored.
+        //
+        // Note that it's possible to construct pathological blueprints for which this
+        // optimization gives the wrong answer! I believe this can only happen when a
+        // geode robot costs very little obsidian, which isn't the case for my input.
+        if time == time_remaining - 1 {
+            stats.pruned += 1;
+            return best;
+        }
+    }
+
+    // See whether we can make each type of robot in turn given the robots available.
+    if let Some((time, after_build)) = factory.build_ore_robot(time_remaining) {
+        let build_ore = find_best(&after_build, time, memo, stats, cancel);
+        best = best.max(build_ore);
+    }
+
+    if let Some((time, after_build)) = factory.build_clay_robot(time_remaining) {
+        let build_clay = find_best(&after_build, time, memo, stats, cancel);
+        best = best.max(build_clay);
+    }
+
+    if let Some((time, after_build)) = factory.build_obsidian_robot(time_remaining) {
+        let build_obsidian = find_best(&after_build, time, memo, stats, cancel);
+        best = best.max(build_obsidian);
+    }
+
+    // The recursive call returns the best *total* number of geodes.
+    // Store it for later use, then return it.
+    memo.insert(state, best);
+
+    best
+}
+
+/*
+ * Statistics about a single run of the search above.
+ */
+#[derive(Clone, Debug, Default)]
+pub struct SearchStats {
+    pub states_explored: u64,
+    pub cache_hits: u64,
+    // Branches skipped because we could build a geode robot immediately.
+    pub pruned: u64,
+    pub evictions: u64,
+    pub memo_size: usize,
+}
+
+/**
+ * Finds the most geodes a single blueprint can open in the given time.
+ */
+fn search_blueprint(
+    factory: &RobotFactory,
+    time: u32,
+    memo_capacity: Option<usize>,
+    cancel: &CancelToken,
+) -> (u32, SearchStats) {
+    enter_span!("blueprint", id = factory.id);
+    let mut stats = SearchStats::default();
+    let mut memo = match memo_capacity {
+        Some(capacity) => Memo::with_capacity(capacity),
+        None => Memo::new(),
+    };
+    let best = find_best(factory, time, &mut memo, &mut stats, cancel);
+    stats.cache_hits = memo.hits();
+    stats.evictions = memo.evictions();
+    stats.memo_size = memo.len();
+    debug_event!(
+        "search finished",
+        geodes = best,
+        states_explored = stats.states_explored,
+        cache_hits = stats.cache_hits,
+        pruned = stats.pruned,
+        evictions = stats.evictions,
+        memo_size = stats.memo_size,
+    );
+    (best, stats)
+}
+
+#[derive(Clone, Debug)]
+pub struct BlueprintStats {
+    pub id: u32,
+    pub geodes: u32,
+    pub search: SearchStats,
+    pub elapsed: Duration,
+}
+
+impl fmt::Display for BlueprintStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "blueprint {}: {} geodes, {} states explored, {} cache hits, {} pruned, {} evictions, memo size {}, {:?}",
+            self.id,
+            self.geodes,
+            self.search.states_explored,
+            self.search.cache_hits,
+            self.search.pruned,
+            self.search.evictions,
+            self.search.memo_size,
+            self.elapsed
+        )
+    }
+}
+
+/**
+ * Solves every blueprint the same way the parts do, but reports how each
+ * search went instead of just the score. Handy for tuning the heuristics.
+ *
+ * The memo can optionally be capped at `memo_capacity` entries, to keep
+ * memory use down on pathological blueprints (at the cost of more work).
+ */
+pub fn blueprint_stats(
+    factories: &[RobotFactory],
+    time: u32,
+    memo_capacity: Option<usize>,
+) -> Vec<BlueprintStats> {
+    factories
+        .iter()
+        .map(|factory| {
+            let start = Instant::now();
+            let (geodes, search) =
+                search_blueprint(factory, time, memo_capacity, &CancelToken::new());
+            BlueprintStats {
+                id: factory.id,
+                geodes,
+                search,
+                elapsed: start.elapsed(),
+            }
+        })
+        .collect()
+}
+
+/*
+ * The search above only returns the number of geodes. To get the build
+ * order that opens them, we re-derive it the same way as day 16: from each
+ * state, the next robot is the first one (in the order find_best() tries
+ * them) whose own best result accounts for the rest. The memo from the
+ * original search makes each of those lookups cheap.
+ */
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Robot {
+    Ore,
+    Clay,
+    Obsidian,
+    Geode,
+}
+
+/**
+ * Spending resources to start building a robot during the given minute
+ * (counting from 1, as the puzzle does). It's ready to collect from the
+ * minute after that one.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Build {
+    pub minute: u32,
+    pub robot: Robot,
+}
+
+/**
+ * The robots to build, in order, to open the most geodes in the given time.
+ */
+pub fn build_order(factory: &RobotFactory, minutes: u32) -> Vec<Build> {
+    let mut memo = Memo::new();
+    let mut stats = SearchStats::default();
+    let cancel = CancelToken::new();
+    let mut best = find_best(factory, minutes, &mut memo, &mut stats, &cancel);
+
+    let mut builds = Vec::new();
+    let mut factory = factory.clone();
+    let mut time_remaining = minutes;
+    while best > 0 {
+        // Mirrors the options (and their order) in find_best().
+        let options = [
+            (Robot::Geode, factory.build_geode_robot(time_remaining)),
+            (Robot::Ore, factory.build_ore_robot(time_remaining)),
+            (Robot::Clay, factory.build_clay_robot(time_remaining)),
+            (
+                Robot::Obsidian,
+                factory.build_obsidian_robot(time_remaining),
+            ),
+        ];
+        let (robot, time, after_build, rest) = options
+            .into_iter()
+            .find_map(|(robot, option)| {
+                let (time, after_build) = option?;
+                let opened = if robot == Robot::Geode { time } else { 0 };
+                let rest = find_best(&after_build, time, &mut memo, &mut stats, &cancel);
+                (opened + rest == best).then_some((robot, time, after_build, rest))
+            })
+            .expect("the best build order should be reachable");
+
+        builds.push(Build {
+            minute: minutes - time,
+            robot,
+        });
+        (factory, time_remaining, best) = (after_build, time, rest);
+    }
+
+    builds
+}
+
+/**
+ * Replays a build order minute by minute, returning the number of geodes
+ * opened, or None if it isn't possible: two robots started in the same
+ * minute, one started outside the time limit, or one that the factory
+ * can't afford when it's started.
+ */
+pub fn simulate(factory: &RobotFactory, minutes: u32, builds: &[Build]) -> Option<u32> {
+    let costs = &factory.costs;
+    // [ore, clay, obsidian] for each robot; nothing costs geodes.
+    let cost = |robot: Robot| match robot {
+        Robot::Ore => [costs.ore.ore(), 0, 0],
+        Robot::Clay => [costs.clay.ore(), 0, 0],
+        Robot::Obsidian => [costs.obsidian.ore(), costs.obsidian.clay(), 0],
+        Robot::Geode => [costs.geode.ore(), 0, costs.geode.obsidian()],
+    };
+
+    // [ore, clay, obsidian, geode], of both robots and what they've collected.
+    let mut robots = [1, 0, 0, 0];
+    let mut materials = [0u32; 4];
+    let mut builds = builds.to_vec();
+    builds.sort_by_key(|build| build.minute);
+    let mut builds = builds.into_iter().peekable();
+
+    for minute in 1..=minutes {
+        let mut robot = None;
+        if let Some(build) = builds.next_if(|build| build.minute == minute) {
+            for (material, cost) in materials.iter_mut().zip(cost(build.robot)) {
+                *material = material.checked_sub(cost)?;
+            }
+            robot = Some(build.robot);
+        }
+
+        for (material, count) in materials.iter_mut().zip(robots) {
+            *material += count;
+        }
+        if let Some(robot) = robot {
+            robots[robot as usize] += 1;
+        }
+    }
+
+    // Anything left over was either a second robot in some minute, or
+    // outside the time limit.
+    builds.next().is_none().then_some(materials[3])
+}
+
+/*
+ * The robot and material for each cost in a blueprint, in the order they
+ * appear in the input (after the blueprint's ID).
+ */
+const COSTS: [(&str, &str); 6] = [
+    ("ore", "ore"),
+    ("clay", "ore"),
+    ("obsidian", "ore"),
+    ("obsidian", "clay"),
+    ("geode", "ore"),
+    ("geode", "obsidian"),
+];
+
+/*
+ * Resources has 8 bits per material, and checked_sub() needs the top one
+ * free to spot underflows.
+ */
+const MAX_COST: u32 = 127;
+
+/**
+ * Something about a blueprint that the solver won't cope with.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BlueprintWarning {
+    // Every robot in the puzzle costs something. A free robot breaks the
+    // pruning, which only builds as many robots as the largest cost.
+    ZeroCost {
+        line: usize,
+        robot: &'static str,
+        material: &'static str,
+    },
+    // Too big to fit in a Resources.
+    TooExpensive {
+        line: usize,
+        robot: &'static str,
+        material: &'static str,
+        cost: u32,
+    },
+}
+
+impl fmt::Display for BlueprintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlueprintWarning::ZeroCost {
+                line,
+                robot,
+                material,
+            } => write!(f, "line {line}: the {robot} robot costs no {material}"),
+            BlueprintWarning::TooExpensive {
+                line,
+                robot,
+                material,
+                cost,
+            } => write!(
+                f,
+                "line {line}: the {robot} robot costs {cost} {material}, \
+                 but costs over {MAX_COST} don't fit in a Resources"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "nom")]
+type ParseResult<'a, T> = IResult<&'a str, T, VerboseError<&'a str>>;
+
+/*
+ * "4 ore", "14 clay" and so on.
+ */
+#[cfg(feature = "nom")]
+fn cost<'a>(material: &'static str) -> impl FnMut(&'a str) -> ParseResult<'a, u32> {
+    terminated(u32, pair(space1, tag(material)))
+}
+
+/*
+ * One robot's sentence, e.g. "Each clay robot costs 2 ore.", along with the
+ * whitespace before it. `what` says which sentence it is in errors.
+ */
+#[cfg(feature = "nom")]
+fn sentence<'a, O>(
+    what: &'static str,
+    robot: &'static str,
+    costs: impl FnMut(&'a str) -> ParseResult<'a, O>,
+) -> impl FnMut(&'a str) -> ParseResult<'a, O> {
+    context(
+        what,
+        preceded(
+            multispace1,
+            delimited(
+                pair(tag("Each "), pair(tag(robot), tag(" robot costs "))),
+                costs,
+                tag("."),
+            ),
+        ),
+    )
+}
+
+/*
+ * A whole blueprint: its ID, then its six costs in the order of COSTS.
+ */
+#[cfg(feature = "nom")]
+fn parse_blueprint(input: &str) -> ParseResult<'_, (u32, [u32; 6])> {
+    let (input, id) = context(
+        "\"Blueprint N:\"",
+        delimited(tag("Blueprint "), u32, tag(":")),
+    )(input)?;
+    let (input, ore) = sentence("the ore robot's cost", "ore", cost("ore"))(input)?;
+    let (input, clay) = sentence("the clay robot's cost", "clay", cost("ore"))(input)?;
+    let (input, obsidian) = sentence(
+        "the obsidian robot's costs",
+        "obsidian",
+        separated_pair(cost("ore"), tag(" and "), cost("clay")),
+    )(input)?;
+    let (input, geode) = sentence(
+        "the geode robot's costs",
+        "geode",
+        separated_pair(cost("ore"), tag(" and "), cost("obsidian")),
+    )(input)?;
+
+    Ok((
+        input,
+        (id, [ore, clay, obsidian.0, obsidian.1, geode.0, geode.1]),
+    ))
+}
+
+/*
+ * Parses every blueprint in the input, with the line each one starts on.
+ * Blueprints are usually one per line, but any whitespace will do between
+ * (and within) them, so the wrapped version from the puzzle's description
+ * works too.
+ */
+fn parse_blueprints(input: &str) -> Result<Vec<(usize, u32, [u32; 6])>, AocError> {
+    let mut blueprints = Vec::new();
+    let mut rest = input.trim_start();
+
+    while !rest.is_empty() {
+        let line = position(input, rest).0;
+        let (after, (id, costs)) =
+            parse_blueprint(rest).map_err(|err| blueprint_error(input, err))?;
+        blueprints.push((line, id, costs));
+        rest = after.trim_start();
+    }
+
+    Ok(blueprints)
+}
+
+/*
+ * The (1-indexed) line and column at which `rest` starts in `input`.
+ */
+fn position(input: &str, rest: &str) -> (usize, usize) {
+    let before = &input[..input.len() - rest.len()];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (
+        before.matches('\n').count() + 1,
+        before.len() - line_start + 1,
+    )
+}
+
+/*
+ * Reports where the innermost part of a blueprint that didn't match starts,
+ * and what it should have been.
+ */
+#[cfg(feature = "nom")]
+fn blueprint_error(input: &str, err: nom::Err<VerboseError<&str>>) -> AocError {
+    let err = match err {
+        nom::Err::Error(err) | nom::Err::Failure(err) => err,
+        nom::Err::Incomplete(_) => unreachable!("complete parsers never ask for more input"),
+    };
+    let (rest, expected) = err
+        .errors
+        .iter()
+        .find_map(|(rest, kind)| match kind {
+            VerboseErrorKind::Context(expected) => Some((*rest, *expected)),
+            _ => None,
+        })
+        .unwrap_or((err.errors[0].0, "a blueprint"));
+    error_at(input, rest, expected)
+}
+
+fn error_at(input: &str, rest: &str, expected: &str) -> AocError {
+    // Point at the start of the sentence, not the whitespace before it,
+    // unless it's missing altogether.
+    let rest = match rest.trim_start() {
+        "" => rest,
+        trimmed => trimmed,
+    };
+    let (line, column) = position(input, rest);
+    AocError::parse(line, format!("column {column}: expected {expected}"))
+}
+
+/*
+ * Without nom, the same grammar by hand. Errors are the input where the
+ * part that didn't match starts and what it should have been, like the
+ * contexts in the nom version.
+ */
+#[cfg(not(feature = "nom"))]
+type ParseResult<'a, T> = Result<(&'a str, T), (&'a str, &'static str)>;
+
+#[cfg(not(feature = "nom"))]
+fn number(input: &str) -> Option<(&str, u32)> {
+    let digits = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    Some((&input[digits..], input[..digits].parse().ok()?))
+}
+
+/*
+ * "4 ore", or "3 ore and 14 clay".
+ */
+#[cfg(not(feature = "nom"))]
+fn costs<'a, const N: usize>(input: &'a str, materials: [&str; N]) -> Option<(&'a str, [u32; N])> {
+    let mut rest = input;
+    let mut costs = [0; N];
+    for (i, material) in materials.iter().enumerate() {
+        if i > 0 {
+            rest = rest.strip_prefix(" and ")?;
+        }
+        let (after, cost) = number(rest)?;
+        let spaced = after.trim_start_matches([' ', '\t']);
+        if spaced.len() == after.len() {
+            return None;
+        }
+        rest = spaced.strip_prefix(material)?;
+        costs[i] = cost;
+    }
+    Some((rest, costs))
+}
+
+#[cfg(not(feature = "nom"))]
+fn sentence<'a, const N: usize>(
+    input: &'a str,
+    what: &'static str,
+    robot: &str,
+    materials: [&str; N],
+) -> ParseResult<'a, [u32; N]> {
+    let parse = || {
+        let rest = input.trim_start_matches([' ', '\t', '\r', '\n']);
+        if rest.len() == input.len() {
+            return None;
+        }
+        let rest = rest
+            .strip_prefix("Each ")?
+            .strip_prefix(robot)?
+            .strip_prefix(" robot costs ")?;
+        let (rest, costs) = costs(rest, materials)?;
+        Some((rest.strip_prefix('.')?, costs))
+    };
+    parse().ok_or((input, what))
+}
+
+#[cfg(not(feature = "nom"))]
+fn parse_blueprint(input: &str) -> ParseResult<'_, (u32, [u32; 6])> {
+    let (rest, id) = input
+        .strip_prefix("Blueprint ")
+        .and_then(number)
+        .and_then(|(rest, id)| Some((rest.strip_prefix(':')?, id)))
+        .ok_or((input, "\"Blueprint N:\""))?;
+    let (rest, [ore]) = sentence(rest, "the ore robot's cost", "ore", ["ore"])?;
+    let (rest, [clay]) = sentence(rest, "the clay robot's cost", "clay", ["ore"])?;
+    let (rest, obsidian) = sentence(
+        rest,
+        "the obsidian robot's costs",
+        "obsidian",
+        ["ore", "clay"],
+    )?;
+    let (rest, geode) = sentence(
+        rest,
+        "the geode robot's costs",
+        "geode",
+        ["ore", "obsidian"],
+    )?;
+
+    Ok((
+        rest,
+        (
+            id,
+            [ore, clay, obsidian[0], obsidian[1], geode[0], geode[1]],
+        ),
+    ))
+}
+
+#[cfg(not(feature = "nom"))]
+fn blueprint_error(input: &str, (rest, expected): (&str, &str)) -> AocError {
+    error_at(input, rest, expected)
+}
+
+/**
+ * Checks the blueprints before create_factories() gets to them. Anything
+ * that doesn't parse is an error; anything the solver would get wrong is
+ * returned as a warning.
+ *
+ * This works on the input rather than on RobotFactory, because a cost too
+ * big for a Resources spills over into the next material once it's packed.
+ */
+pub fn validate_blueprints(input: &str) -> Result<Vec<BlueprintWarning>, AocError> {
+    let input = normalize(input);
+    let mut warnings = Vec::new();
+
+    for (line, _, costs) in parse_blueprints(&input)? {
+        for (&(robot, material), cost) in COSTS.iter().zip(costs) {
+            if cost == 0 {
+                warnings.push(BlueprintWarning::ZeroCost {
+                    line,
+                    robot,
+                    material,
+                });
+            } else if cost > MAX_COST {
+                warnings.push(BlueprintWarning::TooExpensive {
+                    line,
+                    robot,
+                    material,
+                    cost,
+                });
+            }
+        }
+    }
+
+    Ok(warnings)
+}
+
+#[aoc_generator(day19)]
+pub fn create_factories(input: &str) -> Result<Vec<RobotFactory>, AocError> {
+    enter_span!("parse");
+    let input = normalize(input);
+
+    let factories = parse_blueprints(&input)?
+        .into_iter()
+        .map(
+            |(_, id, [ore, clay, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian])| {
+                let costs = RobotCosts {
+                    ore: Resources::from_one(Ore(ore)),
+                    clay: Resources::from_one(Ore(clay)),
+                    obsidian: Resources::from(&[Ore(obsidian_ore), Clay(obsidian_clay)]),
+                    geode: Resources::from(&[Ore(geode_ore), Obsidian(geode_obsidian)]),
+                };
+
+                RobotFactory::new(id, costs)
+            },
+        )
+        .collect();
+
+    Ok(factories)
+}
+
+#[aoc(day19, part1)]
+pub fn part1(factories: &[RobotFactory]) -> u32 {
+    part1_with(factories, 24)
+}
+
+/**
+ * Part 1, but with `minutes` minutes instead of 24.
+ */
+pub fn part1_with(factories: &[RobotFactory], minutes: u32) -> u32 {
+    part1_cancellable(factories, minutes, &NoProgress, &CancelToken::new())
+        .expect("nothing cancels this search")
+}
+
+/**
+ * Part 1, reporting how many blueprints have been searched as it goes, and
+ * giving up if `cancel` is cancelled before it's done.
+ */
+pub fn part1_cancellable(
+    factories: &[RobotFactory],
+    minutes: u32,
+    progress: &dyn Progress,
+    cancel: &CancelToken,
+) -> Result<u32, Cancelled> {
+    let mut result: u32 = 0;
+    for (done, factory) in factories.iter().enumerate() {
+        let (factory_best, _) = search_blueprint(factory, minutes, None, cancel);
+        cancel.check()?;
+        result += factory_best * factory.id;
+        progress.update("blueprints processed", done + 1, factories.len());
+    }
+
+    Ok(result)
+}
+
+#[aoc(day19, part2)]
+pub fn part2(factories: &[RobotFactory]) -> u32 {
+    part2_with(factories, 32)
+}
+
+/**
+ * Part 2, but with `minutes` minutes instead of 32.
+ */
+pub fn part2_with(factories: &[RobotFactory], minutes: u32) -> u32 {
+    part2_cancellable(factories, minutes, &NoProgress, &CancelToken::new())
+        .expect("nothing cancels this search")
+}
+
+/**
+ * Part 2, reporting how many blueprints have been searched as it goes, and
+ * giving up if `cancel` is cancelled before it's done.
+ */
+pub fn part2_cancellable(
+    factories: &[RobotFactory],
+    minutes: u32,
+    progress: &dyn Progress,
+    cancel: &CancelToken,
+) -> Result<u32, Cancelled> {
+    let best = factories
+        .iter()
+        .take(3)
+        .enumerate()
+        .map(|(done, factory)| {
+            let best = search_blueprint(factory, minutes, None, cancel).0;
+            cancel.check()?;
+            progress.update("blueprints processed", done + 1, factories.len().min(3));
+            Ok(best)
+        })
+        .collect::<Result<Vec<u32>, Cancelled>>()?;
+
+    Ok(best[0] * best[1] * best[2])
+}
+
+/*
+ * An independent way to get the same answers: write the blueprint as an
+ * integer linear program and hand it to a generic solver. It's much slower
+ * than the search above, but doesn't share any of its pruning heuristics,
+ * so it makes a good check on them.
+ *
+ * There's one 0/1 variable per (robot, minute), for "start building this
+ * robot during this minute". With minutes counted from 1 to T:
+ *   - at most one robot is started each minute;
+ *   - for ore, clay and obsidian, everything spent by the end of minute m
+ *     must have been collected during minutes 1 to m - 1. A robot started
+ *     in minute u collects one unit in each of minutes u + 1 onwards;
+ *   - the objective is the number of geodes opened, where a geode robot
+ *     started in minute u opens T - u of them.
+ */
+#[cfg(feature = "ilp")]
+fn build_program(factory: &RobotFactory, minutes: u32) -> crate::utils::ilp::Problem {
+    use crate::utils::ilp::Problem;
+
+    let minutes = minutes as usize;
+    // Robots in the order ore, clay, obsidian, geode.
+    let costs = [
+        factory.costs.ore,
+        factory.costs.clay,
+        factory.costs.obsidian,
+        factory.costs.geode,
+    ];
+    let var = |robot: usize, minute: usize| 4 * (minute - 1) + robot;
+
+    let mut objective = vec![0.0; 4 * minutes];
+    for minute in 1..=minutes {
+        objective[var(3, minute)] = (minutes - minute) as f64;
+    }
+    let mut program = Problem::new(objective);
+
+    for minute in 1..=minutes {
+        let mut row = vec![0.0; 4 * minutes];
+        for robot in 0..4 {
+            row[var(robot, minute)] = 1.0;
+        }
+        program.add_constraint(row, 1.0);
+    }
+
+    let initial_robots = [
+        factory.robots.ore(),
+        factory.robots.clay(),
+        factory.robots.obsidian(),
+    ];
+    for (material, initial) in initial_robots.into_iter().enumerate() {
+        let cost = |robot: usize| match material {
+            0 => costs[robot].ore(),
+            1 => costs[robot].clay(),
+            _ => costs[robot].obsidian(),
+        } as f64;
+
+        for minute in 1..=minutes {
+            let mut row = vec![0.0; 4 * minutes];
+            for started in 1..=minute {
+                for robot in 0..4 {
+                    row[var(robot, started)] += cost(robot);
+                }
+                if started + 1 < minute {
+                    row[var(material, started)] -= (minute - 1 - started) as f64;
+                }
+            }
+            program.add_constraint(row, (initial * (minute as u32 - 1)) as f64);
+        }
+    }
+
+    program
+}
+
+/**
+ * The most geodes a blueprint can open, found by the ILP solver.
+ */
+#[cfg(feature = "ilp")]
+pub fn max_geodes_ilp(factory: &RobotFactory, minutes: u32) -> u32 {
+    let solution = build_program(factory, minutes)
+        .maximize()
+        .expect("doing nothing is always possible");
+    solution.value.round() as u32
+}
+
+#[cfg(feature = "ilp")]
+#[aoc(day19, part1, Ilp)]
+pub fn part1_ilp(factories: &[RobotFactory]) -> u32 {
+    factories
+        .iter()
+        .map(|factory| max_geodes_ilp(factory, 24) * factory.id)
+        .sum()
+}
+
+#[cfg(feature = "ilp")]
+#[aoc(day19, part2, Ilp)]
+pub fn part2_ilp(factories: &[RobotFactory]) -> u32 {
+    factories
+        .iter()
+        .take(3)
+        .map(|factory| max_geodes_ilp(factory, 32))
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::testing::read_example;
+
+    #[test]
+    fn test_blueprint_stats() {
+        let Some(input) = read_example("day19.txt") else {
+            return;
+        };
+        let factories = create_factories(&input).unwrap();
+        let stats = blueprint_stats(&factories, 24, None);
+        let geodes: Vec<u32> = stats.iter().map(|s| s.geodes).collect();
+        assert_eq!(geodes, vec![9, 12]);
+        assert!(stats.iter().all(|s| s.search.states_explored > 0));
+
+        // Capping the memo shouldn't change the answers, just the work done.
+        let capped = blueprint_stats(&factories, 24, Some(100));
+        let geodes: Vec<u32> = capped.iter().map(|s| s.geodes).collect();
+        assert_eq!(geodes, vec![9, 12]);
+        assert!(capped.iter().all(|s| s.search.memo_size <= 100));
+    }
+
+    #[test]
+    fn test_build_order() {
+        let Some(input) = read_example("day19.txt") else {
+            return;
+        };
+        let factories = create_factories(&input).unwrap();
+        for (factory, geodes) in factories.iter().zip([9, 12]) {
+            let builds = build_order(factory, 24);
+            assert_eq!(simulate(factory, 24, &builds), Some(geodes));
+        }
+
+        // The order from the puzzle's walkthrough.
+        let walkthrough: Vec<_> = [
+            (3, Robot::Clay),
+            (5, Robot::Clay),
+            (7, Robot::Clay),
+            (11, Robot::Obsidian),
+            (12, Robot::Clay),
+            (15, Robot::Obsidian),
+            (18, Robot::Geode),
+            (21, Robot::Geode),
+        ]
+        .into_iter()
+        .map(|(minute, robot)| Build { minute, robot })
+        .collect();
+        assert_eq!(simulate(&factories[0], 24, &walkthrough), Some(9));
+
+        // Only 2 ore by minute 3, but an ore robot costs 4.
+        let mut impossible = walkthrough;
+        impossible[0].robot = Robot::Ore;
+        assert_eq!(simulate(&factories[0], 24, &impossible), None);
+        // Two robots in the same minute.
+        impossible[0] = Build {
+            minute: 5,
+            robot: Robot::Clay,
+        };
+        assert_eq!(simulate(&factories[0], 24, &impossible), None);
+    }
+
+    #[cfg(feature = "ilp")]
+    #[test]
+    fn test_ilp() {
+        let Some(input) = read_example("day19.txt") else {
+            return;
+        };
+        let factories = create_factories(&input).unwrap();
+        let geodes: Vec<u32> = factories.iter().map(|f| max_geodes_ilp(f, 24)).collect();
+        assert_eq!(geodes, vec![9, 12]);
+    }
+
+    #[test]
+    fn test_display() {
+        let Some(input) = read_example("day19.txt") else {
+            return;
+        };
+        let factories = create_factories(&input).unwrap();
+        let printed: Vec<String> = factories.iter().map(|f| f.to_string()).collect();
+        assert_eq!(printed, input.lines().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_validate_blueprints() {
+        let Some(input) = read_example("day19.txt") else {
+            return;
+        };
+        assert_eq!(validate_blueprints(&input), Ok(vec![]));
+
+        let input = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. \
+                     Each obsidian robot costs 3 ore and 0 clay. \
+                     Each geode robot costs 2 ore and 300 obsidian.\n";
+        let warnings = validate_blueprints(input).unwrap();
+        assert_eq!(
+            warnings,
+            vec![
+                BlueprintWarning::ZeroCost {
+                    line: 1,
+                    robot: "obsidian",
+                    material: "clay"
+                },
+                BlueprintWarning::TooExpensive {
+                    line: 1,
+                    robot: "geode",
+                    material: "obsidian",
+                    cost: 300
+                },
+            ]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "line 1: the obsidian robot costs no clay"
+        );
+
+        assert_eq!(
+            validate_blueprints("Blueprint 1: Each ore robot costs 4 ore.\n"),
+            Err(AocError::parse(
+                1,
+                "column 41: expected the clay robot's cost"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        // The puzzle's description wraps the blueprints over several lines.
+        let wrapped = "Blueprint 1:\n  Each ore robot costs 4 ore.\n  \
+                       Each clay robot costs 2 ore.\n  \
+                       Each obsidian robot costs 3 ore and 14 clay.\n  \
+                       Each geode robot costs 2 ore and 7 obsidian.\n\n";
+        let factories = create_factories(wrapped).unwrap();
+        assert_eq!(
+            factories[0].to_string(),
+            "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. \
+             Each obsidian robot costs 3 ore and 14 clay. \
+             Each geode robot costs 2 ore and 7 obsidian."
+        );
+
+        // The costs swapped around, which picking out the numbers in order
+        // would have quietly accepted.
+        let swapped = wrapped.replace("3 ore and 14 clay", "14 clay and 3 ore");
+        assert_eq!(
+            create_factories(&swapped).unwrap_err(),
+            AocError::parse(4, "column 3: expected the obsidian robot's costs")
+        );
+        assert_eq!(
+            create_factories(&(wrapped.to_string() + "Blueprint two:")).unwrap_err(),
+            AocError::parse(7, "column 1: expected \"Blueprint N:\"")
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::utils::input::normalize;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shape {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Shape {
+    /**
+     * Parses a shape from the first column of the strategy guide.
+     */
+    fn from_opponent(byte: u8) -> Shape {
+        match byte {
+            b'A' => Shape::Rock,
+            b'B' => Shape::Paper,
+            b'C' => Shape::Scissors,
+            _ => panic!("unexpected shape"),
+        }
+    }
+
+    /**
+     * Returns the shape that this shape defeats.
+     */
+    pub fn beats(&self) -> Shape {
+        match self {
+            Shape::Rock => Shape::Scissors,
+            Shape::Paper => Shape::Rock,
+            Shape::Scissors => Shape::Paper,
+        }
+    }
+
+    /**
+     * Returns the shape that defeats this shape.
+     */
+    pub fn beaten_by(&self) -> Shape {
+        match self {
+            Shape::Rock => Shape::Paper,
+            Shape::Paper => Shape::Scissors,
+            Shape::Scissors => Shape::Rock,
+        }
+    }
+
+    pub fn score(&self) -> u32 {
+        match self {
+            Shape::Rock => 1,
+            Shape::Paper => 2,
+            Shape::Scissors => 3,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    /**
+     * The outcome of a round, from my point of view.
+     */
+    pub fn of(opponent: Shape, me: Shape) -> Outcome {
+        if me == opponent {
+            Outcome::Draw
+        } else if me.beats() == opponent {
+            Outcome::Win
+        } else {
+            Outcome::Loss
+        }
+    }
+
+    pub fn score(&self) -> u32 {
+        match self {
+            Outcome::Loss => 0,
+            Outcome::Draw => 3,
+            Outcome::Win => 6,
+        }
+    }
+}
+
+/**
+ * The total score for a single round: the value of my shape plus the
+ * value of the outcome.
+ */
+pub fn score_round(opponent: Shape, me: Shape) -> u32 {
+    me.score() + Outcome::of(opponent, me).score()
+}
+
+/*
+ * The two ways of reading the second column of the guide. Both take the
+ * opponent's shape and the raw column, and return the shape I should play.
+ */
+
+// Part 1: X, Y, and Z are the shapes to play.
+pub fn interpret_as_shape(_opponent: Shape, column: u8) -> Shape {
+    match column {
+        b'X' => Shape::Rock,
+        b'Y' => Shape::Paper,
+        b'Z' => Shape::Scissors,
+        _ => panic!("unexpected shape"),
+    }
+}
+
+// Part 2: X, Y, and Z are the outcomes we need to produce.
+pub fn interpret_as_outcome(opponent: Shape, column: u8) -> Shape {
+    match column {
+        b'X' => opponent.beats(),
+        b'Y' => opponent,
+        b'Z' => opponent.beaten_by(),
+        _ => panic!("unexpected outcome"),
+    }
+}
+
+/**
+ * A named way of reading the guide's second column, for tournament().
+ */
+#[derive(Clone, Copy)]
+pub struct Interpretation<'a> {
+    pub name: &'a str,
+    pub interpret: &'a dyn Fn(Shape, u8) -> Shape,
+}
+
+pub const AS_SHAPE: Interpretation<'static> = Interpretation {
+    name: "shape",
+    interpret: &interpret_as_shape,
+};
+
+pub const AS_OUTCOME: Interpretation<'static> = Interpretation {
+    name: "outcome",
+    interpret: &interpret_as_outcome,
+};
+
+/**
+ * The total score under each of several interpretations, in the same order.
+ */
+pub struct Tournament<'a> {
+    pub scores: Vec<(&'a str, u32)>,
+}
+
+impl<'a> Tournament<'a> {
+    /**
+     * The names of the interpretations that give the `expected` score.
+     */
+    pub fn matching(&self, expected: u32) -> Vec<&'a str> {
+        self.scores
+            .iter()
+            .filter(|(_, score)| *score == expected)
+            .map(|(name, _)| *name)
+            .collect()
+    }
+}
+
+impl fmt::Display for Tournament<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .scores
+            .iter()
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or(0);
+        for (name, score) in &self.scores {
+            writeln!(f, "{name:width$}  {score}")?;
+        }
+        Ok(())
+    }
+}
+
+/**
+ * Scores the guide under every interpretation at once, reading it only
+ * once. Handy for working out which reading gives the score you expect.
+ */
+pub fn tournament<'a>(input: &str, interpretations: &[Interpretation<'a>]) -> Tournament<'a> {
+    let mut scores: Vec<(&str, u32)> = interpretations
+        .iter()
+        .map(|interpretation| (interpretation.name, 0))
+        .collect();
+
+    for line in normalize(input).lines() {
+        let bytes = line.as_bytes();
+        let opponent = Shape::from_opponent(bytes[0]);
+        for (interpretation, (_, score)) in interpretations.iter().zip(scores.iter_mut()) {
+            *score += score_round(opponent, (interpretation.interpret)(opponent, bytes[2]));
+        }
+    }
+
+    Tournament { scores }
+}
+
+#[cfg_attr(feature = "std", aoc(day2, part1))]
+pub fn part1(input: &str) -> u32 {
+    tournament(input, &[AS_SHAPE]).scores[0].1
+}
+
+#[cfg_attr(feature = "std", aoc(day2, part2))]
+pub fn part2(input: &str) -> u32 {
+    tournament(input, &[AS_OUTCOME]).scores[0].1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "A Y\n\
+                           B X\n\
+                           C Z";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(EXAMPLE), 15);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(EXAMPLE), 12);
+    }
+
+    #[test]
+    fn test_tournament() {
+        // Always playing whatever the opponent just played.
+        let copycat = |opponent: Shape, _| opponent;
+        let interpretations = [
+            AS_SHAPE,
+            AS_OUTCOME,
+            Interpretation {
+                name: "copycat",
+                interpret: &copycat,
+            },
+        ];
+
+        let results = tournament(EXAMPLE, &interpretations);
+        assert_eq!(
+            results.scores,
+            vec![("shape", 15), ("outcome", 12), ("copycat", 15)]
+        );
+        assert_eq!(results.matching(15), vec!["shape", "copycat"]);
+        assert_eq!(
+            alloc::format!("{results}"),
+            "shape    15\noutcome  12\ncopycat  15\n"
+        );
+    }
+
+    #[test]
+    fn test_score_round() {
+        assert_eq!(score_round(Shape::Rock, Shape::Paper), 8);
+        assert_eq!(score_round(Shape::Paper, Shape::Rock), 1);
+        assert_eq!(score_round(Shape::Scissors, Shape::Scissors), 6);
+    }
+}
@@ -0,0 +1,352 @@
+use crate::utils::input::{ints_from_bytes, normalize};
+
+/**
+ * The encrypted file, as a circular list of numbers that can be mixed.
+ *
+ * Each number is stored along with its original position, which makes it
+ * easy to process them in their original order, even after mixing multiple
+ * times, and even when the same value appears more than once.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MixList {
+    entries: Vec<(usize, i64)>,
+}
+
+impl MixList {
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /**
+     * The numbers, in their current order.
+     */
+    pub fn values(&self) -> impl Iterator<Item = i64> + '_ {
+        self.entries.iter().map(|&(_, value)| value)
+    }
+
+    /**
+     * A copy of the list with every number multiplied by `key`, as part 2's
+     * decryption key requires. The original order is kept.
+     */
+    pub fn with_key(&self, key: i64) -> MixList {
+        self.entries
+            .iter()
+            .map(|&(idx, value)| (idx, value * key))
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    /**
+     * Performs one iteration of the "mix" operation in-place.
+     */
+    pub fn mix(&mut self) {
+        let entries = &mut self.entries;
+        for original_idx in 0..entries.len() {
+            // Find the *current* index of the value *originally* at original_idx.
+            let current_idx = entries
+                .iter()
+                .position(|&(idx, _)| original_idx == idx)
+                .unwrap();
+
+            // Remove that element from the list.
+            let (orig_idx, value) = entries.remove(current_idx);
+
+            // Find the new index that element should be moved to.
+            // rem_euclid is basically like % but always returns a nonnegative value.
+            let new_idx = (current_idx as i64 + value).rem_euclid(entries.len() as i64);
+
+            // Insert the element into its new location.
+            entries.insert(new_idx as usize, (orig_idx, value));
+        }
+    }
+
+    /**
+     * Mixes the list `n` times.
+     */
+    pub fn rounds(&mut self, n: usize) {
+        for _ in 0..n {
+            self.mix();
+        }
+    }
+
+    /**
+     * Returns the "grove positioning coordinates": the sum of the numbers
+     * 1000, 2000 and 3000 places after the 0.
+     */
+    pub fn coordinates(&self) -> i64 {
+        let entries = &self.entries;
+        // Find the index of value 0 in the list provided.
+        let zero_idx = entries.iter().position(|&(_, val)| val == 0).unwrap();
+
+        // Find the values 1000, 2000, and 3000 out from that and add them.
+        entries[(zero_idx + 1000) % entries.len()].1
+            + entries[(zero_idx + 2000) % entries.len()].1
+            + entries[(zero_idx + 3000) % entries.len()].1
+    }
+}
+
+impl From<Vec<(usize, i64)>> for MixList {
+    fn from(entries: Vec<(usize, i64)>) -> Self {
+        MixList { entries }
+    }
+}
+
+/**
+ * Numbers are given their original positions in the order they're collected.
+ */
+impl FromIterator<i64> for MixList {
+    fn from_iter<T: IntoIterator<Item = i64>>(iter: T) -> Self {
+        iter.into_iter().enumerate().collect::<Vec<_>>().into()
+    }
+}
+
+/**
+ * Given a string containing one number per line, returns them as a MixList.
+ */
+fn parse_numbers(input: &str) -> MixList {
+    input
+        .trim()
+        .lines()
+        .map(|line| line.parse::<i64>().unwrap())
+        .collect()
+}
+
+/**
+ * The same as parse_numbers(), but scanning the input bytes directly.
+ */
+fn parse_numbers_bytes(input: &[u8]) -> MixList {
+    ints_from_bytes(input).collect()
+}
+
+#[aoc_generator(day20)]
+pub fn generator(input: &str) -> MixList {
+    parse_numbers(&normalize(input))
+}
+
+/*
+ * The same as generator(), but scanning the input bytes directly.
+ */
+#[aoc_generator(day20, part1, Bytes)]
+pub fn generator_bytes(input: &[u8]) -> MixList {
+    parse_numbers_bytes(input)
+}
+
+// cargo-aoc looks up generators per part, so part 2 needs its own entry.
+#[aoc_generator(day20, part2, Bytes)]
+fn generator_bytes_part2(input: &[u8]) -> MixList {
+    generator_bytes(input)
+}
+
+fn decrypt_once(numbers: &MixList) -> i64 {
+    let mut numbers = numbers.clone();
+    numbers.mix();
+    numbers.coordinates()
+}
+
+fn decrypt_with_key(numbers: &MixList) -> i64 {
+    // This time we have to multiply each number by the "decryption key",
+    // and mix 10 times.
+    let mut numbers = numbers.with_key(811589153);
+    numbers.rounds(10);
+    numbers.coordinates()
+}
+
+#[aoc(day20, part1)]
+pub fn part1(numbers: &MixList) -> i64 {
+    decrypt_once(numbers)
+}
+
+#[aoc(day20, part2)]
+pub fn part2(numbers: &MixList) -> i64 {
+    decrypt_with_key(numbers)
+}
+
+#[aoc(day20, part1, Bytes)]
+pub fn part1_bytes(numbers: &MixList) -> i64 {
+    decrypt_once(numbers)
+}
+
+#[aoc(day20, part2, Bytes)]
+pub fn part2_bytes(numbers: &MixList) -> i64 {
+    decrypt_with_key(numbers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "1\n2\n-3\n3\n-2\n0\n4\n";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&generator(EXAMPLE)), 3);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&generator(EXAMPLE)), 1623178306);
+    }
+
+    #[test]
+    fn test_bytes() {
+        assert_eq!(part1_bytes(&generator_bytes(EXAMPLE.as_bytes())), 3);
+        assert_eq!(
+            part2_bytes(&generator_bytes(EXAMPLE.as_bytes())),
+            1623178306
+        );
+    }
+
+    /*
+     * A slow but obviously-correct mixer to check mix() against: the numbers
+     * are kept in a circular doubly-linked list (as next/prev arrays indexed
+     * by original position), and each one is unlinked, walked forward, and
+     * relinked, exactly as the puzzle describes.
+     *
+     * Once a number is unlinked there are only len - 1 others in the circle,
+     * so moving it len - 1 steps brings it back to where it was. That's the
+     * modulus mix() relies on, and the easiest part of it to get wrong.
+     */
+    struct ReferenceMixer {
+        values: Vec<i64>,
+        next: Vec<usize>,
+        prev: Vec<usize>,
+    }
+
+    impl ReferenceMixer {
+        fn new(values: &[i64]) -> Self {
+            let len = values.len();
+            ReferenceMixer {
+                values: values.to_vec(),
+                next: (0..len).map(|i| (i + 1) % len).collect(),
+                prev: (0..len).map(|i| (i + len - 1) % len).collect(),
+            }
+        }
+
+        fn mix(&mut self) {
+            let len = self.values.len();
+            for node in 0..len {
+                let steps = self.values[node].rem_euclid(len as i64 - 1);
+                if steps == 0 {
+                    continue;
+                }
+
+                // Unlink the node...
+                let (before, after) = (self.prev[node], self.next[node]);
+                self.next[before] = after;
+                self.prev[after] = before;
+
+                // ...walk forward from where it was...
+                let mut target = before;
+                for _ in 0..steps {
+                    target = self.next[target];
+                }
+
+                // ...and link it back in after `target`.
+                let after = self.next[target];
+                self.next[target] = node;
+                self.prev[node] = target;
+                self.next[node] = after;
+                self.prev[after] = node;
+            }
+        }
+
+        /**
+         * The values in order around the circle, starting from the number
+         * that was originally first.
+         */
+        fn cycle(&self) -> Vec<i64> {
+            let mut result = vec![self.values[0]];
+            let mut node = self.next[0];
+            while node != 0 {
+                result.push(self.values[node]);
+                node = self.next[node];
+            }
+            result
+        }
+    }
+
+    /**
+     * The same as ReferenceMixer::cycle(), for a MixList.
+     */
+    fn cycle(numbers: &MixList) -> Vec<i64> {
+        let start = numbers
+            .entries
+            .iter()
+            .position(|&(idx, _)| idx == 0)
+            .unwrap();
+        let (after, before) = numbers.entries.split_at(start);
+        before
+            .iter()
+            .chain(after)
+            .map(|&(_, value)| value)
+            .collect()
+    }
+
+    /**
+     * Mixes `values` three times, checking mix() against ReferenceMixer
+     * after each round.
+     */
+    fn check_against_reference(values: &[i64]) -> Result<(), TestCaseError> {
+        let mut numbers: MixList = values.iter().copied().collect();
+        let mut reference = ReferenceMixer::new(values);
+        for round in 1..=3 {
+            numbers.mix();
+            reference.mix();
+            prop_assert_eq!(
+                cycle(&numbers),
+                reference.cycle(),
+                "mixing {:?}, round {}",
+                values,
+                round
+            );
+        }
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn test_mix_matches_reference(
+            values in prop::collection::vec(-20i64..=20, 2..12),
+            // Some cases get part 2's huge numbers.
+            key in prop_oneof![Just(1), Just(811589153)],
+        ) {
+            let values: Vec<i64> = values.iter().map(|value| value * key).collect();
+            check_against_reference(&values)?;
+        }
+
+        #[test]
+        fn test_mix_duplicates_matches_reference(
+            // Just a handful of values, so the lists are full of duplicates
+            // (including several zeroes).
+            values in prop::collection::vec(-3i64..=3, 2..12),
+        ) {
+            check_against_reference(&values)?;
+        }
+    }
+
+    #[test]
+    fn test_duplicates() {
+        // Two 1s and two -1s: each copy moves on its own turn, in its
+        // original position's order.
+        let mut numbers: MixList = [1, 1, 0, -1, -1].into_iter().collect();
+        numbers.mix();
+        let mut reference = ReferenceMixer::new(&[1, 1, 0, -1, -1]);
+        reference.mix();
+        assert_eq!(cycle(&numbers), reference.cycle());
+        assert_eq!(numbers.len(), 5);
+
+        // rounds(n) is just n mixes.
+        let mut twice = generator(EXAMPLE);
+        twice.rounds(2);
+        let mut by_hand = generator(EXAMPLE);
+        by_hand.mix();
+        by_hand.mix();
+        assert_eq!(twice, by_hand);
+        assert_eq!(twice.values().filter(|&v| v == 0).count(), 1);
+    }
+}
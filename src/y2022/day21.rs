@@ -0,0 +1,517 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use num::{BigInt, Zero};
+
+use crate::utils::input::normalize;
+use crate::utils::paths::bfs;
+
+#[derive(Clone, Debug)]
+pub enum Operation {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl Operation {
+    fn from(s: &str) -> Self {
+        match s {
+            "+" => Self::Add,
+            "-" => Self::Subtract,
+            "*" => Self::Multiply,
+            "/" => Self::Divide,
+            c => panic!("invalid operation: {}", c),
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            Self::Add => "+",
+            Self::Subtract => "-",
+            Self::Multiply => "*",
+            Self::Divide => "/",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Monkey {
+    Number(i64),
+    Computation(String, String, Operation),
+}
+
+#[aoc_generator(day21)]
+pub fn generator(input: &str) -> HashMap<String, Monkey> {
+    let input = normalize(input);
+    input
+        .lines()
+        .map(|line| {
+            let (name, computation) = line.split_once(": ").unwrap();
+            let monkey: Monkey;
+            if let Ok(value) = computation.parse::<i64>() {
+                monkey = Monkey::Number(value);
+            } else {
+                let parts: Vec<&str> = computation.split(' ').collect();
+                monkey = Monkey::Computation(
+                    parts[0].to_string(),
+                    parts[2].to_string(),
+                    Operation::from(parts[1]),
+                );
+            }
+            (name.to_string(), monkey)
+        })
+        .collect()
+}
+
+/*
+ * The real inputs fit comfortably in an i64, but nothing stops a chain of
+ * multiplications from overflowing one. So the evaluation is generic over
+ * the kind of number: i64 with checked arithmetic, which gives up (returns
+ * None) on overflow, or BigInt, which can't overflow. The parts try i64
+ * first, since it's much faster, and only fall back to BigInt if that fails.
+ */
+pub trait Value: Clone {
+    fn from_i64(value: i64) -> Self;
+
+    /**
+     * Applies an operation, or returns None if the result can't be
+     * represented (overflow, or division by zero).
+     */
+    fn apply(operation: &Operation, lhs: &Self, rhs: &Self) -> Option<Self>;
+}
+
+impl Value for i64 {
+    fn from_i64(value: i64) -> Self {
+        value
+    }
+
+    fn apply(operation: &Operation, lhs: &Self, rhs: &Self) -> Option<Self> {
+        match operation {
+            Operation::Add => lhs.checked_add(*rhs),
+            Operation::Subtract => lhs.checked_sub(*rhs),
+            Operation::Multiply => lhs.checked_mul(*rhs),
+            Operation::Divide => lhs.checked_div(*rhs),
+        }
+    }
+}
+
+impl Value for BigInt {
+    fn from_i64(value: i64) -> Self {
+        BigInt::from(value)
+    }
+
+    fn apply(operation: &Operation, lhs: &Self, rhs: &Self) -> Option<Self> {
+        match operation {
+            Operation::Add => Some(lhs + rhs),
+            Operation::Subtract => Some(lhs - rhs),
+            Operation::Multiply => Some(lhs * rhs),
+            Operation::Divide if rhs.is_zero() => None,
+            // Like i64, this rounds towards zero.
+            Operation::Divide => Some(lhs / rhs),
+        }
+    }
+}
+
+/**
+ * Works out the value of the monkey `root`, remembering the values of all
+ * of the monkeys along the way in `values` (which can be reused between
+ * calls). Returns None if the arithmetic fails.
+ */
+fn evaluate<T: Value>(
+    root: &str,
+    monkeys: &HashMap<String, Monkey>,
+    values: &mut HashMap<String, T>,
+) -> Option<T> {
+    // Strategy: DFS from "root" node.
+    let mut stack = vec![root];
+
+    while let Some(name) = stack.pop() {
+        // If we already have a value for this monkey, nothing further is needed.
+        // Otherwise we need to either compute its value, or determine which monkeys we need.
+        if values.contains_key(name) {
+            continue;
+        }
+
+        match &monkeys[name] {
+            Monkey::Number(value) => {
+                values.insert(name.to_owned(), T::from_i64(*value));
+            }
+            Monkey::Computation(lhs, rhs, operation) => {
+                if let (Some(lhs_val), Some(rhs_val)) = (values.get(lhs), values.get(rhs)) {
+                    let value = T::apply(operation, lhs_val, rhs_val)?;
+                    values.insert(name.to_owned(), value);
+                    continue;
+                }
+
+                // At least one parent doesn't have a value yet, so we can't
+                // get a value for the current node yet either. Because it's a
+                // stack, the parents will be processed before we try the
+                // current node again.
+                stack.push(name);
+                stack.push(lhs);
+                stack.push(rhs);
+            }
+        }
+    }
+
+    values.get(root).cloned()
+}
+
+#[aoc(day21, part1)]
+pub fn part1(input: &HashMap<String, Monkey>) -> i64 {
+    if let Some(value) = evaluate::<i64>("root", input, &mut HashMap::new()) {
+        return value;
+    }
+
+    // Something overflowed, but the answer itself might still fit.
+    i64::try_from(part1_big(input)).expect("the answer doesn't fit in an i64; use part1_big")
+}
+
+/**
+ * The same as part1(), but always using big integers.
+ */
+pub fn part1_big(input: &HashMap<String, Monkey>) -> BigInt {
+    evaluate("root", input, &mut HashMap::new()).expect("division by zero")
+}
+
+/**
+ * Finds the path from "root" to "humn".
+ * This is just a list of the monkeys' names.
+ */
+fn path_to_human(monkeys: &HashMap<String, Monkey>) -> Vec<String> {
+    bfs(
+        &"root".to_string(),
+        |name| match &monkeys[name] {
+            Monkey::Number(_) => vec![],
+            Monkey::Computation(lhs, rhs, _) => vec![lhs.to_owned(), rhs.to_owned()],
+        },
+        |name| name == "humn",
+    )
+    .unwrap()
+}
+
+/**
+ * Renders the monkeys as a Graphviz digraph, with an edge from each monkey
+ * to the monkeys it's waiting on. The path from "root" to "humn" (the one
+ * part 2 has to invert) is highlighted in red.
+ *
+ * Try `dot -Tsvg monkeys.dot > monkeys.svg`.
+ */
+pub fn to_dot(monkeys: &HashMap<String, Monkey>) -> String {
+    let path = path_to_human(monkeys);
+    let on_path: HashSet<&String> = path.iter().collect();
+
+    // Sort by name so the output is stable.
+    let mut names: Vec<&String> = monkeys.keys().collect();
+    names.sort();
+
+    let mut dot = String::new();
+    writeln!(dot, "digraph monkeys {{").unwrap();
+    for name in names {
+        let highlight = if on_path.contains(name) {
+            ", color=red, fontcolor=red"
+        } else {
+            ""
+        };
+
+        match &monkeys[name] {
+            Monkey::Number(value) => {
+                writeln!(dot, "    {name} [label=\"{name}\\n{value}\"{highlight}];").unwrap();
+            }
+            Monkey::Computation(lhs, rhs, operation) => {
+                let symbol = operation.symbol();
+                writeln!(dot, "    {name} [label=\"{name}\\n{symbol}\"{highlight}];").unwrap();
+                for operand in [lhs, rhs] {
+                    let edge_highlight = if on_path.contains(name) && on_path.contains(operand) {
+                        " [color=red]"
+                    } else {
+                        ""
+                    };
+                    writeln!(dot, "    {name} -> {operand}{edge_highlight};").unwrap();
+                }
+            }
+        }
+    }
+    writeln!(dot, "}}").unwrap();
+
+    dot
+}
+
+#[aoc(day21, part2)]
+pub fn part2(input: &HashMap<String, Monkey>) -> i64 {
+    if let Some(value) = solve_for_human::<i64>(input) {
+        return value;
+    }
+
+    // Something overflowed, but the answer itself might still fit.
+    i64::try_from(part2_big(input)).expect("the answer doesn't fit in an i64; use part2_big")
+}
+
+/**
+ * The same as part2(), but always using big integers.
+ */
+pub fn part2_big(input: &HashMap<String, Monkey>) -> BigInt {
+    solve_for_human(input).expect("division by zero")
+}
+
+fn solve_for_human<T: Value>(input: &HashMap<String, Monkey>) -> Option<T> {
+    let mut monkeys = input.clone();
+
+    // Find a path from "root" to "humn".
+    let path = path_to_human(&monkeys);
+
+    // The next step will be to walk that path, inverting each operation as we go.
+    // We know the "target" value of the current node; by computing the value of
+    // the subtree not including "humn", we can figure out the target value for
+    // the subtree that *does* include "humn", then repeat.
+
+    // Replace the operation of the "root" monkey with a subtraction.
+    // This lets us use the same logic here throughout the path-inverting loop.
+    // Since A == B  <==>  A - B == 0, our initial target value will be 0.
+    let root_name = "root".to_string();
+    if let Monkey::Computation(lhs, rhs, _) = &monkeys[&root_name] {
+        monkeys.insert(
+            root_name,
+            Monkey::Computation(lhs.to_owned(), rhs.to_owned(), Operation::Subtract),
+        );
+    } else {
+        panic!("root node cannot be a value");
+    }
+
+    let mut target = T::from_i64(0);
+    let mut values = HashMap::new();
+
+    // Now we can walk over the path.
+    for i in 0..path.len() - 1 {
+        let Monkey::Computation(lhs, rhs, operation) = monkeys[&path[i]].to_owned() else {
+            panic!("unexpected value at {}: {:?}", path[i], &monkeys[&path[i]])
+        };
+
+        // Since division and subtraction are not commutative, we need to handle
+        // the case where "humn" is in the left subtree differently from when it
+        // is in the right subtree.
+        let (inverse, lhs, rhs) = if lhs == path[i + 1] {
+            let rhs_value = evaluate(&rhs, &monkeys, &mut values)?;
+
+            match operation {
+                Operation::Add => (Operation::Subtract, target, rhs_value), // target = path[i+1] + rhs
+                Operation::Subtract => (Operation::Add, target, rhs_value), // target = path[i+1] - rhs
+                Operation::Multiply => (Operation::Divide, target, rhs_value), // target = path[i+1] * rhs
+                Operation::Divide => (Operation::Multiply, target, rhs_value), // target = path[i+1] / rhs
+            }
+        } else {
+            let lhs_value = evaluate(&lhs, &monkeys, &mut values)?;
+
+            match operation {
+                Operation::Add => (Operation::Subtract, target, lhs_value), // target = lhs + path[i+1]
+                Operation::Subtract => (Operation::Subtract, lhs_value, target), // target = lhs - path[i+1]
+                Operation::Multiply => (Operation::Divide, target, lhs_value), // target = lhs * path[i+1]
+                Operation::Divide => (Operation::Divide, lhs_value, target), // target = lhs / path[i+1]
+            }
+        };
+        target = T::apply(&inverse, &lhs, &rhs)?;
+    }
+
+    // Once we get to "humn", we know what value to shout.
+    Some(target)
+}
+
+/**
+ * The root's value as a function of what "humn" shouts.
+ *
+ * Only the monkeys on the path from "root" to "humn" depend on that, so
+ * everything else is evaluated once up front, and each call to eval()
+ * just recomputes the path.
+ */
+pub struct HumanSpine {
+    // The monkeys on the path from "root" down to (not including) "humn":
+    // each one's operation, the value of its other operand, and whether
+    // the path continues through its left operand.
+    steps: Vec<(Operation, i64, bool)>,
+}
+
+impl HumanSpine {
+    pub fn new(monkeys: &HashMap<String, Monkey>) -> Self {
+        let path = path_to_human(monkeys);
+        let mut values = HashMap::new();
+
+        let steps = path
+            .windows(2)
+            .map(|pair| {
+                let Monkey::Computation(lhs, rhs, operation) = &monkeys[&pair[0]] else {
+                    panic!("unexpected value at {}", pair[0]);
+                };
+                let human_on_left = *lhs == pair[1];
+                let other = if human_on_left { rhs } else { lhs };
+                let value = evaluate::<i64>(other, monkeys, &mut values)
+                    .expect("the monkeys off the path overflow an i64");
+                (operation.clone(), value, human_on_left)
+            })
+            .collect();
+
+        HumanSpine { steps }
+    }
+
+    /**
+     * The root's value if "humn" shouts `humn`, or None on overflow.
+     */
+    pub fn eval(&self, humn: i64) -> Option<i64> {
+        self.eval_with_root(humn, None)
+    }
+
+    /**
+     * Evaluates from "humn" back up to the root, optionally replacing the
+     * root's operation.
+     */
+    fn eval_with_root(&self, humn: i64, root: Option<&Operation>) -> Option<i64> {
+        let mut value = humn;
+        for (i, (operation, other, human_on_left)) in self.steps.iter().enumerate().rev() {
+            let operation = if i == 0 {
+                root.unwrap_or(operation)
+            } else {
+                operation
+            };
+            value = if *human_on_left {
+                i64::apply(operation, &value, other)?
+            } else {
+                i64::apply(operation, other, &value)?
+            };
+        }
+        Some(value)
+    }
+}
+
+/**
+ * The root's value if "humn" shouts `humn`. To try lots of values, build a
+ * HumanSpine once and call eval() on it instead.
+ */
+pub fn eval_with_humn(monkeys: &HashMap<String, Monkey>, humn: i64) -> i64 {
+    HumanSpine::new(monkeys)
+        .eval(humn)
+        .expect("evaluation overflowed an i64")
+}
+
+/*
+ * Part 2 again, but without any algebra: the difference between the root's
+ * two operands only ever moves in one direction as "humn" increases (every
+ * operation is monotonic in each operand), so we can binary search for
+ * where it hits zero.
+ *
+ * Integer division means more than one value might work. This finds the
+ * smallest one.
+ */
+#[aoc(day21, part2, BinarySearch)]
+pub fn part2_binary_search(input: &HashMap<String, Monkey>) -> i64 {
+    let spine = HumanSpine::new(input);
+    let difference = |humn: i64| spine.eval_with_root(humn, Some(&Operation::Subtract));
+
+    let at_zero = difference(0).expect("evaluation overflowed an i64");
+    if at_zero == 0 {
+        return 0;
+    }
+
+    // Look further and further out in both directions until the sign flips.
+    let mut bound: i64 = 1;
+    let (mut low, mut high) = loop {
+        let flipped = |humn| difference(humn).is_some_and(|d| d.signum() != at_zero.signum());
+        if flipped(bound) {
+            break (0, bound);
+        }
+        if flipped(-bound) {
+            break (-bound, 0);
+        }
+        bound = bound.checked_mul(2).expect("no value of humn works");
+    };
+
+    // Find the first value where the difference isn't on the same side of
+    // zero as it is at `low`.
+    let low_sign = difference(low).unwrap().signum();
+    while low + 1 < high {
+        let middle = low + (high - low) / 2;
+        if difference(middle).unwrap().signum() == low_sign {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+
+    assert_eq!(difference(high), Some(0), "no value of humn works");
+    high
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "root: pppw + sjmn\n\
+                           dbpl: 5\n\
+                           cczh: sllz + lgvd\n\
+                           zczc: 2\n\
+                           ptdq: humn - dvpt\n\
+                           dvpt: 3\n\
+                           lfqf: 4\n\
+                           humn: 5\n\
+                           ljgn: 2\n\
+                           sjmn: drzm * dbpl\n\
+                           sllz: 4\n\
+                           pppw: cczh / lfqf\n\
+                           lgvd: ljgn * ptdq\n\
+                           drzm: hmdt - zczc\n\
+                           hmdt: 32";
+
+    #[test]
+    fn test_part1() {
+        let input = generator(EXAMPLE);
+        assert_eq!(part1(&input), 152);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = generator(EXAMPLE);
+        assert_eq!(part2(&input), 301);
+    }
+
+    #[test]
+    fn test_human_spine() {
+        let input = generator(EXAMPLE);
+        assert_eq!(eval_with_humn(&input, 5), part1(&input));
+
+        let spine = HumanSpine::new(&input);
+        // pppw depends on humn; sjmn is always 150.
+        assert_eq!(spine.eval(301), Some(150 + 150));
+        assert_eq!(spine.eval(305), Some(152 + 150));
+
+        assert_eq!(part2_binary_search(&input), part2(&input));
+    }
+
+    #[test]
+    fn test_overflow() {
+        // aaaa * bbbb is 10^24, which doesn't fit in an i64, even though
+        // the final answers do.
+        let input = generator(
+            "root: humn + rght\n\
+             rght: prod / dddd\n\
+             prod: aaaa * bbbb\n\
+             aaaa: 1000000000000\n\
+             bbbb: 1000000000000\n\
+             dddd: 10000000000000\n\
+             humn: 5",
+        );
+        assert_eq!(evaluate::<i64>("root", &input, &mut HashMap::new()), None);
+        assert_eq!(part1(&input), 100000000005);
+        assert_eq!(part2(&input), 100000000000);
+        assert_eq!(part1_big(&input), BigInt::from(100000000005i64));
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = to_dot(&generator(EXAMPLE));
+        assert!(dot.starts_with("digraph monkeys {\n"));
+        assert!(dot.contains("    root [label=\"root\\n+\", color=red, fontcolor=red];\n"));
+        assert!(dot.contains("    root -> pppw [color=red];\n"));
+        assert!(dot.contains("    root -> sjmn;\n"));
+        assert!(dot.contains("    hmdt [label=\"hmdt\\n32\"];\n"));
+        assert_eq!(dot.matches(" -> ").count(), 14);
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+#[cfg(feature = "nom")]
 use nom::{
     character::complete::{one_of, u32},
     combinator::{map, opt},
@@ -8,21 +9,32 @@ use nom::{
     IResult,
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::utils::input::normalize;
+
+mod connectivity;
+mod cube;
+mod jumps;
+
+pub use connectivity::MapStats;
+use cube::CubeNet;
+use jumps::JumpTable;
+
 /*
  * Day 22 asks us to move around a strangely-shaped map filled with
  * obstacles. In part 1, we wrap when we go off an edge; in part 2,
  * it turns out we're actually moving on a cube, so we have to handle
  * the edge transitions very differently.
  *
- * I am pretty happy with my solution for part 1, and extremely unhappy
- * with my solution for part 2, which relies on hardcoding the edge
- * transitions for my specific input shape.
- *
- * So, I've put much less effort into cleaning up and commenting the code
- * for this day's puzzle.
+ * Part 2 originally hardcoded the edge transitions for my input's net,
+ * which was about as pleasant as it sounds (and meant the example didn't
+ * work at all). Now the net gets folded into a cube instead; see cube.rs.
  */
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Direction {
     Right,
     Down,
@@ -41,25 +53,45 @@ impl Direction {
             _ => panic!("invalid direction"),
         }
     }
+
+    /**
+     * The direction's position in the puzzle's ordering (which is also
+     * its value in the password).
+     */
+    fn index(&self) -> usize {
+        match self {
+            Self::Right => 0,
+            Self::Down => 1,
+            Self::Left => 2,
+            Self::Up => 3,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Cell {
     Open,
     Solid,
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MapType {
     Torus,
     Cube,
 }
 
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Map {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_pairs"))]
     points: HashMap<(usize, usize), Cell>,
     row_bounds: Vec<(usize, usize)>,
     col_bounds: Vec<(usize, usize)>,
+    jumps: JumpTable,
+    // None if the map isn't the net of a cube.
+    cube: Option<CubeNet>,
 
     structure: MapType,
 }
@@ -71,16 +103,34 @@ impl Map {
         start_col: usize,
         count: usize,
         initial_direction: &Direction,
+    ) -> (usize, usize, Direction) {
+        match self.structure {
+            MapType::Torus => {
+                let (row, col) = self
+                    .jumps
+                    .walk(start_row, start_col, count, initial_direction);
+                (row, col, initial_direction.to_owned())
+            }
+            MapType::Cube => self.walk_stepping(start_row, start_col, count, initial_direction),
+        }
+    }
+
+    /**
+     * Walks one cell at a time, for maps where the jump table doesn't work.
+     */
+    fn walk_stepping(
+        &self,
+        start_row: usize,
+        start_col: usize,
+        count: usize,
+        initial_direction: &Direction,
     ) -> (usize, usize, Direction) {
         let (mut row, mut col) = (start_row, start_col);
         let mut direction = initial_direction.to_owned();
 
         for _ in 0..count {
             // Figure which cell is the next in that direction (accounting for wrapping).
-            let (next_row, next_col, next_direction) = match self.structure {
-                MapType::Torus => self.neighbor_torus(row, col, &direction),
-                MapType::Cube => self.neighbor_cube(row, col, &direction),
-            };
+            let (next_row, next_col, next_direction) = self.neighbor(row, col, &direction);
 
             // If that cell is blocked, we won't be able to move any further in that direction.
             // So return early.
@@ -95,6 +145,17 @@ impl Map {
         (row, col, direction)
     }
 
+    /**
+     * Returns the neighbor of a given cell in a given direction, however
+     * this map wraps at the edges.
+     */
+    fn neighbor(&self, row: usize, col: usize, direction: &Direction) -> (usize, usize, Direction) {
+        match self.structure {
+            MapType::Torus => self.neighbor_torus(row, col, direction),
+            MapType::Cube => self.neighbor_cube(row, col, direction),
+        }
+    }
+
     /**
      * Returns the neighbor of a given cell in a given direction,
      * wrapping when we get to the edges (i.e., part 1).
@@ -139,27 +200,8 @@ impl Map {
     }
 
     /**
-     * My input is laid out like this:
-     *               (0, 50)--F---(0, 100)--G---(0,150)
-     *                  |            |             |
-     *                  A            |             D
-     *                  |            |             |
-     *              (50, 50)------(50, 100)--C--(50,150)
-     *                  |            |
-     *                  B            C
-     *                  |            |
-     * (100, 0)--B-(100, 50)----(100, 100)
-     *    |             |            |
-     *    A             |            D
-     *    |             |            |
-     * (150, 0)----(150, 50)--E-(150, 100)
-     *    |             |
-     *    F             E
-     *    |             |
-     * (200, 0)-G--(200, 50)
-     *
-     * There is a *lot* of casework to handle moving across the edges.
-     * It is probably the worst thing I have ever written.
+     * Returns the neighbor of a given cell in a given direction, folding
+     * the map up into a cube (i.e., part 2).
      */
     fn neighbor_cube(
         &self,
@@ -167,81 +209,53 @@ impl Map {
         col: usize,
         direction: &Direction,
     ) -> (usize, usize, Direction) {
-        match direction {
-            Direction::Right => {
-                if row < 50 && col == 149 {
-                    (149 - row, 99, Direction::Left)
-                } else if (50..100).contains(&row) && col == 99 {
-                    (49, 100 + (row - 50), Direction::Up)
-                } else if (100..150).contains(&row) && col == 99 {
-                    (49 - (row - 100), 149, Direction::Left)
-                } else if 150 <= row && col == 49 {
-                    (149, 50 + (row - 150), Direction::Up)
-                } else {
-                    (row, col + 1, direction.clone())
-                }
-            }
-            Direction::Down => {
-                if row == 199 && col < 50 {
-                    (0, col + 100, Direction::Down)
-                } else if row == 149 && (50..100).contains(&col) {
-                    (150 + (col - 50), 49, Direction::Left)
-                } else if row == 49 && (100..150).contains(&col) {
-                    (50 + (col - 100), 99, Direction::Left)
-                } else {
-                    (row + 1, col, direction.clone())
-                }
-            }
-            Direction::Left => {
-                if row < 50 && col == 50 {
-                    (149 - row, 0, Direction::Right)
-                } else if (50..100).contains(&row) && col == 50 {
-                    (100, row - 50, Direction::Down)
-                } else if (100..150).contains(&row) && col == 0 {
-                    (49 - (row - 100), 50, Direction::Right)
-                } else if 150 <= row && col == 0 {
-                    (0, 50 + (row - 150), Direction::Down)
-                } else {
-                    (row, col - 1, direction.clone())
-                }
-            }
-            Direction::Up => {
-                if row == 100 && col < 50 {
-                    (50 + col, 50, Direction::Right)
-                } else if row == 0 && (50..100).contains(&col) {
-                    (150 + (col - 50), 0, Direction::Right)
-                } else if row == 0 && (100..150).contains(&col) {
-                    (199, col - 100, Direction::Up)
-                } else {
-                    (row - 1, col, direction.clone())
-                }
-            }
-        }
+        self.cube
+            .as_ref()
+            .expect("the map doesn't fold into a cube")
+            .neighbor(row, col, direction)
     }
 }
 
-type Path = Vec<(usize, Direction)>;
+/**
+ * Each step of the path: walk that far, then turn (Up means don't turn,
+ * which is what the last step does).
+ */
+pub type Path = Vec<(usize, Direction)>;
 
-struct You {
-    row: usize,
-    col: usize,
-    facing: Direction,
+/**
+ * Where you are on the map (0-based, unlike the password), and which way
+ * you're facing.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pose {
+    pub row: usize,
+    pub col: usize,
+    pub facing: Direction,
 }
 
-impl You {
-    fn password(&self) -> usize {
-        let facing_value = match self.facing {
-            Direction::Right => 0,
-            Direction::Down => 1,
-            Direction::Left => 2,
-            Direction::Up => 3,
-        };
+impl Pose {
+    /**
+     * The puzzle's starting point: the leftmost open cell of the top row,
+     * facing right.
+     */
+    pub fn start(map: &Map) -> Self {
+        let (first, last) = map.row_bounds[0];
+        let col = (first..=last)
+            .find(|col| matches!(map.points[&(0, *col)], Cell::Open))
+            .expect("no open cell in the top row");
+        Pose {
+            row: 0,
+            col,
+            facing: Direction::Right,
+        }
+    }
 
-        1000 * (1 + self.row) + 4 * (1 + self.col) + facing_value
+    pub fn password(&self) -> usize {
+        1000 * (1 + self.row) + 4 * (1 + self.col) + self.facing.index()
     }
 }
 
-impl You {
+impl Pose {
     fn turn(&mut self, direction: &Direction) {
         match *direction {
             Direction::Right => {
@@ -293,7 +307,7 @@ fn parse_map(input: &str) -> Map {
     }
 
     // Figure out the points at which each column wraps.
-    let col_bounds = (0..=num_cols)
+    let col_bounds: Vec<(usize, usize)> = (0..=num_cols)
         .map(|col| {
             points
                 .keys()
@@ -304,14 +318,20 @@ fn parse_map(input: &str) -> Map {
         })
         .collect();
 
+    let jumps = JumpTable::new(&points, &row_bounds, &col_bounds);
+    let cube = CubeNet::fold(&points);
+
     Map {
         points,
         row_bounds,
         col_bounds,
+        jumps,
+        cube,
         structure: MapType::Torus,
     }
 }
 
+#[cfg(feature = "nom")]
 fn parse_path(input: &str) -> Path {
     let parsed: IResult<&str, Vec<(usize, Direction)>> = many1(map(
         tuple((u32, opt(one_of("RDLU")))),
@@ -325,27 +345,61 @@ fn parse_path(input: &str) -> Path {
     parsed.expect("error parsing path").1
 }
 
+/*
+ * The same as above without nom: each step is a count, then a turn (or
+ * nothing, at the end of the path).
+ */
+#[cfg(not(feature = "nom"))]
+fn parse_path(input: &str) -> Path {
+    let mut path = Vec::new();
+    let mut rest = input;
+    loop {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if digits == 0 {
+            break;
+        }
+        let count: u32 = rest[..digits].parse().expect("error parsing path");
+        rest = &rest[digits..];
+
+        let direction = match rest.chars().next() {
+            Some(turn @ ('R' | 'D' | 'L' | 'U')) => {
+                rest = &rest[1..];
+                Direction::from(turn)
+            }
+            _ => Direction::Up,
+        };
+        path.push((count as usize, direction));
+    }
+
+    assert!(!path.is_empty(), "error parsing path");
+    path
+}
+
 #[aoc_generator(day22)]
-fn generator(input: &str) -> (Map, Path) {
+pub fn generator(input: &str) -> (Map, Path) {
+    let input = normalize(input);
     let (map_str, path_str) = input.split_once("\n\n").unwrap().to_owned();
 
     (parse_map(map_str), parse_path(path_str))
 }
 
-#[aoc(day22, part1)]
-pub fn part1((map, path): &(Map, Path)) -> usize {
-    let mut you = You {
-        row: 0,
-        col: map.row_bounds[0].0,
-        facing: Direction::Right,
-    };
-
+/**
+ * Follows the whole path from `start`, returning where you end up.
+ */
+pub fn walk_path(map: &Map, path: &Path, start: Pose) -> Pose {
+    let mut you = start;
     for (count, direction) in path {
         (you.row, you.col, you.facing) = map.walk(you.row, you.col, *count, &you.facing);
         you.turn(direction);
     }
+    you
+}
 
-    you.password()
+#[aoc(day22, part1)]
+pub fn part1((map, path): &(Map, Path)) -> usize {
+    walk_path(map, path, Pose::start(map)).password()
 }
 
 #[aoc(day22, part2)]
@@ -358,18 +412,28 @@ pub fn part2((initial_map, path): &(Map, Path)) -> usize {
 
 #[cfg(test)]
 mod tests {
-    use std::fs;
-
-    use super::{generator, part1};
+    use super::*;
+    use crate::utils::testing::read_example;
 
     #[test]
-    fn test_part1() {
-        let input = fs::read_to_string("input/2022/test/day22.txt").expect("missing input");
-        let parsed = generator(&input);
-        assert_eq!(part1(&parsed), 6032);
+    fn test_walk_path() {
+        let Some(input) = read_example("day22.txt") else {
+            return;
+        };
+        let (map, _) = generator(&input);
+        let start = Pose::start(&map);
+        assert_eq!((start.row, start.col), (0, 8));
+
+        // The top row is "...#": walking right stops at the wall...
+        let right = walk_path(&map, &vec![(10, Direction::Up)], start.clone());
+        assert_eq!(right.password(), 1044);
+
+        // ...and walking left wraps around into it, so doesn't go anywhere.
+        let facing_left = Pose {
+            facing: Direction::Left,
+            ..start
+        };
+        let left = walk_path(&map, &vec![(1, Direction::Up)], facing_left.clone());
+        assert_eq!(left, facing_left);
     }
-
-    // Because the cube edge transitions are hardcoded for 50x50 faces
-    // with my input's format, they don't work at all for the example.
-    // So, no test for part two.
 }
@@ -0,0 +1,146 @@
+use crate::utils::hash::FastSet;
+use crate::utils::search::bfs_reach;
+
+use super::jumps::JumpTable;
+use super::{Cell, Direction, Map, Pose};
+
+/*
+ * Nothing in the puzzle promises that every open cell can be reached: a
+ * cell walled in on all four sides (counting the wrap-around) can never be
+ * walked into, whatever the path says. These are handy for checking odd
+ * inputs, and for throwing such cells away.
+ *
+ * Reachability depends on how the map wraps, so it's worked out for the
+ * map's current structure (the torus, as parsed).
+ */
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MapStats {
+    pub open: usize,
+    pub walls: usize,
+    // Open cells that can be reached from the start, including it.
+    pub reachable: usize,
+    // Each face's top left cell and the fraction of its cells that are
+    // walls, in reading order. Empty if the map doesn't fold into a cube.
+    pub face_walls: Vec<((usize, usize), f64)>,
+}
+
+impl Map {
+    /**
+     * Every open cell that can be walked to from `from`, including itself.
+     */
+    pub fn reachable(&self, from: &Pose) -> FastSet<(usize, usize)> {
+        let directions = [
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+            Direction::Up,
+        ];
+        let (reachable, _) = bfs_reach(
+            (from.row, from.col),
+            |&(row, col)| {
+                directions
+                    .iter()
+                    .map(|direction| self.neighbor(row, col, direction))
+                    .filter(|(row, col, _)| matches!(self.points[&(*row, *col)], Cell::Open))
+                    .map(|(row, col, _)| (row, col))
+                    .collect::<Vec<_>>()
+            },
+            |_| false,
+        );
+        reachable
+    }
+
+    /**
+     * Counts of open cells, walls and reachable cells, plus how walled-in
+     * each face of the cube is.
+     */
+    pub fn stats(&self) -> MapStats {
+        let is_solid = |point: &(usize, usize)| matches!(self.points[point], Cell::Solid);
+        let walls = self.points.keys().filter(|point| is_solid(point)).count();
+
+        let face_walls = match &self.cube {
+            Some(cube) => {
+                let size = cube.size();
+                cube.faces()
+                    .into_iter()
+                    .map(|(top, left)| {
+                        let walls = (top..top + size)
+                            .flat_map(|row| (left..left + size).map(move |col| (row, col)))
+                            .filter(is_solid)
+                            .count();
+                        ((top, left), walls as f64 / (size * size) as f64)
+                    })
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+
+        MapStats {
+            open: self.points.len() - walls,
+            walls,
+            reachable: self.reachable(&Pose::start(self)).len(),
+            face_walls,
+        }
+    }
+
+    /**
+     * Turns every open cell that can't be reached from the start into a
+     * wall, returning how many there were. Since nothing could walk into
+     * them anyway, this doesn't change where any path ends up.
+     */
+    pub fn prune_unreachable(&mut self) -> usize {
+        let reachable = self.reachable(&Pose::start(self));
+        let mut pruned = 0;
+        for (point, cell) in self.points.iter_mut() {
+            if matches!(cell, Cell::Open) && !reachable.contains(point) {
+                *cell = Cell::Solid;
+                pruned += 1;
+            }
+        }
+
+        if pruned > 0 {
+            self.jumps = JumpTable::new(&self.points, &self.row_bounds, &self.col_bounds);
+        }
+        pruned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{parse_map, part1, Path};
+    use super::*;
+    use crate::utils::testing::read_example;
+
+    #[test]
+    fn test_example_stats() {
+        let Some(input) = read_example("day22.txt") else {
+            return;
+        };
+        let (map_str, _) = input.split_once("\n\n").unwrap();
+        let map = parse_map(map_str);
+
+        let stats = map.stats();
+        assert_eq!((stats.open, stats.walls, stats.reachable), (83, 13, 83));
+        assert_eq!(stats.face_walls.len(), 6);
+        assert_eq!(stats.face_walls[0].0, (0, 8));
+        let face_walls: f64 = stats.face_walls.iter().map(|(_, walls)| walls * 16.0).sum();
+        assert_eq!(face_walls.round() as usize, stats.walls);
+    }
+
+    #[test]
+    fn test_prune_unreachable() {
+        // The open cell in the third row is walled in, even around the wraps.
+        let mut map = parse_map("..##\n####\n#.##\n####");
+        let stats = map.stats();
+        assert_eq!((stats.open, stats.walls, stats.reachable), (3, 13, 2));
+        assert!(stats.face_walls.is_empty());
+
+        let path: Path = vec![(5, Direction::Right), (5, Direction::Up)];
+        let before = part1(&(map.clone(), path.clone()));
+        assert_eq!(map.prune_unreachable(), 1);
+        assert_eq!(map.stats().open, 2);
+        assert_eq!(part1(&(map.clone(), path)), before);
+        assert_eq!(map.prune_unreachable(), 0);
+    }
+}
@@ -0,0 +1,297 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{Cell, Direction};
+
+/*
+ * Folds the map's net into a cube, so that part 2 can work out where each
+ * edge leads without hardcoding the shape of the net.
+ *
+ * Each face gets three unit vectors in 3D: which way "right" and "down" on
+ * the map point once the face is folded into place, and its outward normal.
+ * The first face lies flat (right = x, down = y, normal = z), and the rest
+ * follow by walking across the net: folding the face to the right of this
+ * one down around their shared edge turns its right into our -normal and
+ * its normal into our right, and similarly for the other three directions.
+ *
+ * Walking off the edge of a face then means:
+ *   - finding the cell's position in 3D, with the cube centered on the
+ *     origin (in units of half a cell, so that cell centers are integers);
+ *   - stepping over the edge, which moves one half-cell in the direction
+ *     we were walking and one half-cell into the cube;
+ *   - finding the face whose normal is the direction we were walking, and
+ *     reading the map coordinates back off its right and down vectors.
+ * On the new face we're moving in the direction of the old face's -normal.
+ */
+
+type Vector = [i32; 3];
+
+fn neg(v: Vector) -> Vector {
+    [-v[0], -v[1], -v[2]]
+}
+
+fn dot(a: Vector, b: Vector) -> i32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Face {
+    // The map coordinates of the face's top left cell.
+    row: usize,
+    col: usize,
+    right: Vector,
+    down: Vector,
+    normal: Vector,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CubeNet {
+    size: usize,
+    faces: Vec<Face>,
+}
+
+impl CubeNet {
+    /**
+     * Folds the map, or returns None if it isn't the net of a cube.
+     */
+    pub fn fold(points: &HashMap<(usize, usize), Cell>) -> Option<Self> {
+        let size = (1..)
+            .take_while(|size| 6 * size * size <= points.len())
+            .last()?;
+        if 6 * size * size != points.len() {
+            return None;
+        }
+
+        // Which size x size blocks of the map are faces. With the right
+        // number of cells, having six blocks means they're all full.
+        let mut blocks: Vec<(usize, usize)> = points
+            .keys()
+            .map(|(row, col)| (row / size, col / size))
+            .collect();
+        blocks.sort();
+        blocks.dedup();
+        if blocks.len() != 6 {
+            return None;
+        }
+
+        let mut frames: HashMap<(usize, usize), (Vector, Vector, Vector)> = HashMap::new();
+        let mut stack = vec![(blocks[0], ([1, 0, 0], [0, 1, 0], [0, 0, 1]))];
+        while let Some((block, frame)) = stack.pop() {
+            if frames.contains_key(&block) || !blocks.contains(&block) {
+                continue;
+            }
+            frames.insert(block, frame);
+
+            let (right, down, normal) = frame;
+            let (row, col) = block;
+            stack.push(((row, col + 1), (neg(normal), down, right)));
+            stack.push(((row + 1, col), (right, neg(normal), down)));
+            if col > 0 {
+                stack.push(((row, col - 1), (normal, down, neg(right))));
+            }
+            if row > 0 {
+                stack.push(((row - 1, col), (right, normal, neg(down))));
+            }
+        }
+
+        let faces: Vec<Face> = frames
+            .into_iter()
+            .map(|((row, col), (right, down, normal))| Face {
+                row: row * size,
+                col: col * size,
+                right,
+                down,
+                normal,
+            })
+            .collect();
+
+        // The blocks have to be connected, and fold up without overlapping.
+        let mut normals: Vec<Vector> = faces.iter().map(|face| face.normal).collect();
+        normals.sort();
+        normals.dedup();
+        if normals.len() != 6 {
+            return None;
+        }
+
+        Some(CubeNet { size, faces })
+    }
+
+    /**
+     * The length of each face's edges.
+     */
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /**
+     * The map coordinates of each face's top left cell, in reading order.
+     */
+    pub fn faces(&self) -> Vec<(usize, usize)> {
+        let mut corners: Vec<_> = self.faces.iter().map(|face| (face.row, face.col)).collect();
+        corners.sort();
+        corners
+    }
+
+    fn face_at(&self, row: usize, col: usize) -> &Face {
+        let (row, col) = (row / self.size * self.size, col / self.size * self.size);
+        self.faces
+            .iter()
+            .find(|face| (face.row, face.col) == (row, col))
+            .expect("not on the cube")
+    }
+
+    /**
+     * The cell next to (row, col) in the given direction, and which way
+     * we're facing once we get there.
+     */
+    pub fn neighbor(
+        &self,
+        row: usize,
+        col: usize,
+        direction: &Direction,
+    ) -> (usize, usize, Direction) {
+        let face = self.face_at(row, col);
+        let (i, j) = ((row - face.row) as i32, (col - face.col) as i32);
+        let (di, dj, heading) = match direction {
+            Direction::Right => (0, 1, face.right),
+            Direction::Down => (1, 0, face.down),
+            Direction::Left => (0, -1, neg(face.right)),
+            Direction::Up => (-1, 0, neg(face.down)),
+        };
+
+        let size = self.size as i32;
+        if (0..size).contains(&(i + di)) && (0..size).contains(&(j + dj)) {
+            return (
+                (row as i32 + di) as usize,
+                (col as i32 + dj) as usize,
+                direction.clone(),
+            );
+        }
+
+        // Over the edge: find where we are in 3D, and step around the corner.
+        let position: Vector = std::array::from_fn(|axis| {
+            face.normal[axis] * size
+                + face.right[axis] * (2 * j + 1 - size)
+                + face.down[axis] * (2 * i + 1 - size)
+                + heading[axis]
+                - face.normal[axis]
+        });
+
+        let next = self
+            .faces
+            .iter()
+            .find(|next| next.normal == heading)
+            .expect("every direction has a face");
+        let next_i = (dot(position, next.down) + size - 1) / 2;
+        let next_j = (dot(position, next.right) + size - 1) / 2;
+
+        let facing = neg(face.normal);
+        let next_direction = if facing == next.right {
+            Direction::Right
+        } else if facing == next.down {
+            Direction::Down
+        } else if facing == neg(next.right) {
+            Direction::Left
+        } else {
+            Direction::Up
+        };
+
+        (
+            next.row + next_i as usize,
+            next.col + next_j as usize,
+            next_direction,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * An all-open map, with a size x size face for each 'x' in `layout`.
+     */
+    fn net(layout: &[&str], size: usize) -> HashMap<(usize, usize), Cell> {
+        let mut points = HashMap::new();
+        for (block_row, line) in layout.iter().enumerate() {
+            for (block_col, _) in line.char_indices().filter(|(_, c)| *c == 'x') {
+                for row in block_row * size..(block_row + 1) * size {
+                    for col in block_col * size..(block_col + 1) * size {
+                        points.insert((row, col), Cell::Open);
+                    }
+                }
+            }
+        }
+        points
+    }
+
+    fn reversed(direction: &Direction) -> Direction {
+        match direction {
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Up => Direction::Down,
+        }
+    }
+
+    /**
+     * From every cell, in every direction: stepping and then turning
+     * around leads back, and going straight ahead all the way around the
+     * cube ends up where we started.
+     */
+    fn check_folding(layout: &[&str]) {
+        let size = 3;
+        let points = net(layout, size);
+        let cube = CubeNet::fold(&points).expect("should fold into a cube");
+
+        for &(row, col) in points.keys() {
+            for direction in [
+                Direction::Right,
+                Direction::Down,
+                Direction::Left,
+                Direction::Up,
+            ] {
+                let (next_row, next_col, next_direction) = cube.neighbor(row, col, &direction);
+                assert!(points.contains_key(&(next_row, next_col)));
+                assert_eq!(
+                    cube.neighbor(next_row, next_col, &reversed(&next_direction)),
+                    (row, col, reversed(&direction)),
+                    "stepping {direction:?} from {:?} and back",
+                    (row, col)
+                );
+
+                let mut position = (row, col, direction.clone());
+                for _ in 0..4 * size {
+                    position = cube.neighbor(position.0, position.1, &position.2);
+                }
+                assert_eq!(position, (row, col, direction));
+            }
+        }
+    }
+
+    #[test]
+    fn test_nets() {
+        // The example's layout.
+        check_folding(&["  x", "xxx", "  xx"]);
+        // A cross.
+        check_folding(&[" x", "xxxx", " x"]);
+        // A T.
+        check_folding(&["xxx", " x", " x", " x"]);
+        // My input's layout.
+        check_folding(&[" xx", " x", "xx", "x"]);
+    }
+
+    #[test]
+    fn test_not_nets() {
+        // Six faces, but two of them overlap when folded.
+        assert!(CubeNet::fold(&net(&["xxx", "xxx"], 2)).is_none());
+        // Six faces, but not all connected.
+        assert!(CubeNet::fold(&net(&["xxxxx", "", "x"], 2)).is_none());
+        // Not six faces' worth of cells.
+        assert!(CubeNet::fold(&net(&["xxxxx"], 2)).is_none());
+    }
+}
@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{Cell, Direction};
+
+/*
+ * On the torus (part 1), every row and column is a loop, so the only thing
+ * that can stop a run of steps is a wall. If we know how far it is from
+ * each cell to the next wall in each direction, a whole run resolves in
+ * one lookup instead of one step at a time:
+ *
+ *   - walk min(count, steps before the wall) steps, then
+ *   - wrap that back into the row or column with a modulus.
+ *
+ * This doesn't help on the cube (part 2), where walking off an edge can
+ * change direction, so that still steps one cell at a time.
+ */
+
+/**
+ * The number of steps that can be taken from each open cell in each
+ * direction before running into a wall, or None if the row or column
+ * has no walls at all. Stored densely, one entry per cell of the map's
+ * bounding box, and indexed by Direction::index() within each entry.
+ */
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct JumpTable {
+    free_steps: Vec<[Option<u32>; 4]>,
+    width: usize,
+    row_bounds: Vec<(usize, usize)>,
+    col_bounds: Vec<(usize, usize)>,
+}
+
+impl JumpTable {
+    pub fn new(
+        points: &HashMap<(usize, usize), Cell>,
+        row_bounds: &[(usize, usize)],
+        col_bounds: &[(usize, usize)],
+    ) -> Self {
+        let width = col_bounds.len();
+        let mut free_steps = vec![[None; 4]; row_bounds.len() * width];
+        // Looking cells up in the map's HashMap is the slow part of building
+        // this, so copy the walls into a grid with the same layout first.
+        let mut walls = vec![false; free_steps.len()];
+        for (&(row, col), cell) in points {
+            walls[row * width + col] = matches!(cell, Cell::Solid);
+        }
+        let is_solid = |cell: &usize| walls[*cell];
+
+        for (row, &(start, end)) in row_bounds.iter().enumerate() {
+            let cells: Vec<usize> = (start..=end).map(|col| row * width + col).collect();
+            record_line(
+                &mut free_steps,
+                &cells,
+                &is_solid,
+                Direction::Right,
+                Direction::Left,
+            );
+        }
+
+        for (col, &(start, end)) in col_bounds.iter().enumerate() {
+            // Columns past the edge of every row don't have any cells.
+            if start > end {
+                continue;
+            }
+            let cells: Vec<usize> = (start..=end).map(|row| row * width + col).collect();
+            record_line(
+                &mut free_steps,
+                &cells,
+                &is_solid,
+                Direction::Down,
+                Direction::Up,
+            );
+        }
+
+        JumpTable {
+            free_steps,
+            width,
+            row_bounds: row_bounds.to_vec(),
+            col_bounds: col_bounds.to_vec(),
+        }
+    }
+
+    /**
+     * Walks up to `count` steps from (row, col), stopping early at a wall,
+     * and returns where we end up.
+     */
+    pub fn walk(
+        &self,
+        row: usize,
+        col: usize,
+        count: usize,
+        direction: &Direction,
+    ) -> (usize, usize) {
+        let free = self.free_steps[row * self.width + col][direction.index()];
+        let steps = free.map_or(count, |free| count.min(free as usize));
+
+        match direction {
+            Direction::Right => (row, wrap(self.row_bounds[row], col, steps, true)),
+            Direction::Left => (row, wrap(self.row_bounds[row], col, steps, false)),
+            Direction::Down => (wrap(self.col_bounds[col], row, steps, true), col),
+            Direction::Up => (wrap(self.col_bounds[col], row, steps, false), col),
+        }
+    }
+}
+
+/**
+ * Records the free steps for every cell in a row or column, in both
+ * directions along it. `cells` holds the cells' indices into the table,
+ * in order of travel for `forward`.
+ */
+fn record_line(
+    free_steps: &mut [[Option<u32>; 4]],
+    cells: &[usize],
+    is_solid: &impl Fn(&usize) -> bool,
+    forward: Direction,
+    backward: Direction,
+) {
+    let solid: Vec<bool> = cells.iter().map(is_solid).collect();
+    let forward_steps = steps_to_wall(&solid);
+
+    let reversed: Vec<bool> = solid.iter().rev().copied().collect();
+    let backward_steps = steps_to_wall(&reversed);
+
+    for (i, cell) in cells.iter().enumerate() {
+        let entry = &mut free_steps[*cell];
+        entry[forward.index()] = forward_steps[i];
+        entry[backward.index()] = backward_steps[cells.len() - 1 - i];
+    }
+}
+
+/**
+ * For each position in a loop of cells, the number of steps forward that
+ * can be taken before the next step would be into a wall.
+ *
+ * Going backwards over two laps of the loop means that by the time we
+ * reach each position in the first lap, we've seen the next wall after it
+ * (if there is one), even if it's only reachable by wrapping around.
+ */
+fn steps_to_wall(solid: &[bool]) -> Vec<Option<u32>> {
+    let len = solid.len();
+    let mut steps = vec![None; len];
+    let mut next_wall = None;
+
+    for i in (0..2 * len).rev() {
+        if i < len {
+            steps[i] = next_wall.map(|wall: usize| (wall - i - 1) as u32);
+        }
+        if solid[i % len] {
+            next_wall = Some(i);
+        }
+    }
+
+    steps
+}
+
+/**
+ * Moves `steps` cells forward or backward from `position` within the
+ * (inclusive) bounds, wrapping around at the ends.
+ */
+fn wrap((start, end): (usize, usize), position: usize, steps: usize, forward: bool) -> usize {
+    let len = end - start + 1;
+    let offset = position - start;
+    let steps = steps % len;
+
+    if forward {
+        start + (offset + steps) % len
+    } else {
+        start + (offset + len - steps) % len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::testing::read_example;
+    use crate::y2022::day22::generator;
+
+    #[test]
+    fn test_steps_to_wall() {
+        assert_eq!(
+            steps_to_wall(&[false, true, false, false]),
+            vec![Some(0), Some(3), Some(2), Some(1)]
+        );
+        assert_eq!(steps_to_wall(&[false, false]), vec![None, None]);
+    }
+
+    #[test]
+    fn test_matches_stepping() {
+        let Some(input) = read_example("day22.txt") else {
+            return;
+        };
+        let (map, _) = generator(&input);
+
+        let directions = [
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+            Direction::Up,
+        ];
+        for (&(row, col), cell) in map.points.iter() {
+            if matches!(cell, Cell::Solid) {
+                continue;
+            }
+            for direction in directions.iter() {
+                for count in 0..30 {
+                    let (step_row, step_col, _) = map.walk_stepping(row, col, count, direction);
+                    assert_eq!(
+                        map.jumps.walk(row, col, count, direction),
+                        (step_row, step_col),
+                        "walking {count} {direction:?} from ({row}, {col})"
+                    );
+                }
+            }
+        }
+    }
+}
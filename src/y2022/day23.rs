@@ -0,0 +1,761 @@
+use std::cmp::{max, min};
+
+use itertools::Itertools;
+
+use crate::utils::hash::{FastMap, FastSet};
+use crate::utils::input::normalize;
+
+#[derive(Clone, Debug)]
+enum Direction {
+    North,
+    South,
+    West,
+    East,
+}
+
+impl Direction {
+    /**
+     * Returns the next direction for an elf to try moving.
+     */
+    fn next(&self) -> Self {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::East,
+            Direction::East => Direction::North,
+        }
+    }
+
+    /**
+     * Returns the point 1 unit in this direction from
+     * a given starting point.
+     */
+    fn of(&self, point: Point) -> Point {
+        match self {
+            Direction::North => (point.0 - 1, point.1),
+            Direction::South => (point.0 + 1, point.1),
+            Direction::West => (point.0, point.1 - 1),
+            Direction::East => (point.0, point.1 + 1),
+        }
+    }
+}
+
+type Point = (i32, i32);
+type Elves = FastSet<Point>;
+
+/*
+ * The operations the simulation needs from a set of elves. The hash set is
+ * fine for the real inputs; ChunkedElves (below) is for inputs where the
+ * elves end up spread over a huge area.
+ */
+trait ElfSet: Clone + Sync {
+    fn contains(&self, point: &Point) -> bool;
+    fn insert(&mut self, point: Point);
+    fn remove(&mut self, point: &Point);
+    fn len(&self) -> usize;
+    fn points(&self) -> Vec<Point>;
+}
+
+impl ElfSet for Elves {
+    fn contains(&self, point: &Point) -> bool {
+        FastSet::contains(self, point)
+    }
+
+    fn insert(&mut self, point: Point) {
+        FastSet::insert(self, point);
+    }
+
+    fn remove(&mut self, point: &Point) {
+        FastSet::remove(self, point);
+    }
+
+    fn len(&self) -> usize {
+        FastSet::len(self)
+    }
+
+    fn points(&self) -> Vec<Point> {
+        self.iter().copied().collect()
+    }
+}
+
+/*
+ * A sparse bitmap of elves: the plane is split into 64x64 tiles, and only
+ * tiles containing at least one elf are stored, each as 64 rows of u64.
+ *
+ * Memory use is proportional to the number of occupied tiles rather than
+ * the size of the bounding box, so elves can wander arbitrarily far apart.
+ */
+const TILE_SHIFT: i32 = 6;
+const TILE_MASK: i32 = (1 << TILE_SHIFT) - 1;
+
+#[derive(Clone, Default)]
+struct ChunkedElves {
+    tiles: FastMap<Point, [u64; 64]>,
+    len: usize,
+}
+
+impl ChunkedElves {
+    /**
+     * Splits a point into (tile coordinates, row within the tile, column within the tile).
+     * The shifts round towards negative infinity, so negative coordinates work too.
+     */
+    fn locate(point: &Point) -> (Point, usize, u32) {
+        let tile = (point.0 >> TILE_SHIFT, point.1 >> TILE_SHIFT);
+        (
+            tile,
+            (point.0 & TILE_MASK) as usize,
+            (point.1 & TILE_MASK) as u32,
+        )
+    }
+}
+
+impl From<&Elves> for ChunkedElves {
+    fn from(elves: &Elves) -> Self {
+        let mut chunked = ChunkedElves::default();
+        for elf in elves {
+            chunked.insert(*elf);
+        }
+        chunked
+    }
+}
+
+impl ElfSet for ChunkedElves {
+    fn contains(&self, point: &Point) -> bool {
+        let (tile, row, col) = Self::locate(point);
+        self.tiles
+            .get(&tile)
+            .is_some_and(|bits| bits[row] & (1 << col) != 0)
+    }
+
+    fn insert(&mut self, point: Point) {
+        let (tile, row, col) = Self::locate(&point);
+        let bits = self.tiles.entry(tile).or_insert([0; 64]);
+        if bits[row] & (1 << col) == 0 {
+            bits[row] |= 1 << col;
+            self.len += 1;
+        }
+    }
+
+    fn remove(&mut self, point: &Point) {
+        let (tile, row, col) = Self::locate(point);
+        let Some(bits) = self.tiles.get_mut(&tile) else {
+            return;
+        };
+
+        if bits[row] & (1 << col) != 0 {
+            bits[row] &= !(1 << col);
+            self.len -= 1;
+
+            // Drop tiles once they're empty, so memory use tracks the elves.
+            if bits.iter().all(|&row| row == 0) {
+                self.tiles.remove(&tile);
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn points(&self) -> Vec<Point> {
+        let mut points = Vec::with_capacity(self.len);
+        for (tile, bits) in self.tiles.iter() {
+            for (row, &row_bits) in bits.iter().enumerate() {
+                let mut remaining = row_bits;
+                while remaining != 0 {
+                    let col = remaining.trailing_zeros() as i32;
+                    remaining &= remaining - 1;
+                    points.push((
+                        (tile.0 << TILE_SHIFT) + row as i32,
+                        (tile.1 << TILE_SHIFT) + col,
+                    ));
+                }
+            }
+        }
+        points
+    }
+}
+
+/*
+ * Inputs whose bounding box is bigger than this many cells get simulated
+ * with ChunkedElves.
+ */
+const CHUNKED_THRESHOLD: i64 = 1 << 20;
+
+fn use_chunked(elves: &Elves) -> bool {
+    let (lower, upper) = bounding_box(elves);
+    let area = (upper.0 as i64 - lower.0 as i64 + 1) * (upper.1 as i64 - lower.1 as i64 + 1);
+    area > CHUNKED_THRESHOLD
+}
+
+#[aoc_generator(day23)]
+pub fn generator(input: &str) -> Elves {
+    let input = normalize(input);
+    let mut elves = Elves::default();
+
+    for (row, line) in input.lines().enumerate() {
+        for (col, c) in line.chars().enumerate() {
+            if c == '#' {
+                elves.insert((row as i32, col as i32));
+            }
+        }
+    }
+
+    elves
+}
+
+/**
+ * Draws the elves as a grid of '#' and '.', one line per row and cropped to
+ * their bounding box, so that the same arrangement always comes out the
+ * same wherever it is on the (infinite) map.
+ */
+pub fn render_elves(elves: &Elves) -> String {
+    render(elves, |point| elves.contains(point).then_some('#'))
+}
+
+/*
+ * Draws the bounding box of `points`, using `cell` for each cell's
+ * character ('.' where it returns None).
+ */
+fn render<'a>(
+    points: impl IntoIterator<Item = &'a Point>,
+    cell: impl Fn(&Point) -> Option<char>,
+) -> String {
+    let (lower_bounds, upper_bounds) = bounding_box(points);
+    let mut out = String::new();
+    for row in lower_bounds.0..=upper_bounds.0 {
+        for col in lower_bounds.1..=upper_bounds.1 {
+            out.push(cell(&(row, col)).unwrap_or('.'));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/**
+ * Returns true iff the given point has any neighboring cells occupied.
+ *
+ * Assumes that the point is itself occupied.
+ */
+fn has_neighbors(point: Point, elves: &impl ElfSet) -> bool {
+    // This would probably be more efficient if I just hardcoded all of the coordinates,
+    // but using cartesian_product() is ~~cool~~.
+    (-1..=1)
+        .cartesian_product(-1..=1)
+        .filter(|(dx, dy)| elves.contains(&(point.0 + dx, point.1 + dy)))
+        .count()
+        > 1
+}
+
+/**
+ * Returns true iff all of the neighboring cells in a given direction are empty.
+ */
+fn empty_in_direction(point: Point, direction: &Direction, elves: &impl ElfSet) -> bool {
+    let deltas_to_check = match direction {
+        Direction::North => [(-1, -1), (-1, 0), (-1, 1)],
+        Direction::South => [(1, -1), (1, 0), (1, 1)],
+        Direction::West => [(-1, -1), (0, -1), (1, -1)],
+        Direction::East => [(-1, 1), (0, 1), (1, 1)],
+    };
+
+    let num_occupied_spots = deltas_to_check
+        .iter()
+        .map(|(dx, dy)| (point.0 + dx, point.1 + dy))
+        .filter(|p| elves.contains(p))
+        .count();
+
+    num_occupied_spots == 0
+}
+
+/**
+ * Gets the proposed movement for an elf at `point`.
+ *
+ * The elf will consider moving `initial_direction` first.
+ * If no movement is possible, or the elf is already happy with his position, returns None.
+ */
+fn proposed_move(
+    point: Point,
+    initial_direction: &Direction,
+    elves: &impl ElfSet,
+) -> Option<(i32, i32)> {
+    if !has_neighbors(point, elves) {
+        return None;
+    }
+
+    let mut direction = initial_direction.clone();
+    for _ in 0..4 {
+        if empty_in_direction(point, &direction, elves) {
+            return Some(direction.of(point));
+        }
+
+        direction = direction.next();
+    }
+
+    None
+}
+
+/*
+ * Working out which elves to consider next round costs about as much as
+ * computing their proposals would, so it's only worth it once fewer than
+ * 1 in this many elves are moving each round.
+ */
+const TRACKING_RATIO: usize = 4;
+
+/**
+ * Moves all elves according to the problem's rules.
+ *
+ * If `active` is Some, only the elves in it are considered. Every other elf
+ * is known not to want to move: it didn't propose a move last round, and
+ * nothing around it has changed since. (An elf that doesn't propose a move
+ * has either no neighbors or no free direction, and neither of those
+ * depends on which direction it considers first.)
+ *
+ * Afterwards, `active` holds the elves that might propose a move next
+ * round, or None (meaning all of them) while lots of elves are moving.
+ * Late in the simulation, when most elves have settled, each round only
+ * looks at the few elves around the last moves.
+ *
+ * Returns true if at least one elf moved, or false if none did so.
+ */
+fn do_round(
+    elves: &mut impl ElfSet,
+    direction: &mut Direction,
+    active: &mut Option<FastSet<Point>>,
+) -> bool {
+    let proposed_moves = proposals(elves, direction, active);
+    let destinations = count_destinations(&proposed_moves);
+    make_moves(elves, direction, active, &proposed_moves, &destinations)
+}
+
+/**
+ * The same as do_round(), but working out the proposals and counting up
+ * their destinations across threads. Each elf's proposal only reads the
+ * elves around it, so they're all independent.
+ */
+#[cfg(feature = "parallel")]
+fn do_round_parallel(
+    elves: &mut impl ElfSet,
+    direction: &mut Direction,
+    active: &mut Option<FastSet<Point>>,
+) -> bool {
+    use rayon::prelude::*;
+
+    let propose = |p: Point| proposed_move(p, direction, elves).map(|new_p| (p, new_p));
+    let proposed_moves: Vec<(Point, Point)> = match active {
+        Some(active) => active.par_iter().copied().filter_map(propose).collect(),
+        None => elves.points().into_par_iter().filter_map(propose).collect(),
+    };
+
+    // Each thread counts its share of the moves, and then the counts are
+    // merged into the biggest of them.
+    let destinations = proposed_moves
+        .par_iter()
+        .fold(
+            FastMap::default,
+            |mut counts: FastMap<Point, usize>, (_, dest)| {
+                *counts.entry(*dest).or_default() += 1;
+                counts
+            },
+        )
+        .reduce(FastMap::default, |lhs, rhs| {
+            let (mut larger, smaller) = if lhs.len() >= rhs.len() {
+                (lhs, rhs)
+            } else {
+                (rhs, lhs)
+            };
+            for (dest, count) in smaller {
+                *larger.entry(dest).or_default() += count;
+            }
+            larger
+        });
+
+    make_moves(elves, direction, active, &proposed_moves, &destinations)
+}
+
+/**
+ * Gets a list of (original location, proposed location) for each elf that
+ * wants to move.
+ */
+fn proposals(
+    elves: &impl ElfSet,
+    direction: &Direction,
+    active: &Option<FastSet<Point>>,
+) -> Vec<(Point, Point)> {
+    let propose = |p: Point| proposed_move(p, direction, elves).map(|new_p| (p, new_p));
+    match active {
+        Some(active) => active.iter().copied().filter_map(propose).collect(),
+        None => elves.points().into_iter().filter_map(propose).collect(),
+    }
+}
+
+/**
+ * Counts the number of elves who proposed moving to each point.
+ */
+fn count_destinations(proposed_moves: &[(Point, Point)]) -> FastMap<Point, usize> {
+    let mut destinations: FastMap<Point, usize> = FastMap::default();
+    for (_, dest) in proposed_moves {
+        *destinations.entry(*dest).or_default() += 1;
+    }
+    destinations
+}
+
+/**
+ * The second half of a round: moves the elves whose destinations nobody
+ * else proposed, and works out which elves are active next round.
+ */
+fn make_moves(
+    elves: &mut impl ElfSet,
+    direction: &mut Direction,
+    active: &mut Option<FastSet<Point>>,
+    proposed_moves: &[(Point, Point)],
+    destinations: &FastMap<Point, usize>,
+) -> bool {
+    let mut any_moved = false;
+
+    let moving = destinations.values().filter(|&&count| count == 1).count();
+    let mut next_active = (moving * TRACKING_RATIO < elves.len()).then(FastSet::default);
+
+    // Figure out which moves will actually be made.
+    for (elf, dest) in proposed_moves {
+        // Was this elf the only one who proposed moving to `dest`?
+        if destinations[dest] == 1 {
+            // If so, move it.
+            elves.remove(elf);
+            elves.insert(*dest);
+            any_moved = true;
+
+            // Anything next to where it was or where it went sees a
+            // different neighborhood next round (and so does the elf).
+            // The two cells are adjacent, so that's a 3x4 or 4x3 block.
+            if let Some(next_active) = next_active.as_mut() {
+                let rows = min(elf.0, dest.0) - 1..=max(elf.0, dest.0) + 1;
+                let cols = min(elf.1, dest.1) - 1..=max(elf.1, dest.1) + 1;
+                for neighbor in rows.cartesian_product(cols) {
+                    if elves.contains(&neighbor) {
+                        next_active.insert(neighbor);
+                    }
+                }
+            }
+        } else if let Some(next_active) = next_active.as_mut() {
+            // It was blocked by another elf, and will try a different
+            // direction first next round.
+            next_active.insert(*elf);
+        }
+    }
+
+    // Some of those elves may have moved away after being marked.
+    if let Some(next_active) = next_active.as_mut() {
+        next_active.retain(|point| elves.contains(point));
+    }
+    *active = next_active;
+
+    // The first direction considered will be different next round.
+    *direction = direction.next();
+
+    any_moved
+}
+
+/*
+ * The usual functions for computing a bounding box.
+ */
+
+fn lower_bounds(lhs: &Point, rhs: &Point) -> Point {
+    (min(lhs.0, rhs.0), min(lhs.1, rhs.1))
+}
+
+fn upper_bounds(lhs: &Point, rhs: &Point) -> Point {
+    (max(lhs.0, rhs.0), max(lhs.1, rhs.1))
+}
+
+fn bounding_box<'a>(elves: impl IntoIterator<Item = &'a Point>) -> (Point, Point) {
+    const SMALLEST_POINT: Point = (i32::MIN, i32::MIN);
+    const LARGEST_POINT: Point = (i32::MAX, i32::MAX);
+
+    elves.into_iter().fold(
+        (LARGEST_POINT, SMALLEST_POINT),
+        |bounds: (Point, Point), point| {
+            (
+                lower_bounds(&bounds.0, point),
+                upper_bounds(&bounds.1, point),
+            )
+        },
+    )
+}
+
+/**
+ * Where the elves are after the given number of rounds.
+ */
+pub fn elves_after(input: &Elves, rounds: usize) -> Elves {
+    let mut elves = input.clone();
+    let mut direction = Direction::North;
+    let mut active = None;
+    for _ in 0..rounds {
+        do_round(&mut elves, &mut direction, &mut active);
+    }
+    elves
+}
+
+/**
+ * The elves that moved between two rounds: the cells they left, and the
+ * cells they moved into, each in (row, col) order.
+ */
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RoundDiff {
+    pub moved_from: Vec<Point>,
+    pub moved_to: Vec<Point>,
+}
+
+impl RoundDiff {
+    pub fn between(before: &Elves, after: &Elves) -> Self {
+        RoundDiff {
+            moved_from: before.difference(after).copied().sorted().collect(),
+            moved_to: after.difference(before).copied().sorted().collect(),
+        }
+    }
+
+    /**
+     * Draws the elves after the round, with the elves that just arrived as
+     * '@' and the cells they left as 'o'.
+     */
+    pub fn render(&self, after: &Elves) -> String {
+        render(after.iter().chain(&self.moved_from), |point| {
+            if self.moved_to.contains(point) {
+                Some('@')
+            } else if after.contains(point) {
+                Some('#')
+            } else if self.moved_from.contains(point) {
+                Some('o')
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/*
+ * A function that runs one round of the simulation: do_round(), or
+ * do_round_parallel() with the parallel feature.
+ */
+type Round<E> = fn(&mut E, &mut Direction, &mut Option<FastSet<Point>>) -> bool;
+
+#[aoc(day23, part1)]
+pub fn part1(input: &Elves) -> i32 {
+    if use_chunked(input) {
+        empty_ground_after_10_rounds(ChunkedElves::from(input), do_round)
+    } else {
+        empty_ground_after_10_rounds(input.clone(), do_round)
+    }
+}
+
+fn empty_ground_after_10_rounds<E: ElfSet>(mut elves: E, round: Round<E>) -> i32 {
+    let mut direction = Direction::North;
+    let mut active = None;
+
+    // Run 10 rounds, then find the bounding box size.
+    for _ in 0..10 {
+        round(&mut elves, &mut direction, &mut active);
+    }
+
+    let (lower_bounds, upper_bounds) = bounding_box(&elves.points());
+
+    // The answer is the size of the bounding box, minus the number of elf-occupied places.
+    (upper_bounds.0 - lower_bounds.0 + 1) * (upper_bounds.1 - lower_bounds.1 + 1)
+        - (elves.len() as i32)
+}
+
+#[aoc(day23, part2)]
+pub fn part2(input: &Elves) -> u32 {
+    if use_chunked(input) {
+        rounds_until_settled(ChunkedElves::from(input), do_round)
+    } else {
+        rounds_until_settled(input.clone(), do_round)
+    }
+}
+
+fn rounds_until_settled<E: ElfSet>(mut elves: E, round: Round<E>) -> u32 {
+    let mut direction = Direction::North;
+    let mut active = None;
+
+    // Iterate until no elves move. The last round only has to look at the
+    // elves around the final moves, rather than all of them.
+    let mut rounds = 1;
+    while round(&mut elves, &mut direction, &mut active) {
+        rounds += 1;
+    }
+
+    rounds
+}
+
+#[cfg(feature = "parallel")]
+#[aoc(day23, part1, Parallel)]
+pub fn part1_parallel(input: &Elves) -> i32 {
+    if use_chunked(input) {
+        empty_ground_after_10_rounds(ChunkedElves::from(input), do_round_parallel)
+    } else {
+        empty_ground_after_10_rounds(input.clone(), do_round_parallel)
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[aoc(day23, part2, Parallel)]
+pub fn part2_parallel(input: &Elves) -> u32 {
+    if use_chunked(input) {
+        rounds_until_settled(ChunkedElves::from(input), do_round_parallel)
+    } else {
+        rounds_until_settled(input.clone(), do_round_parallel)
+    }
+}
+
+/**
+ * A size by size square with elves scattered over it, about `percent` of
+ * the cells. The real inputs are roughly 70 by 70 and half full; bigger
+ * fields are for seeing how the solutions scale. The same seed always gives
+ * the same field.
+ */
+pub fn random_elves(size: i32, percent: u64, seed: u64) -> Elves {
+    // xorshift, which is plenty random enough for this.
+    let mut state = seed | 1;
+    let mut random = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    (0..size)
+        .cartesian_product(0..size)
+        .filter(|_| random() % 100 < percent)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "....#..\n\
+                           ..###.#\n\
+                           #...#.#\n\
+                           .#...##\n\
+                           #.###..\n\
+                           ##.#.##\n\
+                           .#..#..\n";
+
+    #[test]
+    fn test_part1() {
+        let input = generator(EXAMPLE);
+        assert_eq!(part1(&input), 110);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = generator(EXAMPLE);
+        assert_eq!(part2(&input), 20);
+    }
+
+    #[test]
+    fn test_active_elves() {
+        // Only looking at the active elves should give exactly the same
+        // positions as looking at every elf, every round.
+        let mut tracked = generator(EXAMPLE);
+        let mut untracked = tracked.clone();
+        let (mut tracked_direction, mut untracked_direction) = (Direction::North, Direction::North);
+        let mut active = Some(tracked.clone());
+
+        loop {
+            let moved = do_round(&mut tracked, &mut tracked_direction, &mut active);
+            assert_eq!(
+                moved,
+                do_round(&mut untracked, &mut untracked_direction, &mut None)
+            );
+            assert_eq!(tracked, untracked);
+            if !moved {
+                break;
+            }
+            if active.is_none() {
+                active = Some(tracked.clone());
+            }
+        }
+    }
+
+    #[test]
+    fn test_chunked_elves() {
+        // Spread the example's elves across several tiles (including
+        // negative ones), which shouldn't change the answers.
+        let input: Elves = generator(EXAMPLE)
+            .iter()
+            .map(|&(row, col)| (row - 62, col + 60))
+            .collect();
+        assert_eq!(
+            empty_ground_after_10_rounds(ChunkedElves::from(&input), do_round),
+            110
+        );
+        assert_eq!(
+            rounds_until_settled(ChunkedElves::from(&input), do_round),
+            20
+        );
+    }
+
+    #[test]
+    fn test_render_elves() {
+        // The small example from the puzzle, after each of its rounds.
+        let input = generator(".....\n..##.\n..#..\n.....\n..##.\n.....\n");
+        assert_eq!(render_elves(&input), "##\n#.\n..\n##\n");
+        assert_eq!(
+            render_elves(&elves_after(&input, 1)),
+            "##\n..\n#.\n.#\n#.\n"
+        );
+        assert_eq!(
+            render_elves(&elves_after(&input, 3)),
+            "..#..\n....#\n#....\n....#\n.....\n..#..\n"
+        );
+
+        // Three elves move in the third round; the other two stay put.
+        let (second, third) = (elves_after(&input, 2), elves_after(&input, 3));
+        let diff = RoundDiff::between(&second, &third);
+        assert_eq!(diff.moved_from, vec![(1, 2), (1, 3), (2, 1)]);
+        assert_eq!(diff.moved_to, vec![(0, 2), (1, 4), (2, 0)]);
+        assert_eq!(
+            diff.render(&third),
+            "..@..\n..oo@\n@o...\n....#\n.....\n..#..\n"
+        );
+        assert_eq!(RoundDiff::between(&third, &third), RoundDiff::default());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel() {
+        let input = generator(EXAMPLE);
+        assert_eq!(part1_parallel(&input), 110);
+        assert_eq!(part2_parallel(&input), 20);
+
+        // Every round should leave the elves in the same places as the
+        // serial version does, including once only some of them are active.
+        let mut serial = random_elves(30, 50, 23);
+        let mut parallel = serial.clone();
+        let (mut serial_direction, mut parallel_direction) = (Direction::North, Direction::North);
+        let (mut serial_active, mut parallel_active) = (None, None);
+        loop {
+            let moved = do_round(&mut serial, &mut serial_direction, &mut serial_active);
+            assert_eq!(
+                moved,
+                do_round_parallel(&mut parallel, &mut parallel_direction, &mut parallel_active)
+            );
+            assert_eq!(serial, parallel);
+            assert_eq!(serial_active, parallel_active);
+            if !moved {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_elves() {
+        let elves = random_elves(100, 50, 23);
+        assert_eq!(elves, random_elves(100, 50, 23));
+        assert!((4500..5500).contains(&elves.len()));
+        assert!(elves
+            .iter()
+            .all(|&(row, col)| (0..100).contains(&row) && (0..100).contains(&col)));
+        assert!(random_elves(10, 0, 1).is_empty());
+    }
+}
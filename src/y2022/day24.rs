@@ -0,0 +1,824 @@
+use std::fmt;
+
+use crate::error::AocError;
+use crate::utils::cancel::{CancelToken, Cancelled};
+
+use crate::utils::hash::FastSet;
+use crate::utils::input::normalize;
+use crate::utils::search::astar;
+use crate::utils::trace::{debug_event, enter_span};
+
+#[derive(Clone, Debug)]
+pub enum Direction {
+    Right,
+    Down,
+    Left,
+    Up,
+    // Not in the puzzle, but handy for experimenting.
+    UpRight,
+    DownRight,
+    DownLeft,
+    UpLeft,
+}
+
+impl Direction {
+    /**
+     * The (row, col) offset of one step in this direction.
+     */
+    fn delta(&self) -> Point {
+        match self {
+            Direction::Right => (0, 1),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Up => (-1, 0),
+            Direction::UpRight => (-1, 1),
+            Direction::DownRight => (1, 1),
+            Direction::DownLeft => (1, -1),
+            Direction::UpLeft => (-1, -1),
+        }
+    }
+
+    fn from_delta(delta: Point) -> Direction {
+        match delta {
+            (0, 1) => Direction::Right,
+            (1, 0) => Direction::Down,
+            (0, -1) => Direction::Left,
+            (-1, 0) => Direction::Up,
+            (-1, 1) => Direction::UpRight,
+            (1, 1) => Direction::DownRight,
+            (1, -1) => Direction::DownLeft,
+            (-1, -1) => Direction::UpLeft,
+            _ => panic!("no direction for {:?}", delta),
+        }
+    }
+
+    fn from_char(c: char) -> Option<Direction> {
+        match c {
+            '>' => Some(Direction::Right),
+            'v' => Some(Direction::Down),
+            '<' => Some(Direction::Left),
+            '^' => Some(Direction::Up),
+            '↗' => Some(Direction::UpRight),
+            '↘' => Some(Direction::DownRight),
+            '↙' => Some(Direction::DownLeft),
+            '↖' => Some(Direction::UpLeft),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::Up => '^',
+            Direction::UpRight => '↗',
+            Direction::DownRight => '↘',
+            Direction::DownLeft => '↙',
+            Direction::UpLeft => '↖',
+        }
+    }
+}
+
+/**
+ * What blizzards do when they reach the valley walls. The puzzle's
+ * blizzards wrap around to the opposite wall; reflecting ones bounce back
+ * the way they came (flipping only the part of a diagonal's direction that
+ * hit the wall).
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Walls {
+    #[default]
+    Wrap,
+    Reflect,
+}
+
+type Point = (i32, i32);
+type Blizzard = (Point, Direction);
+
+/**
+ * Returns the state of the valley at a specific point in time.
+ */
+#[derive(Clone)]
+pub struct State {
+    // The points in the valley occupied by blizzards.
+    obstacles: FastSet<Point>,
+
+    // A list of the blizzards themselves. This is stored separately
+    // to make accessing the set of obstacles more efficient.
+    blizzards: Vec<Blizzard>,
+
+    // The size of the valley, in (rows, cols).
+    dimensions: (i32, i32),
+
+    // The starting and ending points (just stored for display purposes).
+    start: Point,
+    end: Point,
+
+    walls: Walls,
+}
+
+impl State {
+    /**
+     * Switches what the blizzards do at the walls (see Walls).
+     */
+    pub fn with_walls(self, walls: Walls) -> State {
+        State { walls, ..self }
+    }
+
+    /**
+     * The number of minutes after which the blizzards are back where they
+     * started. A blizzard bouncing across n cells takes 2 * (n - 1) minutes
+     * to get back to where it was, facing the same way.
+     */
+    fn period(&self) -> usize {
+        let (rows, cols) = self.dimensions;
+        match self.walls {
+            Walls::Wrap => num::integer::lcm(rows, cols) as usize,
+            Walls::Reflect => num::integer::lcm(bounce_period(rows), bounce_period(cols)) as usize,
+        }
+    }
+
+    /**
+     * Generates the valley state at the next time step.
+     */
+    fn next(&self) -> State {
+        let mut blizzards = Vec::new();
+        let mut obstacles = FastSet::default();
+
+        // Move each blizzard forward, wrapping if necessary.
+        for blizzard in self.blizzards.iter() {
+            let new_blizzard = self.move_blizzard(blizzard);
+            obstacles.insert(new_blizzard.0);
+            blizzards.push(new_blizzard);
+        }
+
+        State {
+            obstacles,
+            blizzards,
+
+            // Everything except the blizzards (and obstacles) stays the same.
+            dimensions: self.dimensions,
+            start: self.start,
+            end: self.end,
+            walls: self.walls,
+        }
+    }
+
+    /**
+     * Moves a blizzard forward one unit, wrapping or reflecting at the walls.
+     * Rows and columns are handled separately, so diagonal blizzards work
+     * the same way as the others.
+     */
+    fn move_blizzard(&self, blizzard: &Blizzard) -> Blizzard {
+        let ((row, col), direction) = blizzard;
+        let (dy, dx) = direction.delta();
+        let (rows, cols) = self.dimensions;
+
+        let (row, dy) = self.step(*row, dy, rows);
+        let (col, dx) = self.step(*col, dx, cols);
+
+        ((row, col), Direction::from_delta((dy, dx)))
+    }
+
+    /**
+     * Moves one coordinate by `delta` within 0..len, returning the new
+     * coordinate and the (possibly reflected) delta.
+     */
+    fn step(&self, position: i32, delta: i32, len: i32) -> (i32, i32) {
+        let next = position + delta;
+        if (0..len).contains(&next) {
+            return (next, delta);
+        }
+
+        match self.walls {
+            Walls::Wrap => (next.rem_euclid(len), delta),
+            // In a valley one cell wide, there's nowhere to bounce to.
+            Walls::Reflect if len == 1 => (position, -delta),
+            Walls::Reflect => (position - delta, -delta),
+        }
+    }
+
+    /**
+     * Checks whether `cell` will have a blizzard in it `elapsed` minutes after this state.
+     */
+    fn occupied_after(&self, cell: Point, elapsed: usize) -> bool {
+        let (rows, cols) = self.dimensions;
+        let elapsed = elapsed as i32;
+
+        self.blizzards.iter().any(|((row, col), direction)| {
+            let (dy, dx) = direction.delta();
+            self.position_after(*row, dy, rows, elapsed) == cell.0
+                && self.position_after(*col, dx, cols, elapsed) == cell.1
+        })
+    }
+
+    /**
+     * Where one coordinate moving by `delta` each minute will be after
+     * `elapsed` minutes. A reflecting blizzard's path can be "unfolded" into
+     * a loop of 2 * (len - 1) cells, where it just moves forward; cells past
+     * the end of the valley are its trip back.
+     */
+    fn position_after(&self, position: i32, delta: i32, len: i32, elapsed: i32) -> i32 {
+        match self.walls {
+            Walls::Wrap => (position + delta * elapsed).rem_euclid(len),
+            Walls::Reflect => {
+                let period = bounce_period(len);
+                let unfolded = if delta < 0 {
+                    period - position
+                } else {
+                    position
+                };
+                let moved = (unfolded + delta.abs() * elapsed).rem_euclid(period);
+                if moved < len {
+                    moved
+                } else {
+                    period - moved
+                }
+            }
+        }
+    }
+}
+
+fn bounce_period(len: i32) -> i32 {
+    (2 * (len - 1)).max(1)
+}
+
+/*
+ * A better A* heuristic than plain Manhattan distance.
+ *
+ * Every path to the goal has to pass through the valley cell right next to
+ * it (the "exit"), and can only step onto the exit when there isn't a
+ * blizzard there. So a lower bound on the time to the goal is: walk to the
+ * exit as if there were no blizzards, wait until the exit is clear, then
+ * take the last step.
+ *
+ * Blizzards repeat every lcm(rows, cols) minutes (or a bit longer if they
+ * reflect; see State::period), so how long we'd have to wait for the exit
+ * to clear can be precomputed for each point in that cycle.
+ */
+struct ExitHeuristic {
+    end: Point,
+    exit: Point,
+    wait_for_exit: Vec<u32>,
+}
+
+impl ExitHeuristic {
+    fn new(initial: &State, end: &Point) -> Self {
+        let rows = initial.dimensions.0;
+        let exit = if end.0 < 0 {
+            (0, end.1)
+        } else {
+            (rows - 1, end.1)
+        };
+        let period = initial.period();
+        let blocked: Vec<bool> = (0..period)
+            .map(|time| initial.occupied_after(exit, time))
+            .collect();
+
+        // Walk backwards through two cycles, so that the waits near the end of
+        // the cycle account for the exit clearing early in the next one.
+        // If the exit is never clear, the goal is unreachable anyways, so any
+        // estimate is fine.
+        let mut wait_for_exit = vec![0; period];
+        let mut wait = 0;
+        for time in (0..2 * period).rev() {
+            wait = if blocked[time % period] { wait + 1 } else { 0 };
+            wait_for_exit[time % period] = wait;
+        }
+
+        ExitHeuristic {
+            end: *end,
+            exit,
+            wait_for_exit,
+        }
+    }
+
+    fn estimate(&self, point: &Point, time: usize) -> u32 {
+        if *point == self.end {
+            return 0;
+        }
+
+        let distance = self.exit.0.abs_diff(point.0) + self.exit.1.abs_diff(point.1);
+        let arrival = time + distance as usize;
+        let wait = self.wait_for_exit[arrival % self.wait_for_exit.len()];
+
+        // Plus one for the final step from the exit to the goal.
+        distance + wait + 1
+    }
+}
+
+impl fmt::Debug for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The first row is always a bunch of #'s, with one gap at the start point.
+        for col in 0..=self.dimensions.1 + 1 {
+            if col == self.start.1 + 1 {
+                write!(f, " ")?;
+            } else {
+                write!(f, "#")?;
+            }
+        }
+        writeln!(f)?;
+
+        // Now we draw the map. It always starts and ends with a '#' for the wall.
+        for row in 0..self.dimensions.0 {
+            write!(f, "#")?;
+            for col in 0..self.dimensions.1 {
+                let blizzards: Vec<&Blizzard> = self
+                    .blizzards
+                    .iter()
+                    .filter(|b| (row, col) == b.0)
+                    .collect();
+                if blizzards.is_empty() {
+                    write!(f, " ")?;
+                } else if blizzards.len() > 1 {
+                    write!(f, "{}", blizzards.len())?;
+                } else {
+                    write!(f, "{}", blizzards[0].1.to_char())?;
+                }
+            }
+            write!(f, "#")?;
+            writeln!(f)?;
+        }
+
+        // The last row is also a bunch of #'s, with one gap at the end point.
+        for col in 0..=self.dimensions.1 + 1 {
+            if col == self.end.1 + 1 {
+                write!(f, " ")?;
+            } else {
+                write!(f, "#")?;
+            }
+        }
+        writeln!(f)?;
+
+        Ok(())
+    }
+}
+
+/**
+ * Returns the set of empty locations that are:
+ *   1. adjacent to the given point
+ *   2. inside the valley, or the start/end point
+ *   3. not occupied by a blizzard
+ *
+ * Assumes that you can always move to the start or end points.
+ * Blizzards never leave the valley, so this holds as long as no
+ * vertically-moving blizzard shares a column with either opening
+ * (it would be ambiguous whether it wraps through the gap in the
+ * wall). The generator rejects inputs where that happens.
+ */
+fn neighbors(state: &State, point: &Point) -> Vec<Point> {
+    [(0, 0), (-1, 0), (1, 0), (0, -1), (0, 1)]
+        .iter()
+        .map(|(dy, dx)| (point.0 + dy, point.1 + dx))
+        .filter(|&p| {
+            p == state.start
+                || p == state.end
+                || (0 <= p.0
+                    && p.0 < state.dimensions.0
+                    && 0 <= p.1
+                    && p.1 < state.dimensions.1
+                    && !state.obstacles.contains(&p))
+        })
+        .collect::<Vec<_>>()
+}
+
+/**
+ * The valley state at each minute of one blizzard cycle, generated as the
+ * search asks for them. Since the blizzards repeat every `period` minutes,
+ * this never holds more than `period` states, however long the journey.
+ */
+struct StateCache {
+    states: Vec<State>,
+    period: usize,
+}
+
+impl StateCache {
+    fn new(initial: &State) -> Self {
+        StateCache {
+            period: initial.period(),
+            states: vec![initial.clone()],
+        }
+    }
+
+    fn initial(&self) -> &State {
+        &self.states[0]
+    }
+
+    /**
+     * Returns the state `time` minutes in, generating it if needed.
+     */
+    fn at(&mut self, time: usize) -> &State {
+        let phase = time % self.period;
+        while self.states.len() <= phase {
+            let next_state = self.states.last().unwrap().next();
+            self.states.push(next_state);
+        }
+        &self.states[phase]
+    }
+}
+
+/**
+ * Determines when an expedition at `start` will make it to `end, given that they
+ * start moving at time `start_time`.
+ *
+ * The main challenge for this problem is that the usual pathfinding algorithms
+ * stop working when you have obstacles that move over time.
+ *
+ * So, the key insight to solving it is that we need to transform the search space
+ * into one where the obstacles *aren't* moving. We can do this by adding in a
+ * third dimension - time - and searching in this 3D space.
+ *
+ * My mental image was like one of those video game levels where you're skydiving
+ * and have to move to dodge obstacles that pop up. Yours might be different :)
+ *
+ * Anyways, this basically just runs A* on a 3D grid, where allowable moves are
+ * those that move forward 1 step in time to a point without a blizzard in it.
+ * I originally used Manhattan distance as the A* heuristic, which worked pretty
+ * well; ExitHeuristic above tightens it up a bit.
+ *
+ * The search stops expanding nodes once `cancel` is cancelled.
+ */
+fn arrival_time(
+    start: &Point,
+    end: &Point,
+    start_time: usize,
+    states: &mut StateCache,
+    cancel: &CancelToken,
+) -> Result<u32, Cancelled> {
+    enter_span!("arrival_time", start_time = start_time);
+    let heuristic = ExitHeuristic::new(states.initial(), end);
+    let period = states.period;
+
+    // Search nodes only record time modulo the period: being at the same
+    // point at the same phase of the cycle later on can't possibly help.
+    let (found, stats) = astar(
+        (*start, start_time % period),
+        |(p, phase)| {
+            if cancel.is_cancelled() {
+                return Vec::new();
+            }
+
+            // Now figure out which (row, col, t) points are accessible.
+            // A* wants (neighbor, distance) pairs; we're on a grid so all
+            // distances are identically 1.
+            let next_phase = (phase + 1) % period;
+            neighbors(states.at(next_phase), p)
+                .iter()
+                .map(|&neighbor| ((neighbor, next_phase), 1))
+                .collect::<Vec<_>>()
+        },
+        |(p, phase)| heuristic.estimate(p, *phase),
+        |(p, _)| *p == *end,
+    );
+    cancel.check()?;
+    let (_, distance) = found.expect("no path found");
+
+    debug_event!(
+        "search finished",
+        distance = distance,
+        states_expanded = stats.expanded,
+        blizzard_states = states.states.len(),
+    );
+
+    // Make sure to add in the start time!
+    Ok(start_time as u32 + distance)
+}
+
+/**
+ * The same as arrival_time(), but searching breadth-first a minute at a
+ * time instead: every move takes a minute, so the first minute that `end`
+ * is reachable is the answer. Working out where the expedition could be
+ * next minute is independent for each cell it could be in now, so wide
+ * valleys (with lots of those cells) spread that across threads. Minutes
+ * with only a few hundred cells to look at stay on one thread.
+ */
+#[cfg(feature = "parallel")]
+fn arrival_time_parallel(
+    start: &Point,
+    end: &Point,
+    start_time: usize,
+    states: &mut StateCache,
+    cancel: &CancelToken,
+) -> Result<u32, Cancelled> {
+    use rayon::prelude::*;
+
+    enter_span!("arrival_time_parallel", start_time = start_time);
+
+    // Waiting at the start is always safe, so the search never runs dry.
+    // Instead, once it's gone on longer than there are (cell, phase) pairs
+    // to visit, it can't ever get there.
+    let (rows, cols) = states.initial().dimensions;
+    let limit = states.period * (rows * cols) as usize + 2;
+
+    let mut reachable: FastSet<Point> = [*start].into_iter().collect();
+    for time in start_time..start_time + limit {
+        if reachable.contains(end) {
+            return Ok(time as u32);
+        }
+        cancel.check()?;
+
+        // Hash sets can't be split evenly between threads, but a Vec can.
+        let next_state = states.at(time + 1);
+        let frontier: Vec<Point> = reachable.into_iter().collect();
+        reachable = frontier
+            .par_iter()
+            .with_min_len(256)
+            .flat_map_iter(|p| neighbors(next_state, p))
+            .collect();
+    }
+    panic!("no path found");
+}
+
+/*
+ * A function that finds when the expedition can get from one point to
+ * another: arrival_time(), or arrival_time_parallel().
+ */
+type Leg = fn(&Point, &Point, usize, &mut StateCache, &CancelToken) -> Result<u32, Cancelled>;
+
+/**
+ * Parses the valley map.
+ *
+ * Fails if the map contains an unknown character, or if an up/down
+ * blizzard sits in the same column as the start or end - the puzzle
+ * doesn't say what happens when one reaches the opening, and
+ * `neighbors` treats both openings as always safe.
+ */
+#[aoc_generator(day24)]
+pub fn generator(input: &str) -> Result<State, AocError> {
+    enter_span!("parse");
+    let input = normalize(input);
+    let num_cols = input.find('\n').unwrap() - 2;
+    let start = (-1, input.find('.').unwrap() as i32 - 1);
+
+    let mut obstacles = FastSet::default();
+    let mut blizzards = Vec::new();
+
+    for (row, line) in input
+        .lines()
+        .skip(1)
+        .enumerate()
+        .take_while(|(_, line)| line.as_bytes()[1] != b'#')
+    {
+        for (col, c) in line.chars().skip(1).enumerate().take(num_cols) {
+            let coords = (row as i32, col as i32);
+            if c == '.' {
+                continue;
+            }
+            let direction = Direction::from_char(c)
+                .ok_or_else(|| AocError::parse(row + 2, format!("bad map character {c:?}")))?;
+            obstacles.insert(coords);
+            blizzards.push((coords, direction));
+        }
+    }
+
+    let num_rows = input.lines().count() - 2;
+    let dimensions = (num_rows as i32, num_cols as i32);
+
+    let last_line = input.lines().last().unwrap();
+    let end = (num_rows as i32, last_line.find('.').unwrap() as i32 - 1);
+
+    for (point, direction) in &blizzards {
+        if matches!(direction, Direction::Up | Direction::Down)
+            && (point.1 == start.1 || point.1 == end.1)
+        {
+            return Err(AocError::parse(
+                point.0 as usize + 2,
+                format!(
+                    "'{}' blizzard in column {} would pass through the start or end",
+                    direction.to_char(),
+                    point.1 + 1
+                ),
+            ));
+        }
+    }
+
+    Ok(State {
+        obstacles,
+        blizzards,
+        dimensions,
+        start,
+        end,
+        walls: Walls::Wrap,
+    })
+}
+
+#[aoc(day24, part1)]
+pub fn part1(input: &State) -> u32 {
+    part1_cancellable(input, &CancelToken::new()).expect("nothing cancels this search")
+}
+
+/**
+ * Part 1, giving up if `cancel` is cancelled before it's done.
+ */
+pub fn part1_cancellable(input: &State, cancel: &CancelToken) -> Result<u32, Cancelled> {
+    let mut states = StateCache::new(input);
+
+    arrival_time(&input.start, &input.end, 0, &mut states, cancel)
+}
+
+#[aoc(day24, part2)]
+pub fn part2(input: &State) -> u32 {
+    part2_cancellable(input, &CancelToken::new()).expect("nothing cancels this search")
+}
+
+/**
+ * Part 2, giving up if `cancel` is cancelled before it's done.
+ */
+pub fn part2_cancellable(input: &State, cancel: &CancelToken) -> Result<u32, Cancelled> {
+    there_and_back_again(input, arrival_time, cancel)
+}
+
+fn there_and_back_again(input: &State, leg: Leg, cancel: &CancelToken) -> Result<u32, Cancelled> {
+    let mut states = StateCache::new(input);
+
+    // Go from the start to the end.
+    let get_to_end = leg(&input.start, &input.end, 0, &mut states, cancel)?;
+
+    // Oops, the elves forgot snacks. Head back to the start.
+    let back_to_start = leg(
+        &input.end,
+        &input.start,
+        get_to_end as usize,
+        &mut states,
+        cancel,
+    )?;
+
+    // Aaaand finally we can finish our journey.
+    leg(
+        &input.start,
+        &input.end,
+        back_to_start as usize,
+        &mut states,
+        cancel,
+    )
+}
+
+#[cfg(feature = "parallel")]
+#[aoc(day24, part1, Parallel)]
+pub fn part1_parallel(input: &State) -> u32 {
+    let mut states = StateCache::new(input);
+    arrival_time_parallel(
+        &input.start,
+        &input.end,
+        0,
+        &mut states,
+        &CancelToken::new(),
+    )
+    .expect("nothing cancels this search")
+}
+
+#[cfg(feature = "parallel")]
+#[aoc(day24, part2, Parallel)]
+pub fn part2_parallel(input: &State) -> u32 {
+    // The three legs each start when the last one finishes, so they can't
+    // run in parallel with each other; only the search within each can.
+    there_and_back_again(input, arrival_time_parallel, &CancelToken::new())
+        .expect("nothing cancels this search")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::testing::read_example;
+
+    #[test]
+    fn test_exit_heuristic() {
+        let Some(input) = read_example("day24.txt") else {
+            return;
+        };
+        let state = generator(&input).unwrap();
+        let heuristic = ExitHeuristic::new(&state, &state.end);
+
+        // Never less than the Manhattan distance, and never more than the
+        // actual answer (18) - otherwise A* could return the wrong path.
+        let estimate = heuristic.estimate(&state.start, 0);
+        assert!((10..=18).contains(&estimate), "estimate was {estimate}");
+        assert_eq!(heuristic.estimate(&state.end, 18), 0);
+    }
+
+    #[test]
+    fn test_reflecting_diagonals() {
+        let input = "#.####\n#↘..<#\n#.↖..#\n#..>.#\n####.#\n";
+        let state = generator(input).unwrap().with_walls(Walls::Reflect);
+        assert_eq!(state.period(), 12);
+
+        // The ↘ in the corner bounces straight back off the bottom wall.
+        let mut states = vec![state.clone()];
+        for _ in 0..state.period() {
+            states.push(states.last().unwrap().next());
+        }
+        assert_eq!(states[2].blizzards[0].0, (2, 2));
+        assert_eq!(states[3].blizzards[0].0, (1, 3));
+        assert_eq!(states[3].blizzards[0].1.to_char(), '↗');
+
+        // The closed form used by the heuristic agrees with stepping.
+        for (elapsed, later) in states.iter().enumerate() {
+            for row in 0..3 {
+                for col in 0..4 {
+                    assert_eq!(
+                        state.occupied_after((row, col), elapsed),
+                        later.obstacles.contains(&(row, col)),
+                        "({row}, {col}) after {elapsed} minutes"
+                    );
+                }
+            }
+        }
+        assert_eq!(states.last().unwrap().obstacles, state.obstacles);
+    }
+
+    #[test]
+    fn test_blizzard_in_opening_column() {
+        // The 'v' shares a column with the start, the '^' with the end.
+        let err = generator("#.###\n#v..#\n#...#\n###.#\n").unwrap_err();
+        assert!(err.to_string().contains("column 1"), "{err}");
+        let err = generator("#.###\n#...#\n#..^#\n###.#\n").unwrap_err();
+        assert!(err.to_string().contains("column 3"), "{err}");
+
+        // Sideways blizzards are fine anywhere.
+        let state = generator("#.###\n#>..#\n#..<#\n###.#\n").unwrap();
+        assert_eq!(state.blizzards.len(), 2);
+    }
+
+    #[test]
+    fn test_state_cache_is_bounded() {
+        let Some(input) = read_example("day24.txt") else {
+            return;
+        };
+        let state = generator(&input).unwrap();
+        let mut states = StateCache::new(&state);
+        assert_eq!(states.period, 12);
+
+        // Setting off a few cycles late takes just as long as setting off now.
+        let late = 5 * states.period;
+        let arrival = arrival_time(
+            &state.start,
+            &state.end,
+            late,
+            &mut states,
+            &CancelToken::new(),
+        )
+        .unwrap();
+        assert_eq!(arrival as usize, late + 18);
+        assert!(states.states.len() <= states.period);
+        assert_eq!(states.at(late).obstacles, state.obstacles);
+    }
+
+    #[test]
+    fn test_cancel() {
+        let Some(input) = read_example("day24.txt") else {
+            return;
+        };
+        let state = generator(&input).unwrap();
+        let cancel = CancelToken::new();
+        assert_eq!(part2_cancellable(&state, &cancel), Ok(54));
+
+        cancel.cancel();
+        assert_eq!(part1_cancellable(&state, &cancel), Err(Cancelled));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel() {
+        let Some(input) = read_example("day24.txt") else {
+            return;
+        };
+        let state = generator(&input).unwrap();
+        let cancel = CancelToken::new();
+        assert_eq!(
+            there_and_back_again(&state, arrival_time_parallel, &cancel),
+            Ok(54)
+        );
+
+        // A wider valley, with sideways blizzards on every other row, where
+        // the later minutes have hundreds of cells to look at.
+        let (rows, cols) = (20, 120);
+        let mut valley = format!("#.{}\n", "#".repeat(cols));
+        for row in 0..rows {
+            let line: String = (0..cols)
+                .map(|col| match (row % 2, col % 7) {
+                    (0, 0) => '>',
+                    (0, 3) => '<',
+                    _ => '.',
+                })
+                .collect();
+            valley.push_str(&format!("#{line}#\n"));
+        }
+        valley.push_str(&format!("{}.#\n", "#".repeat(cols)));
+        let state = generator(&valley).unwrap();
+        assert_eq!(part1_parallel(&state), super::part1(&state));
+        assert_eq!(part2_parallel(&state), super::part2(&state));
+
+        cancel.cancel();
+        assert_eq!(
+            arrival_time_parallel(
+                &state.start,
+                &state.end,
+                0,
+                &mut StateCache::new(&state),
+                &cancel
+            ),
+            Err(Cancelled)
+        );
+    }
+}
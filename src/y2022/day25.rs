@@ -0,0 +1,489 @@
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{
+    fmt,
+    iter::Sum,
+    ops::{Add, AddAssign},
+    str::FromStr,
+};
+
+use num::Zero;
+
+use crate::utils::input::normalize;
+
+/*
+Day 25 introduces a novel numbering system. Instead of normal base-10 numbers,
+it uses a base-5 system - but more than that, it's a *balanced* base-5 system,
+using the symbols '=' (-2), '-' (-1), 0, 1, and 2.
+
+Getting the first star requires you to read in a list of numbers written using
+this system, sum them up, and write out the result in balanced quinary.
+
+The obvious (and straightforward) way to approach this is to convert each string
+of symbols into a native-format integer, add those integers, and convert back
+to balanced quinary for output.
+
+I chose instead to build a (limited) implementation of balanced quinary from scratch,
+thereby avoiding any pesky conversions to other bases and keeping the computations
+pure and simple (?).
+*/
+
+/**
+ * Represents a single symbol (digit-equivalent) for balanced quinary.
+ */
+#[derive(Clone, Copy, PartialEq)]
+enum Quint {
+    MinusTwo,
+    MinusOne,
+    Zero,
+    One,
+    Two,
+}
+
+impl TryFrom<char> for Quint {
+    type Error = &'static str;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '=' => Ok(Self::MinusTwo),
+            '-' => Ok(Self::MinusOne),
+            '0' => Ok(Self::Zero),
+            '1' => Ok(Self::One),
+            '2' => Ok(Self::Two),
+            _ => Err("invalid char"),
+        }
+    }
+}
+
+impl TryFrom<u8> for Quint {
+    type Error = &'static str;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::try_from(char::from(value))
+    }
+}
+
+impl From<Quint> for char {
+    fn from(pent: Quint) -> Self {
+        match pent {
+            Quint::MinusTwo => '=',
+            Quint::MinusOne => '-',
+            Quint::Zero => '0',
+            Quint::One => '1',
+            Quint::Two => '2',
+        }
+    }
+}
+
+/**
+ * Implements a half-adder for Quints.
+ *
+ * `lhs_quint + rhs_quint` returns a pair (sum, carry).
+ *
+ * Unfortunately, since we're working entirely symbolically, the best
+ * we can do here is write down the addition table as concisely as possible.
+ */
+impl Add for Quint {
+    type Output = (Self, Self);
+
+    fn add(self, other: Self) -> (Self, Self) {
+        match (self, other) {
+            // 0 + X = X
+            (Self::Zero, any) | (any, Self::Zero) => (any, Self::Zero),
+
+            // X + -X = 0
+            (Self::One, Self::MinusOne)
+            | (Self::Two, Self::MinusTwo)
+            | (Self::MinusOne, Self::One)
+            | (Self::MinusTwo, Self::Two) => (Self::Zero, Self::Zero),
+
+            // There are only a couple of ways to get -1 or 1 with no carry
+            (Self::Two, Self::MinusOne) | (Self::MinusOne, Self::Two) => (Self::One, Self::Zero),
+            (Self::MinusTwo, Self::One) | (Self::One, Self::MinusTwo) => {
+                (Self::MinusOne, Self::Zero)
+            }
+
+            // There's only one way each to get 2 or -2 with no carry
+            (Self::One, Self::One) => (Self::Two, Self::Zero),
+            (Self::MinusOne, Self::MinusOne) => (Self::MinusTwo, Self::Zero),
+
+            // Finally we get to the operands that result in carries.
+            // For example, 2 + 1 = 3 = (-2) + (1)*5, so the sum is -2 and the carry is 1.
+            (Self::Two, Self::One) | (Self::One, Self::Two) => (Self::MinusTwo, Self::One),
+            (Self::MinusTwo, Self::MinusOne) | (Self::MinusOne, Self::MinusTwo) => {
+                (Self::Two, Self::MinusOne)
+            }
+
+            // e.g. 2 + 2 = 4 = (-1) + (1)*5
+            (Self::Two, Self::Two) => (Self::MinusOne, Self::One),
+            (Self::MinusTwo, Self::MinusTwo) => (Self::One, Self::MinusOne),
+        }
+    }
+}
+
+/**
+ * Represents an arbitrary integer as a string of quints.
+ *
+ * Note that quints are stored in little-endian order, with the least-
+ * significant quint first. This simplifies operations and makes them
+ * a bit faster.
+ */
+#[derive(PartialEq)]
+pub struct BalancedQuinary {
+    quints: Vec<Quint>,
+}
+
+impl BalancedQuinary {
+    /**
+     * Parses a number straight from the input's bytes.
+     */
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        let mut number = Self { quints: Vec::new() };
+        number.read_bytes(bytes)?;
+        Ok(number)
+    }
+
+    /**
+     * Replaces this number with the one in `bytes`, reusing its buffer, so
+     * that reading a long list of numbers one at a time only allocates for
+     * the longest. On an error the number is left empty (that is, zero).
+     */
+    pub fn read_bytes(&mut self, bytes: &[u8]) -> Result<(), &'static str> {
+        self.quints.clear();
+        for &byte in bytes.iter().rev() {
+            match Quint::try_from(byte) {
+                Ok(quint) => self.quints.push(quint),
+                Err(err) => {
+                    self.quints.clear();
+                    return Err(err);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for BalancedQuinary {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_bytes(s.as_bytes())
+    }
+}
+
+impl From<BalancedQuinary> for String {
+    fn from(value: BalancedQuinary) -> Self {
+        value
+            .quints
+            .iter()
+            .rev()
+            .map(|&quint| char::from(quint))
+            .collect::<String>()
+    }
+}
+
+impl fmt::Display for BalancedQuinary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &quint in self.quints.iter().rev() {
+            write!(f, "{}", char::from(quint))?;
+        }
+        Ok(())
+    }
+}
+
+impl Zero for BalancedQuinary {
+    fn zero() -> Self {
+        Self {
+            quints: vec![Quint::Zero],
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.quints.is_empty() || (self.quints.len() == 1 && matches!(self.quints[0], Quint::Zero))
+    }
+}
+
+/**
+ * The only real operation implemented for balanced quinary: addition.
+ *
+ * Effectively, this builds an awkward full adder out of the half-adder
+ * implemented in Quint::add. The sum is written over the left-hand side's
+ * quints, which only need to grow when the sum is longer.
+ */
+impl AddAssign<&BalancedQuinary> for BalancedQuinary {
+    fn add_assign(&mut self, rhs: &BalancedQuinary) {
+        if self.quints.len() < rhs.quints.len() {
+            self.quints.resize(rhs.quints.len(), Quint::Zero);
+        }
+
+        let mut carry = Quint::Zero;
+        for i in 0..self.quints.len() {
+            // Past the end of the right-hand side's quints, there's nothing
+            // left to do once the carry runs out.
+            let Some(&other) = rhs.quints.get(i) else {
+                if matches!(carry, Quint::Zero) {
+                    break;
+                }
+                let (sum, new_carry) = carry + self.quints[i];
+                self.quints[i] = sum;
+                carry = new_carry;
+                continue;
+            };
+
+            // Add the current quints.
+            let (quint_sum, generated_carry) = self.quints[i] + other;
+
+            // Add the input carry to the sum.
+            let (sum, propagated_carry) = quint_sum + carry;
+
+            // Add the carries. The carry from this sum can *never* be nonzero, since
+            // the only possible inputs are One and MinusOne, and no addition involving
+            // only those values can result in a nonzero carry.
+            let (total_carry, _) = generated_carry + propagated_carry;
+
+            self.quints[i] = sum;
+            carry = total_carry;
+        }
+
+        // If there is a carry left over at this point, we need to add it
+        // as the highest-order quint of the result.
+        if !matches!(carry, Quint::Zero) {
+            self.quints.push(carry);
+        }
+
+        // Let's establish a convention that every number has at least one quint.
+        // This avoids awkward empty strings when printing, for example.
+        if self.quints.is_empty() {
+            self.quints.push(Quint::Zero);
+        }
+    }
+}
+
+impl Add for BalancedQuinary {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += &rhs;
+        self
+    }
+}
+
+/**
+ * Convenient traits so we can call .sum() on iterators of balanced quinary
+ * numbers, owned or not.
+ */
+impl Sum for BalancedQuinary {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(BalancedQuinary::zero(), |acc, n| acc + n)
+    }
+}
+
+impl<'a> Sum<&'a BalancedQuinary> for BalancedQuinary {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(BalancedQuinary::zero(), |mut acc, n| {
+            acc += n;
+            acc
+        })
+    }
+}
+
+/**
+ * A line that isn't a SNAFU number, and the first character in it that
+ * isn't a SNAFU digit. Lines and columns are 1-indexed, to match what an
+ * editor shows.
+ */
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub found: char,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected a SNAFU digit but got {:?}",
+            self.line, self.column, self.found
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+#[cfg(feature = "std")]
+impl From<ParseError> for crate::error::AocError {
+    fn from(err: ParseError) -> Self {
+        crate::error::AocError::parse(
+            err.line,
+            format!(
+                "column {}: expected a SNAFU digit but got {:?}",
+                err.column, err.found
+            ),
+        )
+    }
+}
+
+/**
+ * What to do with lines that aren't SNAFU numbers: fail on the first one,
+ * or skip them all and add up the rest.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Parsing {
+    Strict,
+    Lenient,
+}
+
+/**
+ * Adds up the numbers, one per line. Each line is read into the same buffer
+ * and added straight into the total, so that the whole sum allocates next
+ * to nothing. Blank lines count as zero.
+ */
+pub fn sum_lines(input: &str, parsing: Parsing) -> Result<BalancedQuinary, ParseError> {
+    let input = normalize(input);
+    let mut total = BalancedQuinary::zero();
+    let mut number = BalancedQuinary::zero();
+    for (index, line) in input.lines().enumerate() {
+        if number.read_bytes(line.as_bytes()).is_ok() {
+            total += &number;
+        } else if parsing == Parsing::Strict {
+            let (column, found) = line
+                .chars()
+                .enumerate()
+                .find(|&(_, c)| Quint::try_from(c).is_err())
+                .expect("a line that didn't parse has a bad character");
+            return Err(ParseError {
+                line: index + 1,
+                column: column + 1,
+                found,
+            });
+        }
+    }
+    Ok(total)
+}
+
+/*
+ * There's only the one part, so the generator does all the work; part 1
+ * just prints the total.
+ */
+#[cfg_attr(feature = "std", aoc_generator(day25))]
+pub fn generator(input: &str) -> Result<BalancedQuinary, ParseError> {
+    sum_lines(input, Parsing::Strict)
+}
+
+#[cfg_attr(feature = "std", aoc(day25, part1))]
+pub fn part1(total: &BalancedQuinary) -> String {
+    total.to_string()
+}
+
+/**
+ * `count` random SNAFU numbers of up to `digits` digits each, one per line,
+ * for benchmarking. The same seed always gives the same numbers.
+ */
+pub fn random_snafu(count: usize, digits: usize, seed: u64) -> String {
+    // xorshift, as in day 23.
+    let mut state = seed | 1;
+    let mut random = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    let mut input = String::new();
+    for _ in 0..count {
+        let len = 1 + random() as usize % digits;
+        // No leading zeros, like the puzzle's numbers.
+        input.push(['1', '2'][random() as usize % 2]);
+        for _ in 1..len {
+            input.push(['=', '-', '0', '1', '2'][random() as usize % 5]);
+        }
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1=-0-2\n\
+                           12111\n\
+                           2=0=\n\
+                           21\n\
+                           2=01\n\
+                           111\n\
+                           20012\n\
+                           112\n\
+                           1=-1=\n\
+                           1-12\n\
+                           12\n\
+                           1=\n\
+                           122\n";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&generator(EXAMPLE).unwrap()), "2=-1=0");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let input = "1=-0-2\n12111\n2=0=\n2l\n2=01\n";
+        assert_eq!(
+            generator(input).err(),
+            Some(ParseError {
+                line: 4,
+                column: 2,
+                found: 'l',
+            })
+        );
+
+        // Only when asked for: skipping the bad line leaves 1747 + 906 + 198 + 201.
+        let total = sum_lines(input, Parsing::Lenient).unwrap();
+        assert_eq!(total.to_string(), "10-202");
+    }
+
+    fn decimal(snafu: &str) -> i64 {
+        snafu.bytes().fold(0, |total, byte| {
+            let digit = match byte {
+                b'=' => -2,
+                b'-' => -1,
+                digit => (digit - b'0') as i64,
+            };
+            5 * total + digit
+        })
+    }
+
+    #[test]
+    fn test_sums() {
+        let input = random_snafu(500, 20, 7);
+        let expected: i64 = input.lines().map(decimal).sum();
+
+        let total = part1(&generator(&input).unwrap());
+        assert_eq!(decimal(&total), expected);
+
+        let numbers: Vec<BalancedQuinary> =
+            input.lines().map(|line| line.parse().unwrap()).collect();
+        let by_ref: BalancedQuinary = numbers.iter().sum();
+        assert_eq!(String::from(by_ref), total);
+        let owned: BalancedQuinary = numbers.into_iter().sum();
+        assert_eq!(String::from(owned), total);
+    }
+
+    #[test]
+    fn test_add_assign() {
+        // The carry runs off the end of the shorter number, and then the longer one.
+        let mut number = BalancedQuinary::from_bytes(b"22").unwrap();
+        number += &BalancedQuinary::from_bytes(b"2").unwrap();
+        assert_eq!(String::from(number), "1=-");
+
+        let mut number = BalancedQuinary::from_bytes(b"1").unwrap();
+        assert!(number.read_bytes(b"1=3").is_err());
+        number += &BalancedQuinary::from_bytes(b"1-").unwrap();
+        assert_eq!(String::from(number), "1-");
+    }
+}
@@ -0,0 +1,415 @@
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::fmt;
+
+use itertools::Itertools;
+
+use crate::utils::input::{normalize, normalize_bytes};
+
+fn priority(item: u8) -> usize {
+    match item {
+        b'a'..=b'z' => (item - b'a' + 1) as usize,
+        b'A'..=b'Z' => (item - b'A' + 27) as usize,
+        _ => panic!("invalid char"),
+    }
+}
+
+/**
+ * A very simple bitset specialized for day 3.
+ *
+ * The i-th bit is 1 if a character with priority i has been
+ * added to the set, and is 0 otherwise.
+ */
+struct CharSet {
+    counts: u64,
+}
+
+impl CharSet {
+    fn new() -> Self {
+        Self { counts: 0 }
+    }
+
+    fn from(string: &str) -> Self {
+        let mut charset = Self::new();
+        charset.add(string);
+        charset
+    }
+
+    fn add(&mut self, string: &str) {
+        for b in string.bytes() {
+            self.counts |= 1 << priority(b);
+        }
+    }
+
+    /**
+     * Returns the priority of the (assumed-unique) character
+     * in the intersection of two CharSets, or None if there isn't one.
+     */
+    fn intersect(&self, other: &Self) -> Option<usize> {
+        let mut mask = 1;
+        for idx in 0..53 {
+            if (self.counts & mask > 0) && (other.counts & mask > 0) {
+                return Some(idx);
+            }
+            mask <<= 1;
+        }
+        None
+    }
+
+    /**
+     * Returns the priority of the (assumed-unique) character
+     * in the intersection of any number of CharSets, or None if
+     * there isn't one.
+     */
+    fn intersect_all(sets: &[CharSet]) -> Option<usize> {
+        if sets.is_empty() {
+            return None;
+        }
+
+        let common = sets.iter().fold(u64::MAX, |acc, set| acc & set.counts);
+        let mut mask = 1;
+        for idx in 0..53 {
+            if common & mask > 0 {
+                return Some(idx);
+            }
+            mask <<= 1;
+        }
+        None
+    }
+}
+
+/**
+ * Something wrong with a rucksack. Lines and columns are 1-indexed, to
+ * match what an editor shows.
+ */
+#[derive(Debug, PartialEq, Eq)]
+pub enum RucksackError {
+    // An item that isn't a letter, so has no priority.
+    InvalidItem {
+        line: usize,
+        column: usize,
+        found: char,
+    },
+    // An odd number of items, which can't be split into two compartments.
+    OddLength {
+        line: usize,
+        length: usize,
+    },
+    // A rucksack whose compartments have nothing in common.
+    NoCommonItem {
+        line: usize,
+    },
+    // A group of rucksacks with no item in every one of them. Groups are
+    // 1-indexed too.
+    NoBadge {
+        group: usize,
+    },
+}
+
+impl fmt::Display for RucksackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidItem {
+                line,
+                column,
+                found,
+            } => write!(f, "line {line}, column {column}: {found:?} isn't an item"),
+            Self::OddLength { line, length } => write!(
+                f,
+                "line {line}: {length} items can't be split into two compartments"
+            ),
+            Self::NoCommonItem { line } => {
+                write!(f, "line {line}: no item is in both compartments")
+            }
+            Self::NoBadge { group } => write!(f, "group {group}: no badge is in every rucksack"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RucksackError {}
+
+#[cfg(feature = "std")]
+impl From<RucksackError> for crate::error::AocError {
+    fn from(err: RucksackError) -> Self {
+        match err {
+            RucksackError::InvalidItem {
+                line,
+                column,
+                found,
+            } => crate::error::AocError::parse(
+                line,
+                format!("column {column}: {found:?} isn't an item"),
+            ),
+            RucksackError::OddLength { line, length } => crate::error::AocError::parse(
+                line,
+                format!("{length} items can't be split into two compartments"),
+            ),
+            RucksackError::NoCommonItem { line } => {
+                crate::error::AocError::parse(line, "no item is in both compartments")
+            }
+            // Not any one line's fault, so point at the group's first.
+            RucksackError::NoBadge { group } => crate::error::AocError::parse(
+                (group - 1) * 3 + 1,
+                format!("group {group}: no badge is in every rucksack"),
+            ),
+        }
+    }
+}
+
+/**
+ * Checks that every rucksack holds only letters, and splits evenly into
+ * two compartments.
+ */
+pub fn validate(input: &str) -> Result<(), RucksackError> {
+    rucksacks(normalize(input).as_bytes())
+        .enumerate()
+        .try_for_each(|(index, line)| validate_line(index + 1, line))
+}
+
+fn validate_line(line: usize, items: &[u8]) -> Result<(), RucksackError> {
+    // Everything before the first bad byte is a letter, so its index is
+    // also the column.
+    if let Some(column) = items.iter().position(|b| !b.is_ascii_alphabetic()) {
+        let found = String::from_utf8_lossy(&items[column..])
+            .chars()
+            .next()
+            .unwrap_or(char::REPLACEMENT_CHARACTER);
+        return Err(RucksackError::InvalidItem {
+            line,
+            column: column + 1,
+            found,
+        });
+    }
+    if !items.len().is_multiple_of(2) {
+        return Err(RucksackError::OddLength {
+            line,
+            length: items.len(),
+        });
+    }
+    Ok(())
+}
+
+/*
+ * The input for the parts below, checked first so that anything validate()
+ * rejects is reported rather than giving a wrong answer.
+ */
+fn validated(input: &str) -> Result<Cow<'_, str>, RucksackError> {
+    validate(input)?;
+    Ok(normalize(input))
+}
+
+#[cfg_attr(feature = "std", aoc(day3, part1))]
+pub fn part1(input: &str) -> Result<usize, RucksackError> {
+    let input = validated(input)?;
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            let compartment_size = line.len() / 2;
+            let compartment1 = &line[0..compartment_size];
+            let compartment2 = &line[compartment_size..];
+
+            let set1 = CharSet::from(compartment1);
+            let set2 = CharSet::from(compartment2);
+
+            set1.intersect(&set2)
+                .ok_or(RucksackError::NoCommonItem { line: index + 1 })
+        })
+        .sum()
+}
+
+/**
+ * Splits the rucksacks into groups of `group_size` consecutive lines and
+ * returns the priority of the badge (the one item common to every rucksack)
+ * for each group, or None for a group with no item in common.
+ */
+pub fn badge_priorities(
+    input: &str,
+    group_size: usize,
+) -> Result<Vec<Option<usize>>, RucksackError> {
+    Ok(validated(input)?
+        .lines()
+        .chunks(group_size)
+        .into_iter()
+        .map(|group| {
+            let charsets: Vec<CharSet> = group.map(CharSet::from).collect();
+            CharSet::intersect_all(&charsets)
+        })
+        .collect())
+}
+
+#[cfg_attr(feature = "std", aoc(day3, part2))]
+pub fn part2(input: &str) -> Result<usize, RucksackError> {
+    badge_priorities(input, 3)?
+        .iter()
+        .enumerate()
+        .map(|(group, badge)| badge.ok_or(RucksackError::NoBadge { group: group + 1 }))
+        .sum()
+}
+
+/*
+ * The `Masks` variants below do the same thing with less work per byte:
+ *
+ *   - each line stays a byte slice, with no UTF-8 checks or str slicing;
+ *   - priorities come from bit twiddling rather than a match. Both ranges
+ *     of letters end in the priority's low five bits (a = 97 = 0b1100001,
+ *     A = 65 = 0b1000001), and bit 5 is clear for exactly the uppercase
+ *     ones, which need another 26;
+ *   - each mask is built eight bytes at a time into separate accumulators,
+ *     so there's no dependency between the ORs within a chunk and the
+ *     compiler is free to vectorize the loop;
+ *   - the common item's priority is just the position of the lowest set
+ *     bit, instead of a scan over all 53.
+ */
+fn bit(item: u8) -> u64 {
+    let priority = (item & 31) + 26 * (((item >> 5) & 1) ^ 1);
+    1 << priority
+}
+
+fn mask(items: &[u8]) -> u64 {
+    let mut chunks = items.chunks_exact(8);
+    let mut masks = [0u64; 8];
+    for chunk in &mut chunks {
+        for (mask, &item) in masks.iter_mut().zip(chunk) {
+            *mask |= bit(item);
+        }
+    }
+    let remainder = chunks
+        .remainder()
+        .iter()
+        .fold(0, |mask, &item| mask | bit(item));
+    masks.iter().fold(remainder, |all, mask| all | mask)
+}
+
+/**
+ * The priority of the lowest item in a mask, or None if it's empty.
+ */
+fn lowest_priority(mask: u64) -> Option<usize> {
+    (mask != 0).then(|| mask.trailing_zeros() as usize)
+}
+
+/*
+ * The lines of the input, the same ones str::lines() would give.
+ */
+fn rucksacks(input: &[u8]) -> impl Iterator<Item = &[u8]> {
+    input
+        .split_inclusive(|&b| b == b'\n')
+        .map(|line| line.strip_suffix(b"\n").unwrap_or(line))
+}
+
+#[cfg_attr(feature = "std", aoc(day3, part1, Masks))]
+pub fn part1_masks(input: &[u8]) -> Result<usize, RucksackError> {
+    let input = normalize_bytes(input);
+    rucksacks(&input)
+        .enumerate()
+        .map(|(index, line)| {
+            validate_line(index + 1, line)?;
+            let (first, second) = line.split_at(line.len() / 2);
+            lowest_priority(mask(first) & mask(second))
+                .ok_or(RucksackError::NoCommonItem { line: index + 1 })
+        })
+        .sum()
+}
+
+#[cfg_attr(feature = "std", aoc(day3, part2, Masks))]
+pub fn part2_masks(input: &[u8]) -> Result<usize, RucksackError> {
+    let input = normalize_bytes(input);
+    let lines: Vec<&[u8]> = rucksacks(&input).collect();
+    for (index, line) in lines.iter().enumerate() {
+        validate_line(index + 1, line)?;
+    }
+    lines
+        .chunks(3)
+        .enumerate()
+        .map(|(group, lines)| {
+            let common = lines
+                .iter()
+                .fold(u64::MAX, |common, line| common & mask(line));
+            lowest_priority(common).ok_or(RucksackError::NoBadge { group: group + 1 })
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp\n\
+                           jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n\
+                           PmmdzqPrVvPwwTWBwg\n\
+                           wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\n\
+                           ttgJtRGJQctTZtZT\n\
+                           CrZsJsPPZsGzwwsLwLmpwMDw";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(EXAMPLE), Ok(157));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(EXAMPLE), Ok(70));
+    }
+
+    #[test]
+    fn test_masks() {
+        for item in (b'a'..=b'z').chain(b'A'..=b'Z') {
+            assert_eq!(bit(item), 1 << priority(item));
+        }
+        assert_eq!(mask(b"abcdefghijA"), ((1 << 11) - 2) | (1 << 27));
+
+        assert_eq!(part1_masks(EXAMPLE.as_bytes()), Ok(157));
+        assert_eq!(part2_masks(EXAMPLE.as_bytes()), Ok(70));
+    }
+
+    #[test]
+    fn test_badge_priorities() {
+        assert_eq!(badge_priorities(EXAMPLE, 3), Ok(vec![Some(18), Some(52)]));
+        assert_eq!(badge_priorities(EXAMPLE, 6), Ok(vec![None]));
+        assert_eq!(badge_priorities("abcc\nbcdd\ncdee", 3), Ok(vec![Some(3)]));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(validate(EXAMPLE), Ok(()));
+        assert_eq!(
+            validate("abAB\nab1B\n"),
+            Err(RucksackError::InvalidItem {
+                line: 2,
+                column: 3,
+                found: '1',
+            })
+        );
+        assert_eq!(
+            validate("abAB\nabcAB\n"),
+            Err(RucksackError::OddLength { line: 2, length: 5 })
+        );
+    }
+
+    #[test]
+    fn test_bad_input() {
+        for input in ["abAB\n", "aa\nabAB\n"] {
+            let line = input.lines().count();
+            assert_eq!(part1(input), Err(RucksackError::NoCommonItem { line }));
+            assert_eq!(
+                part1_masks(input.as_bytes()),
+                Err(RucksackError::NoCommonItem { line })
+            );
+        }
+
+        let invalid = Err(RucksackError::InvalidItem {
+            line: 2,
+            column: 2,
+            found: 'é',
+        });
+        assert_eq!(part1("aa\naébb\n"), invalid);
+        assert_eq!(part1_masks("aa\naébb\n".as_bytes()), invalid);
+        assert_eq!(part2("aa\naébb\n"), invalid);
+        assert_eq!(part2_masks("aa\naébb\n".as_bytes()), invalid);
+
+        let no_badge = Err(RucksackError::NoBadge { group: 2 });
+        let input = "aa\nab\nac\nbb\ncc\ndd\n";
+        assert_eq!(part2(input), no_badge);
+        assert_eq!(part2_masks(input.as_bytes()), no_badge);
+    }
+}
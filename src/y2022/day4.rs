@@ -0,0 +1,136 @@
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::BufRead;
+
+#[cfg(feature = "std")]
+use crate::error::AocError;
+use crate::utils::input::normalize;
+use crate::utils::interval::Interval;
+
+pub type Range = Interval<usize>;
+
+fn parse_range(s: &str) -> Option<Range> {
+    let (start, end) = s.split_once('-')?;
+    Some(Range {
+        start: start.parse().ok()?,
+        end: end.parse().ok()?,
+    })
+}
+
+fn parse_pair(line: &str) -> Option<(Range, Range)> {
+    let (elf1, elf2) = line.trim().split_once(',')?;
+    Some((parse_range(elf1)?, parse_range(elf2)?))
+}
+
+#[cfg_attr(feature = "std", aoc_generator(day4))]
+pub fn generator(input: &str) -> Vec<(Range, Range)> {
+    let input = normalize(input);
+    input
+        .lines()
+        .map(|line| parse_pair(line).unwrap())
+        .collect()
+}
+
+/**
+ * Reads pairs one line at a time, for input that's piped in rather than
+ * already in memory. Blank lines are skipped; anything else that isn't an
+ * "a-b,c-d" pair is reported as an error with its line number.
+ */
+#[cfg(feature = "std")]
+pub fn read_pairs<R: BufRead>(reader: R) -> impl Iterator<Item = Result<(Range, Range), AocError>> {
+    reader.lines().enumerate().filter_map(|(index, line)| {
+        let line = match line {
+            Ok(line) => line,
+            Err(err) => {
+                return Some(Err(AocError::Io {
+                    path: String::from("<input>"),
+                    message: err.to_string(),
+                }))
+            }
+        };
+        if line.trim().is_empty() {
+            return None;
+        }
+        Some(parse_pair(&line).ok_or_else(|| {
+            AocError::parse(index + 1, format!("expected a-b,c-d but got {line:?}"))
+        }))
+    })
+}
+
+/**
+ * Part 1, streamed: stops at the first line that can't be read or parsed.
+ */
+#[cfg(feature = "std")]
+pub fn count_containing<R: BufRead>(reader: R) -> Result<usize, AocError> {
+    read_pairs(reader).try_fold(0, |count, pair| {
+        let (elf1, elf2) = pair?;
+        Ok(count + usize::from(elf1.contains(&elf2) || elf2.contains(&elf1)))
+    })
+}
+
+/**
+ * Part 2, streamed.
+ */
+#[cfg(feature = "std")]
+pub fn count_overlapping<R: BufRead>(reader: R) -> Result<usize, AocError> {
+    read_pairs(reader).try_fold(0, |count, pair| {
+        let (elf1, elf2) = pair?;
+        Ok(count + usize::from(elf1.overlaps(&elf2)))
+    })
+}
+
+#[cfg_attr(feature = "std", aoc(day4, part1))]
+pub fn part1(input: &[(Range, Range)]) -> usize {
+    input
+        .iter()
+        .filter(|(elf1, elf2)| elf1.contains(elf2) || elf2.contains(elf1))
+        .count()
+}
+
+#[cfg_attr(feature = "std", aoc(day4, part2))]
+pub fn part2(input: &[(Range, Range)]) -> usize {
+    input
+        .iter()
+        .filter(|(elf1, elf2)| elf1.overlaps(elf2))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "std")]
+    use super::{count_containing, count_overlapping};
+    use super::{generator, part1, part2};
+    #[cfg(feature = "std")]
+    use crate::error::AocError;
+
+    const EXAMPLE: &str = "2-4,6-8\n\
+                           2-3,4-5\n\
+                           5-7,7-9\n\
+                           2-8,3-7\n\
+                           6-6,4-6\n\
+                           2-6,4-8";
+
+    #[test]
+    fn test_part1() {
+        let input = generator(EXAMPLE);
+        assert_eq!(part1(&input), 2);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = generator(EXAMPLE);
+        assert_eq!(part2(&input), 4);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_streaming() {
+        assert_eq!(count_containing(EXAMPLE.as_bytes()), Ok(2));
+        assert_eq!(count_overlapping(EXAMPLE.as_bytes()), Ok(4));
+
+        assert_eq!(
+            count_overlapping("2-4,6-8\n\n2-3;4-5\n".as_bytes()),
+            Err(AocError::parse(3, "expected a-b,c-d but got \"2-3;4-5\""))
+        );
+    }
+}
@@ -0,0 +1,432 @@
+use std::{collections::VecDeque, fmt::Display, ops::Range};
+
+use crate::error::AocError;
+use crate::utils::input::normalize;
+use crate::utils::parse::{blocks, ints};
+
+/**
+ * Holds the state of the stacks of crates.
+ *
+ * `stacks[i]` contains the crates in the i-th stack, with
+ * the bottom crate in stacks[i][0].
+ *
+ * The problem statement uses 1-indexing for the stacks; we
+ * convert this to 0-indexing at parse time. So "Stack 1"
+ * in the problem statement is stacks[0] here.
+ */
+#[derive(Clone)]
+pub struct State {
+    stacks: Vec<VecDeque<char>>,
+}
+
+/**
+ * A single step in the crate-rearrangement procedure.
+ *
+ * The stack indices are 0-based here as well.
+ */
+#[derive(Clone)]
+pub struct Step {
+    count: usize,
+    from: usize,
+    to: usize,
+}
+
+impl State {
+    /**
+     * Applies a single step ("move N from A to B") to the state.
+     *
+     * The third parameter specifies whether to reverse the order of the crates
+     * before adding them to their new stack. This lets us use one function to
+     * handle both parts of the problem: "move 3 crates, 1 at a time" is
+     * equivalent to "get three crates, reverse their order, and append them".
+     */
+    fn apply(&mut self, step: &Step, reverse: bool) {
+        let mut crates = self.remove_crates(step.from, step.count);
+
+        if reverse {
+            crates.reverse();
+        }
+
+        self.add_crates(step.to, crates);
+    }
+
+    /**
+     * Adds a list of crates to the top of a stack.
+     */
+    fn add_crates(&mut self, stack: usize, crates: Vec<char>) {
+        self.stacks[stack].extend(crates.iter())
+    }
+
+    /**
+     * Removes crates from a stack, returning the removed crates in a list.
+     */
+    fn remove_crates(&mut self, stack: usize, count: usize) -> Vec<char> {
+        let initial_len = self.stacks[stack].len();
+        self.stacks[stack].drain(initial_len - count..).collect()
+    }
+
+    /**
+     * Draws the stacks the same way as the puzzle input: see Diagram.
+     */
+    pub fn diagram(&self) -> Diagram<'_> {
+        Diagram(self)
+    }
+
+    /**
+     * Returns a string containing the letters of the crates at the top
+     * of each stack in order.
+     */
+    fn top_crates(&self) -> String {
+        self.stacks
+            .iter()
+            .map(|stack| stack.back().unwrap())
+            .collect::<String>()
+    }
+}
+
+impl Display for State {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        for i in 0..self.stacks.len() {
+            // Add 1 to switch back to 1-indexing
+            write!(f, "{}:", i + 1)?;
+            for krate in &self.stacks[i] {
+                write!(f, " {}", krate)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/**
+ * Displays a State in the puzzle's format, with a bracketed crate (or
+ * blanks) for each stack on every row and the stack numbers underneath.
+ * Like the input, every line is the full width, with no newline at the end.
+ */
+pub struct Diagram<'a>(&'a State);
+
+impl Display for Diagram<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let stacks = &self.0.stacks;
+        let height = stacks.iter().map(|stack| stack.len()).max().unwrap_or(0);
+
+        for row in (0..height).rev() {
+            for (i, stack) in stacks.iter().enumerate() {
+                if i > 0 {
+                    write!(f, " ")?;
+                }
+                match stack.get(row) {
+                    Some(krate) => write!(f, "[{}]", krate)?,
+                    None => write!(f, "   ")?,
+                }
+            }
+            writeln!(f)?;
+        }
+
+        for i in 0..stacks.len() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            // Two-digit numbers start above the crates' letters, like a
+            // single digit, and run into the space after them.
+            write!(f, " {:<2}", i + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        // Remember to switch back to 1-indexing.
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.count,
+            self.from + 1,
+            self.to + 1
+        )
+    }
+}
+
+/**
+ * The stack numbers in the drawing's last line, and the columns each one
+ * covers.
+ */
+fn stack_labels(footer: &str) -> Vec<(usize, Range<usize>)> {
+    let mut labels = Vec::new();
+    let mut start = None;
+    // The extra space at the end finishes off a number at the end of the line.
+    for (idx, c) in footer.char_indices().chain([(footer.len(), ' ')]) {
+        match (c.is_ascii_digit(), start) {
+            (true, None) => start = Some(idx),
+            (false, Some(from)) => {
+                let label = footer[from..idx].parse().unwrap();
+                labels.push((label, from..idx));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    labels
+}
+
+/**
+ * Reads the drawing of the stacks at the start of the input.
+ */
+pub fn parse_state(drawing: &str) -> Result<State, AocError> {
+    let mut lines: Vec<&str> = drawing.lines().collect();
+
+    // The stack numbers on the last line tell us how many stacks there are,
+    // even if some of them are empty, and which stack each crate is in: the
+    // one whose number is underneath it. Every line above is crates.
+    let footer = lines.pop().unwrap_or("");
+    let labels = stack_labels(footer);
+    let count = labels.iter().map(|(label, _)| *label).max().unwrap_or(0);
+    let mut state = State {
+        stacks: vec![VecDeque::new(); count],
+    };
+
+    for (row, line) in lines.into_iter().enumerate() {
+        // Find all of the letters in this row, and their indices.
+        for (idx, letter) in line.match_indices(|c: char| c.is_ascii_uppercase()) {
+            // For each letter, find the stack number under it.
+            let Some((label, _)) = labels.iter().find(|(_, columns)| columns.contains(&idx)) else {
+                return Err(AocError::parse(
+                    row + 1,
+                    format!(
+                        "crate {letter} (column {}) isn't above a stack number",
+                        idx + 1
+                    ),
+                ));
+            };
+
+            // it is unbelievable that this could be the simplest way to get the first char of a str.
+            state.stacks[label - 1].push_front(letter.chars().next().unwrap());
+        }
+    }
+
+    Ok(state)
+}
+
+/**
+ * The inverse of parse_state().
+ */
+pub fn render_state(state: &State) -> String {
+    state.diagram().to_string()
+}
+
+#[aoc_generator(day5)]
+pub fn generator(input: &str) -> Result<(State, Vec<Step>), AocError> {
+    let input = normalize(input);
+    let mut sections = blocks(&input);
+    let drawing = sections
+        .next()
+        .ok_or_else(|| AocError::parse(1, "there's no drawing of the stacks"))?;
+    let moves = sections.next().unwrap_or("");
+
+    // The first section of the input contains the initial state.
+    let state = parse_state(drawing)?;
+
+    // The rest of the lines include the steps to follow, after the drawing
+    // and the blank line under it.
+    let first_step = drawing.lines().count() + 2;
+    let stacks = 1..=state.stacks.len();
+    let steps = moves
+        .lines()
+        .enumerate()
+        .map(|(row, line)| {
+            let error = |message: String| AocError::parse(first_step + row, message);
+            let [count, from, to] = ints(line)[..] else {
+                return Err(error(format!("unexpected step: {line}")));
+            };
+            if !stacks.contains(&from) || !stacks.contains(&to) {
+                return Err(error(format!("no such stack in step: {line}")));
+            }
+
+            // Create a new Step object. Subtract 1 from the stack indicies
+            // to correct for AoC's 1-indexing.
+            Ok(Step {
+                count,
+                from: from - 1,
+                to: to - 1,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((state, steps))
+}
+
+/**
+ * The two cranes from the puzzle: the CrateMover 9000 (part 1) moves one
+ * crate at a time, and the 9001 (part 2) moves several at once.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Crane {
+    CrateMover9000,
+    CrateMover9001,
+}
+
+/**
+ * Plays back the procedure, yielding the state after each move the crane
+ * makes: every single crate for the 9000, or every step for the 9001.
+ * Each state's diagram() can be printed as a frame of an animation.
+ */
+pub fn animate<'a>(
+    state: &State,
+    steps: &'a [Step],
+    crane: Crane,
+) -> impl Iterator<Item = State> + 'a {
+    let moves = steps.iter().flat_map(move |step| {
+        let (count, repeats) = match crane {
+            Crane::CrateMover9000 => (1, step.count),
+            Crane::CrateMover9001 => (step.count, 1),
+        };
+        std::iter::repeat_n(Step { count, ..*step }, repeats)
+    });
+
+    moves.scan(state.clone(), |state, step| {
+        state.apply(&step, false);
+        Some(state.clone())
+    })
+}
+
+#[aoc(day5, part1)]
+pub fn part1((input_state, steps): &(State, Vec<Step>)) -> String {
+    let mut state = input_state.clone();
+
+    for step in steps {
+        state.apply(step, true);
+    }
+
+    state.top_crates()
+}
+
+#[aoc(day5, part2)]
+pub fn part2((input_state, steps): &(State, Vec<Step>)) -> String {
+    let mut state = input_state.clone();
+
+    for step in steps {
+        state.apply(step, false);
+    }
+
+    state.top_crates()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Spelled out line by line, since a line continuation would eat the
+    // space at the start of the stack numbers.
+    const EXAMPLE: &str = concat!(
+        "    [D]    \n",
+        "[N] [C]    \n",
+        "[Z] [M] [P]\n",
+        " 1   2   3 \n",
+        "\n",
+        "move 1 from 2 to 1\n",
+        "move 3 from 1 to 3\n",
+        "move 2 from 2 to 1\n",
+        "move 1 from 1 to 2",
+    );
+
+    #[test]
+    fn test_part1() {
+        let input = generator(EXAMPLE).unwrap();
+        assert_eq!(part1(&input), "CMZ".to_string());
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = generator(EXAMPLE).unwrap();
+        assert_eq!(part2(&input), String::from("MCD"));
+    }
+
+    #[test]
+    fn test_render_state() {
+        let (drawing, _) = EXAMPLE.split_once("\n\n").unwrap();
+        let mut state = parse_state(drawing).unwrap();
+        assert_eq!(render_state(&state), drawing);
+
+        // The first two steps of the example, which empty the first stack.
+        let (_, steps) = generator(EXAMPLE).unwrap();
+        state.apply(&steps[0], true);
+        state.apply(&steps[1], true);
+        let expected = [
+            "        [Z]",
+            "        [N]",
+            "    [C] [D]",
+            "    [M] [P]",
+            " 1   2   3 ",
+        ]
+        .join("\n");
+        assert_eq!(state.diagram().to_string(), expected);
+        assert_eq!(render_state(&parse_state(&expected).unwrap()), expected);
+    }
+
+    #[test]
+    fn test_many_stacks() {
+        // Eleven stacks, so two-digit stack numbers, with the tallest
+        // ones at the end.
+        let mut state = State {
+            stacks: ('A'..='K').map(|krate| VecDeque::from([krate])).collect(),
+        };
+        state.stacks[9].push_back('X');
+        state.stacks[10].extend(['Y', 'Z']);
+
+        let drawing = state.diagram().to_string();
+        assert!(drawing.ends_with(" 9   10  11"));
+        assert_eq!(render_state(&parse_state(&drawing).unwrap()), drawing);
+
+        let input = format!("{drawing}\n\nmove 2 from 11 to 10\nmove 1 from 10 to 1\n");
+        let input = generator(&input).unwrap();
+        assert_eq!(part1(&input), "YBCDEFGHIZK");
+        assert_eq!(part2(&input), "ZBCDEFGHIYK");
+    }
+
+    #[test]
+    fn test_unindented_crates() {
+        // Crates in the very first column, as long as the numbers line up.
+        let state = parse_state("A   \nB  C\n1  2").unwrap();
+        assert_eq!(state.top_crates(), "AC");
+    }
+
+    #[test]
+    fn test_bad_input() {
+        assert_eq!(
+            parse_state("    [D]\n[N] [C]\n1   2").err(),
+            Some(AocError::parse(
+                1,
+                "crate D (column 6) isn't above a stack number"
+            ))
+        );
+        assert_eq!(
+            generator("[A]\n 1 \n\nmove 1 from 1 to 2\n").err(),
+            Some(AocError::parse(
+                4,
+                "no such stack in step: move 1 from 1 to 2"
+            ))
+        );
+        assert_eq!(
+            generator("[A]\n 1 \n\nmove 1 from 1\n").err(),
+            Some(AocError::parse(4, "unexpected step: move 1 from 1"))
+        );
+    }
+
+    #[test]
+    fn test_animate() {
+        let (state, steps) = generator(EXAMPLE).unwrap();
+
+        // One frame per crate moved: 1 + 3 + 2 + 1.
+        let frames: Vec<State> = animate(&state, &steps, Crane::CrateMover9000).collect();
+        assert_eq!(frames.len(), 7);
+        assert_eq!(
+            frames[6].top_crates(),
+            part1(&(state.clone(), steps.clone()))
+        );
+        assert!(frames[0].diagram().to_string().starts_with("[D]        \n"));
+
+        let frames: Vec<State> = animate(&state, &steps, Crane::CrateMover9001).collect();
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[3].top_crates(), "MCD");
+    }
+}
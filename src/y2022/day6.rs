@@ -0,0 +1,166 @@
+use crate::error::AocError;
+use crate::utils::input::normalize_bytes;
+
+// The obvious way to approach this problem is via hashsets.
+//
+// The simplest (and least efficient) is to create a hashset for each
+// slice of `len` bytes and see whether its len() == len.
+//
+// Or, better, you can use a hashmap from byte -> count_in_window, and
+// add/remove bytes as your sliding window moves.
+//
+// The following implements the latter, but without a real hashmap.
+// Instead our "hash" is just h(b) = b - b'a', and we store the number
+// of unique items in a separate variable for efficiency.
+//
+// It assumes that only lowercase alphabetical characters will be added,
+// and that a character will be added at most 255 times.
+struct CountingCharSet {
+    counts: [u8; 26],
+    unique: usize,
+}
+
+impl CountingCharSet {
+    fn new() -> Self {
+        CountingCharSet {
+            counts: [0u8; 26],
+            unique: 0,
+        }
+    }
+
+    fn add(&mut self, char: u8) {
+        let idx = usize::from(char - b'a');
+        if self.counts[idx] == 0 {
+            self.unique += 1;
+        }
+        self.counts[idx] += 1;
+    }
+
+    fn remove(&mut self, char: u8) {
+        let idx = usize::from(char - b'a');
+        self.counts[idx] -= 1;
+        if self.counts[idx] == 0 {
+            self.unique -= 1;
+        }
+    }
+}
+
+/**
+ * The number of bytes read by the end of the first run of `len` distinct
+ * bytes, or None if there isn't one (including when there are fewer than
+ * `len` bytes altogether).
+ */
+pub fn find_marker(len: usize, data: &[u8]) -> Option<usize> {
+    if data.len() < len {
+        return None;
+    }
+
+    let mut set = CountingCharSet::new();
+
+    // Start by inserting the first `len` items.
+    for char in &data[..len] {
+        set.add(*char);
+    }
+    if set.unique == len {
+        return Some(len);
+    }
+
+    // Slide along until the charset contains `len` unique items.
+    for i in len..data.len() {
+        set.remove(data[i - len]);
+        set.add(data[i]);
+        if set.unique == len {
+            return Some(i + 1);
+        }
+    }
+
+    None
+}
+
+/*
+ * The datastream is the input's only line, without the newline. Anything
+ * other than lowercase letters is an error, since CountingCharSet can't
+ * count it.
+ */
+fn datastream(input: &[u8]) -> Result<&[u8], AocError> {
+    let data = input.strip_suffix(b"\n").unwrap_or(input);
+    match data.iter().find(|b| !b.is_ascii_lowercase()) {
+        Some(&b) => Err(AocError::parse(
+            1,
+            format!("unexpected {:?} in the datastream", char::from(b)),
+        )),
+        None => Ok(data),
+    }
+}
+
+#[aoc(day6, part1, Bytes)]
+pub fn part1(input: &[u8]) -> Result<usize, AocError> {
+    let input = normalize_bytes(input);
+    find_marker(4, datastream(&input)?)
+        .ok_or_else(|| AocError::parse(1, "no start-of-packet marker in the datastream"))
+}
+
+#[aoc(day6, part2, Bytes)]
+pub fn part2(input: &[u8]) -> Result<usize, AocError> {
+    let input = normalize_bytes(input);
+    find_marker(14, datastream(&input)?)
+        .ok_or_else(|| AocError::parse(1, "no start-of-message marker in the datastream"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_marker, part1, part2};
+    use crate::error::AocError;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(b"bvwbjplbgvbhsrlpgdmjqwftvncz"), Ok(5));
+        assert_eq!(part1(b"nppdvjthqldpwncqszvftbrmjlhg"), Ok(6));
+        assert_eq!(part1(b"nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), Ok(10));
+        assert_eq!(part1(b"zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), Ok(11));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(b"mjqjpqmgbljsphdztnvjfqwrcgsmlb"), Ok(19));
+        assert_eq!(part2(b"bvwbjplbgvbhsrlpgdmjqwftvncz"), Ok(23));
+        assert_eq!(part2(b"nppdvjthqldpwncqszvftbrmjlhg"), Ok(23));
+        assert_eq!(part2(b"nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), Ok(29));
+        assert_eq!(part2(b"zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), Ok(26));
+    }
+
+    #[test]
+    fn test_no_marker() {
+        // Shorter than the window.
+        assert_eq!(find_marker(4, b""), None);
+        assert_eq!(find_marker(4, b"abc"), None);
+        assert_eq!(find_marker(4, b"abcd"), Some(4));
+
+        // Long enough, but never four different letters in a row.
+        assert_eq!(find_marker(4, b"abcabcabcabc"), None);
+        assert_eq!(find_marker(14, b"bvwbjplbgvbhsrlpgdmjqwftvncz"), Some(23));
+        assert_eq!(find_marker(14, b"abcdefghijklm"), None);
+    }
+
+    #[test]
+    fn test_parts_without_marker() {
+        assert_eq!(
+            part1(b"aaaaaaaaaa"),
+            Err(AocError::parse(
+                1,
+                "no start-of-packet marker in the datastream"
+            ))
+        );
+        assert_eq!(
+            part2(b"abcdefghijklm\n"),
+            Err(AocError::parse(
+                1,
+                "no start-of-message marker in the datastream"
+            ))
+        );
+        assert_eq!(
+            part1(b"abcD"),
+            Err(AocError::parse(1, "unexpected 'D' in the datastream"))
+        );
+    }
+}
@@ -0,0 +1,398 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+
+use crate::error::AocError;
+use crate::utils::input::normalize;
+
+pub enum Node {
+    File(usize),
+    Directory(HashMap<String, Node>),
+}
+
+impl Node {
+    fn new_directory() -> Self {
+        Self::Directory(HashMap::new())
+    }
+
+    fn new_file(size: usize) -> Self {
+        Self::File(size)
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Self::File(size) => *size,
+            Self::Directory(contents) => contents.values().map(|elt| elt.size()).sum(),
+        }
+    }
+
+    /**
+     * Finds the node at `path`, or None if part of it hasn't been listed
+     * or is a file.
+     */
+    fn resolve(&mut self, path: &[String]) -> Option<&mut Self> {
+        match path.first() {
+            None => Some(self),
+            Some(component) => match self {
+                Self::File(_) => None,
+                Self::Directory(contents) => contents.get_mut(component)?.resolve(&path[1..]),
+            },
+        }
+    }
+
+    fn iter(&self) -> NodeIterator<'_> {
+        let mut queue = VecDeque::new();
+        queue.push_back(self);
+        NodeIterator { queue }
+    }
+}
+
+struct NodeIterator<'a> {
+    // We're iterating over a tree. Do a breadth-first traversal.
+    queue: VecDeque<&'a Node>,
+}
+
+impl<'a> Iterator for NodeIterator<'a> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.queue.pop_front();
+        if let Some(Node::Directory(children)) = next {
+            self.queue.extend(children.values());
+        }
+        next
+    }
+}
+
+/**
+ * Renders the tree in the same format as the puzzle's worked example
+ * (one "- name (type)" line per entry, indented two spaces per level),
+ * except that directories also show their total size. Entries are
+ * sorted by name.
+ */
+pub fn render_tree(root: &Node) -> String {
+    let mut out = String::new();
+    render_node(root, "/", 0, &mut out);
+    out
+}
+
+fn render_node(node: &Node, name: &str, depth: usize, out: &mut String) {
+    let indent = "  ".repeat(depth);
+    match node {
+        Node::File(size) => {
+            writeln!(out, "{indent}- {name} (file, size={size})").unwrap();
+        }
+        Node::Directory(children) => {
+            writeln!(out, "{indent}- {name} (dir, size={})", node.size()).unwrap();
+
+            let mut names: Vec<&String> = children.keys().collect();
+            names.sort();
+            for child in names {
+                render_node(&children[child], child, depth + 1, out);
+            }
+        }
+    }
+}
+
+/**
+ * Returns the full path and total size of every directory, largest first,
+ * like `du | sort -rn`.
+ */
+pub fn largest_directories(root: &Node) -> Vec<(String, usize)> {
+    let mut sizes = Vec::new();
+    collect_directory_sizes(root, String::from("/"), &mut sizes);
+    sizes.sort_by(|(a_path, a_size), (b_path, b_size)| {
+        b_size.cmp(a_size).then_with(|| a_path.cmp(b_path))
+    });
+    sizes
+}
+
+fn collect_directory_sizes(node: &Node, path: String, sizes: &mut Vec<(String, usize)>) {
+    let Node::Directory(children) = node else {
+        return;
+    };
+
+    for (name, child) in children {
+        let child_path = if path == "/" {
+            format!("/{name}")
+        } else {
+            format!("{path}/{name}")
+        };
+        collect_directory_sizes(child, child_path, sizes);
+    }
+    sizes.push((path, node.size()));
+}
+
+/**
+ * Adds one line of `ls` output to a directory's contents.
+ *
+ * The same directory can be listed more than once, so entries that are
+ * already there are merged rather than replaced: listing a directory again
+ * keeps whatever was found inside it, and listing a file again is fine as
+ * long as its size hasn't changed. Anything else (a different size, or a
+ * file and a directory with the same name) is an error.
+ */
+fn add_entry(
+    children: &mut HashMap<String, Node>,
+    entry: &str,
+    line: usize,
+) -> Result<(), AocError> {
+    let (kind, name) = entry
+        .split_once(' ')
+        .ok_or_else(|| AocError::parse(line, format!("unexpected ls entry {entry:?}")))?;
+
+    let node = if kind == "dir" {
+        Node::new_directory()
+    } else {
+        let size = kind
+            .parse()
+            .map_err(|_| AocError::parse(line, format!("unexpected ls entry {entry:?}")))?;
+        Node::new_file(size)
+    };
+
+    let Some(existing) = children.get(name) else {
+        children.insert(String::from(name), node);
+        return Ok(());
+    };
+    match (existing, &node) {
+        (Node::Directory(_), Node::Directory(_)) => Ok(()),
+        (Node::File(old), Node::File(new)) if old == new => Ok(()),
+        (Node::File(old), Node::File(new)) => Err(AocError::parse(
+            line,
+            format!("{name} was listed with size {old}, but now has size {new}"),
+        )),
+        _ => Err(AocError::parse(
+            line,
+            format!("{name} was listed as both a file and a directory"),
+        )),
+    }
+}
+
+#[aoc_generator(day7)]
+pub fn generator(input: &str) -> Result<Node, AocError> {
+    let input = normalize(input);
+    let mut root = Node::new_directory();
+    let mut path: Vec<String> = vec![];
+
+    let mut lines = input.lines().enumerate().peekable();
+    while let Some((index, line)) = lines.by_ref().next() {
+        // By construction, each line should start with a command.
+        if line == "$ ls" {
+            // cd only ever moves into directories, so this is one.
+            let Some(Node::Directory(children)) = root.resolve(&path) else {
+                return Err(AocError::parse(index + 1, "ls outside of a directory"));
+            };
+
+            // Loop until we find either the end of input, or another command
+            while let Some((index, entry)) = lines.next_if(|(_, line)| !line.starts_with("$ ")) {
+                add_entry(children, entry, index + 1)?;
+            }
+        } else if let Some(dir) = line.strip_prefix("$ cd") {
+            match dir.strip_prefix(' ') {
+                Some("..") => {
+                    path.pop();
+                }
+                Some("/") => {
+                    path = vec![];
+                }
+                Some(dir) if !dir.is_empty() => {
+                    path.push(String::from(dir));
+                    if !matches!(root.resolve(&path), Some(Node::Directory(_))) {
+                        return Err(AocError::parse(
+                            index + 1,
+                            format!("cannot cd into {dir:?}: no such directory has been listed"),
+                        ));
+                    }
+                }
+                _ => return Err(AocError::parse(index + 1, "cd without a directory")),
+            }
+        } else {
+            return Err(AocError::parse(
+                index + 1,
+                format!("unknown command {line:?}"),
+            ));
+        }
+    }
+
+    Ok(root)
+}
+
+#[aoc(day7, part1)]
+pub fn part1(root: &Node) -> usize {
+    root.iter()
+        .map(|node| match node {
+            Node::File(_) => 0,
+            Node::Directory(_) => {
+                let size = node.size();
+                if size < 100000 {
+                    size
+                } else {
+                    0
+                }
+            }
+        })
+        .sum()
+}
+
+#[aoc(day7, part2)]
+pub fn part2(root: &Node) -> usize {
+    const TOTAL_SIZE: usize = 70000000;
+    const TARGET_SIZE: usize = 30000000;
+
+    // Do one pass to get the total size of all files on the system.
+    let used_size: usize = root
+        .iter()
+        .map(|node| match node {
+            Node::File(size) => *size,
+            Node::Directory(_) => 0,
+        })
+        .sum();
+
+    // We need to delete at least this much data
+    let target_delete = TARGET_SIZE - (TOTAL_SIZE - used_size);
+
+    root.iter()
+        .map(|node| match node {
+            Node::File(_) => 0,
+            Node::Directory(_) => node.size(),
+        })
+        .filter(|size| *size >= target_delete)
+        .min()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generator, largest_directories, part1, part2, render_tree};
+    use crate::error::AocError;
+
+    const EXAMPLE: &str = "$ cd /\n\
+                           $ ls\n\
+                           dir a\n\
+                           14848514 b.txt\n\
+                           8504156 c.dat\n\
+                           dir d\n\
+                           $ cd a\n\
+                           $ ls\n\
+                           dir e\n\
+                           29116 f\n\
+                           2557 g\n\
+                           62596 h.lst\n\
+                           $ cd e\n\
+                           $ ls\n\
+                           584 i\n\
+                           $ cd ..\n\
+                           $ cd ..\n\
+                           $ cd d\n\
+                           $ ls\n\
+                           4060174 j\n\
+                           8033020 d.log\n\
+                           5626152 d.ext\n\
+                           7214296 k";
+
+    #[test]
+    fn test_part1() {
+        let input = generator(EXAMPLE).unwrap();
+        assert_eq!(part1(&input), 95437);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = generator(EXAMPLE).unwrap();
+        assert_eq!(part2(&input), 24933642);
+    }
+
+    #[test]
+    fn test_render_tree() {
+        let input = generator(EXAMPLE).unwrap();
+        let expected = "- / (dir, size=48381165)\n\
+                        \x20 - a (dir, size=94853)\n\
+                        \x20   - e (dir, size=584)\n\
+                        \x20     - i (file, size=584)\n\
+                        \x20   - f (file, size=29116)\n\
+                        \x20   - g (file, size=2557)\n\
+                        \x20   - h.lst (file, size=62596)\n\
+                        \x20 - b.txt (file, size=14848514)\n\
+                        \x20 - c.dat (file, size=8504156)\n\
+                        \x20 - d (dir, size=24933642)\n\
+                        \x20   - d.ext (file, size=5626152)\n\
+                        \x20   - d.log (file, size=8033020)\n\
+                        \x20   - j (file, size=4060174)\n\
+                        \x20   - k (file, size=7214296)\n";
+        assert_eq!(render_tree(&input), expected);
+    }
+
+    #[test]
+    fn test_largest_directories() {
+        let input = generator(EXAMPLE).unwrap();
+        assert_eq!(
+            largest_directories(&input),
+            vec![
+                ("/".to_string(), 48381165),
+                ("/d".to_string(), 24933642),
+                ("/a".to_string(), 94853),
+                ("/a/e".to_string(), 584),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repeated_listings() {
+        // Listing / again keeps everything found inside a and d.
+        let input = format!("{EXAMPLE}\n$ cd /\n$ ls\ndir a\n14848514 b.txt\n8504156 c.dat\ndir d");
+        let root = generator(&input).unwrap();
+        assert_eq!(part1(&root), 95437);
+        assert_eq!(part2(&root), 24933642);
+
+        assert_eq!(
+            generator("$ cd /\n$ ls\n10 a\n$ ls\n20 a").err(),
+            Some(AocError::parse(
+                5,
+                "a was listed with size 10, but now has size 20"
+            ))
+        );
+        assert_eq!(
+            generator("$ cd /\n$ ls\ndir a\n$ ls\n10 a").err(),
+            Some(AocError::parse(
+                5,
+                "a was listed as both a file and a directory"
+            ))
+        );
+        assert_eq!(
+            generator("$ cd /\n$ rm -rf a").err(),
+            Some(AocError::parse(2, "unknown command \"$ rm -rf a\""))
+        );
+    }
+
+    #[test]
+    fn test_bad_input() {
+        assert_eq!(
+            generator("$ cd /\n$ cd").err(),
+            Some(AocError::parse(2, "cd without a directory"))
+        );
+        assert_eq!(
+            generator("$ cd /\n$ cd ").err(),
+            Some(AocError::parse(2, "cd without a directory"))
+        );
+        assert_eq!(
+            generator("$ cd /\n$ cd a").err(),
+            Some(AocError::parse(
+                2,
+                "cannot cd into \"a\": no such directory has been listed"
+            ))
+        );
+        assert_eq!(
+            generator("$ cd /\n$ ls\n10 a\n$ cd a\n$ ls\n20 b").err(),
+            Some(AocError::parse(
+                4,
+                "cannot cd into \"a\": no such directory has been listed"
+            ))
+        );
+        assert_eq!(
+            generator("$ cd /\n$ ls\n10 a\n$ ls\ndir a").err(),
+            Some(AocError::parse(
+                5,
+                "a was listed as both a file and a directory"
+            ))
+        );
+    }
+}
@@ -0,0 +1,327 @@
+use take_until::TakeUntilExt;
+
+use crate::error::AocError;
+use crate::utils::heatmap;
+use crate::utils::input::normalize;
+
+/**
+ * I'm going to make an iNTeResTInG choice and represent the 2-d grid of
+ * tree heights with a 1-d array. This has a lot of disadvantages, but
+ * allows one neat trick: it makes it trivial to create zero-copy iterators
+ * over the columns of the grid.
+ *
+ * So, for a major sacrifice in readability (see the impls below), we get to
+ * handle every computation in an iterator for maximum ~~functionality~~!
+ */
+pub struct TreeGrid {
+    height: usize,
+    width: usize,
+    values: Vec<u32>,
+}
+
+impl TreeGrid {
+    // Convenience function for translating from 2-d coordinates to our flat array.
+    fn at(&self, row: usize, col: usize) -> u32 {
+        self.values[self.width * row + col]
+    }
+
+    /*
+     * The following somewhat-incomprehensible functions define iterators over the
+     * grid elements you'd encounter by starting at (row, col) and walking in one direction.
+     *
+     * For example, say your grid looks like this:
+     *      30373
+     *      25512
+     *      65332
+     *      33549
+     *      35390
+     *
+     * If you start at, say, the 4 in the second-to-last row (i.e., at row 3, column 3), then
+     * the items you'll see in each direction are, in order:
+     *   * above: [3, 1, 7]
+     *   * below: [9]
+     *   * left:  [5, 3, 3]
+     *   * right: [9]
+     *
+     * Note that the 'left' and 'above' lists might be reversed from what you'd expect
+     * when looking at the grid!
+     */
+
+    /*
+     * For above() and below(), it's helpful to re-label the grid with the index
+     * of each point in the flat array used for storage:
+     *       0  1  2  3  4
+     *       5  6  7  8  9
+     *      10 11 12 13 14
+     *      15 16 17 18 19
+     *      20 21 22 23 24
+     *
+     * Say we're starting at row = 3, col = 3, which is index 3 * 5 + 3 = 18.
+     *
+     * To get the items above it, we:
+     *   1. Take all of the items up to and including the starting point:
+     *         [0, 1, 2, ..., 16, 17, 18]
+     *   2. Reverse the list (since we'll be walking "up" the grid):
+     *         [18, 17, 16, ..., 2, 1, 0]
+     *   3. Take every (self.width)-th element. This is equivalent to moving up 1 row:
+     *         [18, 13, 8, 3]
+     *   4. Drop the first element, which is the starting point:
+     *         [13, 8, 3]
+     *
+     * Getting the items below is basically the same, except we grab the items *starting*
+     * at the starting point, and don't need to reverse.
+     */
+    fn above(&self, row: usize, col: usize) -> impl Iterator<Item = &u32> + '_ {
+        let start_idx = self.width * row + col;
+        self.values
+            .iter()
+            .take(start_idx + 1)
+            .rev()
+            .step_by(self.width)
+            .skip(1)
+    }
+
+    fn below(&self, row: usize, col: usize) -> impl Iterator<Item = &u32> + '_ {
+        let start_idx = self.width * row + col;
+        self.values
+            .iter()
+            .skip(start_idx)
+            .step_by(self.width)
+            .skip(1)
+    }
+
+    /**
+     * left() and right() are much simpler as they operate on a single row.
+     *
+     * For left(), we just skip to the start of the relevant row, grab the elements before
+     * the starting point, and reverse the result.
+     *
+     * For right(), we skip until just after the starting point and grab the rest of the row.
+     */
+    fn left(&self, row: usize, col: usize) -> impl Iterator<Item = &u32> + '_ {
+        self.values.iter().skip(self.width * row).take(col).rev()
+    }
+
+    fn right(&self, row: usize, col: usize) -> impl Iterator<Item = &u32> + '_ {
+        let start_idx = self.width * row + col;
+        self.values
+            .iter()
+            .skip(start_idx + 1)
+            .take(self.width - col - 1)
+    }
+
+    /**
+     * A tree is visible from a direction iff every
+     * tree in that direction is smaller than it.
+     */
+    fn is_visible(&self, row: usize, col: usize) -> bool {
+        let current_height = self.at(row, col);
+
+        self.left(row, col).all(|h| *h < current_height)
+            || self.right(row, col).all(|h| *h < current_height)
+            || self.above(row, col).all(|h| *h < current_height)
+            || self.below(row, col).all(|h| *h < current_height)
+    }
+
+    fn scenic_score(&self, row: usize, col: usize) -> usize {
+        let initial = self.at(row, col);
+
+        // Count trees in each direction until you find either the edge or a larger one.
+        // take_until() includes the first non-matching element, unlike take_while().
+        let left = self.left(row, col).take_until(|h| **h >= initial).count();
+        let right = self.right(row, col).take_until(|h| **h >= initial).count();
+        let above = self.above(row, col).take_until(|h| **h >= initial).count();
+        let below = self.below(row, col).take_until(|h| **h >= initial).count();
+
+        left * right * above * below
+    }
+
+    /**
+     * Calls f(row, col) for every tree, and collects the results into a grid.
+     */
+    fn map<T>(&self, f: impl Fn(usize, usize) -> T) -> Vec<Vec<T>> {
+        (0..self.height)
+            .map(|row| (0..self.width).map(|col| f(row, col)).collect())
+            .collect()
+    }
+}
+
+/**
+ * Whether each tree is visible from outside the grid, indexed by [row][col].
+ */
+pub fn visibility_grid(input: &TreeGrid) -> Vec<Vec<bool>> {
+    input.map(|row, col| input.is_visible(row, col))
+}
+
+/**
+ * The scenic score of each tree, indexed by [row][col].
+ */
+pub fn scenic_scores(input: &TreeGrid) -> Vec<Vec<usize>> {
+    input.map(|row, col| input.scenic_score(row, col))
+}
+
+/**
+ * Which of the grids to draw as a heatmap.
+ */
+#[derive(Clone, Copy, Debug)]
+pub enum Layer {
+    Visibility,
+    ScenicScore,
+}
+
+fn layer_values(input: &TreeGrid, layer: Layer) -> Vec<Vec<usize>> {
+    match layer {
+        Layer::Visibility => input.map(|row, col| input.is_visible(row, col) as usize),
+        Layer::ScenicScore => scenic_scores(input),
+    }
+}
+
+/**
+ * Draws a layer as text, one character per tree (see utils::heatmap).
+ * Visible trees are '@' and hidden ones are blank.
+ */
+pub fn render_ascii(input: &TreeGrid, layer: Layer) -> String {
+    heatmap::ascii(&layer_values(input, layer))
+}
+
+pub fn render_svg(input: &TreeGrid, layer: Layer, cell_size: usize) -> String {
+    heatmap::svg(&layer_values(input, layer), cell_size)
+}
+
+/**
+ * Parses the grid of tree heights. Every row has to be the same length
+ * as the first, and contain only digits.
+ */
+#[aoc_generator(day8)]
+pub fn generator(input: &str) -> Result<TreeGrid, AocError> {
+    let input = normalize(input);
+    let mut values = Vec::new();
+
+    let mut width = 0;
+    let mut height = 0;
+    for (row, line) in input.lines().enumerate() {
+        if row == 0 {
+            width = line.len();
+        } else if line.len() != width {
+            return Err(AocError::parse(
+                row + 1,
+                format!("expected {width} trees but found {}", line.len()),
+            ));
+        }
+
+        for (col, c) in line.chars().enumerate() {
+            let tree = c.to_digit(10).ok_or_else(|| {
+                AocError::parse(
+                    row + 1,
+                    format!("bad tree height {c:?} in column {}", col + 1),
+                )
+            })?;
+            values.push(tree);
+        }
+        height += 1;
+    }
+
+    Ok(TreeGrid {
+        height,
+        width,
+        values,
+    })
+}
+
+#[aoc(day8, part1)]
+pub fn part1(input: &TreeGrid) -> usize {
+    let mut visible = 0;
+    for row in 0..input.height {
+        for col in 0..input.width {
+            if input.is_visible(row, col) {
+                visible += 1;
+            }
+        }
+    }
+
+    visible
+}
+
+#[aoc(day8, part2)]
+pub fn part2(input: &TreeGrid) -> usize {
+    let mut best = 0;
+    for row in 0..input.height {
+        for col in 0..input.width {
+            best = std::cmp::max(best, input.scenic_score(row, col));
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "30373\n\
+                           25512\n\
+                           65332\n\
+                           33549\n\
+                           35390\n";
+
+    #[test]
+    fn test_part1() {
+        let input = generator(EXAMPLE).unwrap();
+        assert_eq!(part1(&input), 21);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = generator(EXAMPLE).unwrap();
+        assert_eq!(part2(&input), 8);
+    }
+
+    #[test]
+    fn test_grids() {
+        let input = generator(EXAMPLE).unwrap();
+
+        let visible = visibility_grid(&input);
+        assert_eq!(visible.iter().flatten().filter(|v| **v).count(), 21);
+        assert!(!visible[2][2]);
+
+        let scores = scenic_scores(&input);
+        assert_eq!(scores[3][2], 8);
+        assert_eq!(scores[1][2], 4);
+
+        assert_eq!(
+            render_ascii(&input, Layer::Visibility),
+            "@@@@@\n@@@ @\n@@ @@\n@ @ @\n@@@@@\n"
+        );
+        assert!(render_svg(&input, Layer::ScenicScore, 8).contains(r#"fill-opacity="1.00""#));
+    }
+
+    #[test]
+    fn test_crlf_input() {
+        let input = generator(&EXAMPLE.replace('\n', "\r\n")).unwrap();
+        assert_eq!(part1(&input), 21);
+
+        let input = generator(EXAMPLE.trim_end()).unwrap();
+        assert_eq!(part2(&input), 8);
+    }
+
+    #[test]
+    fn test_ragged_input() {
+        assert_eq!(
+            generator("303\n2551\n653\n").err(),
+            Some(AocError::parse(2, "expected 3 trees but found 4"))
+        );
+        assert_eq!(
+            generator("303\n255\n65\n").err(),
+            Some(AocError::parse(3, "expected 3 trees but found 2"))
+        );
+        assert_eq!(
+            generator("303\n2x5\n").err(),
+            Some(AocError::parse(2, "bad tree height 'x' in column 2"))
+        );
+
+        // Without a trailing newline, the last row still counts.
+        let input = generator("30373\n25512").unwrap();
+        assert_eq!((input.width, input.height), (5, 2));
+        assert_eq!(part1(&input), 10);
+    }
+}
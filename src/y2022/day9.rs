@@ -0,0 +1,395 @@
+use std::collections::HashSet;
+
+use crate::utils::input::normalize;
+
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn from_str(input: &str) -> Self {
+        match input {
+            "U" => Direction::Up,
+            "D" => Direction::Down,
+            "L" => Direction::Left,
+            "R" => Direction::Right,
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[derive(Hash, Eq, PartialEq, Clone, Copy, Debug)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    fn translate(&mut self, direction: &Direction) {
+        match *direction {
+            Direction::Up => self.y += 1,
+            Direction::Down => self.y -= 1,
+            Direction::Left => self.x -= 1,
+            Direction::Right => self.x += 1,
+        }
+    }
+
+    fn move_towards(&mut self, other: Point) {
+        let dx = other.x - self.x;
+        let dy = other.y - self.y;
+
+        // If directly adjacent, don't move.
+        if dx.abs() <= 1 && dy.abs() <= 1 {
+            return;
+        }
+
+        // Otherwise move 0 or 1 units horizontally and
+        // 0 or 1 units vertically towards `other`.
+        self.x += dx.signum();
+        self.y += dy.signum();
+    }
+}
+
+struct Rope<const N: usize> {
+    knots: [Point; N],
+}
+
+impl<const N: usize> Rope<N> {
+    fn new() -> Self {
+        Rope {
+            knots: [Point { x: 0, y: 0 }; N],
+        }
+    }
+
+    fn pull(&mut self, direction: &Direction) {
+        pull(&mut self.knots, direction);
+    }
+}
+
+/*
+ * Moves the head of a rope one step, and the rest of the knots after it.
+ */
+fn pull(knots: &mut [Point], direction: &Direction) {
+    // Move the head of the rope
+    knots[0].translate(direction);
+
+    // Move each other knot in turn
+    for knot in 1..knots.len() {
+        knots[knot].move_towards(knots[knot - 1]);
+    }
+}
+
+/**
+ * A set of visited points, stored as a dense bitmap over a rectangle of the
+ * grid. The rope's movements stay within a few hundred cells of the origin,
+ * so this is much smaller (and faster) than hashing every position.
+ *
+ * The rectangle starts out small around the origin and grows, at least
+ * doubling in each direction it needs to, whenever a point falls outside it.
+ */
+struct VisitedGrid {
+    // The point stored in bit 0.
+    origin: Point,
+    width: usize,
+    height: usize,
+    bits: Vec<u64>,
+    count: usize,
+}
+
+impl VisitedGrid {
+    fn new() -> Self {
+        Self::with_bounds(Point { x: -32, y: -32 }, 64, 64)
+    }
+
+    fn with_bounds(origin: Point, width: usize, height: usize) -> Self {
+        VisitedGrid {
+            origin,
+            width,
+            height,
+            bits: vec![0; (width * height).div_ceil(64)],
+            count: 0,
+        }
+    }
+
+    fn index(&self, point: Point) -> Option<usize> {
+        let x = usize::try_from(point.x - self.origin.x).ok()?;
+        let y = usize::try_from(point.y - self.origin.y).ok()?;
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    fn insert(&mut self, point: Point) {
+        let index = match self.index(point) {
+            Some(index) => index,
+            None => {
+                self.grow_to_include(point);
+                self.index(point).unwrap()
+            }
+        };
+
+        let mask = 1 << (index % 64);
+        let word = &mut self.bits[index / 64];
+        if *word & mask == 0 {
+            *word |= mask;
+            self.count += 1;
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.count
+    }
+
+    fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.width * self.height)
+            .filter(|index| self.bits[index / 64] & (1 << (index % 64)) != 0)
+            .map(|index| Point {
+                x: self.origin.x + (index % self.width) as i32,
+                y: self.origin.y + (index / self.width) as i32,
+            })
+    }
+
+    fn grow_to_include(&mut self, point: Point) {
+        let (width, height) = (self.width as i32, self.height as i32);
+        let mut min = self.origin;
+        let mut max = Point {
+            x: min.x + width - 1,
+            y: min.y + height - 1,
+        };
+
+        // Extend past the point by the current size, so that the grid at
+        // least doubles and repeated growth is amortized.
+        if point.x < min.x {
+            min.x = point.x - width;
+        } else if point.x > max.x {
+            max.x = point.x + width;
+        }
+        if point.y < min.y {
+            min.y = point.y - height;
+        } else if point.y > max.y {
+            max.y = point.y + height;
+        }
+
+        let mut grown = Self::with_bounds(
+            min,
+            (max.x - min.x + 1) as usize,
+            (max.y - min.y + 1) as usize,
+        );
+        for old_point in self.points() {
+            grown.insert(old_point);
+        }
+        *self = grown;
+    }
+}
+
+pub struct Step {
+    direction: Direction,
+    count: usize,
+}
+
+#[aoc_generator(day9)]
+pub fn generator(input: &str) -> Vec<Step> {
+    let input = normalize(input);
+    input
+        .lines()
+        .map(|line| {
+            let (direction_str, count_str) = line.split_once(' ').unwrap();
+            let direction = Direction::from_str(direction_str);
+            let count = count_str.parse::<usize>().unwrap();
+
+            Step { direction, count }
+        })
+        .collect()
+}
+
+/**
+ * Runs the rope through every step, calling `visit` with the position of
+ * the tail after each move.
+ */
+fn simulate<const N: usize>(steps: &[Step], mut visit: impl FnMut(Point)) {
+    let mut rope = Rope::<N>::new();
+
+    for step in steps {
+        for _ in 0..step.count {
+            rope.pull(&step.direction);
+            visit(rope.knots[N - 1]);
+        }
+    }
+}
+
+/**
+ * Every position of a rope with any number of knots, one after each unit
+ * move of the head (so a step of "R 4" gives four). The head is first.
+ *
+ * This is for working out other things about the rope than where its tail
+ * has been, like how far apart its ends get; the parts use the faster
+ * fixed-size rope.
+ */
+pub struct RopeSimulator<'a> {
+    steps: &'a [Step],
+    knots: Vec<Point>,
+    // How far into `steps` we are, and how many moves are left in that step.
+    step: usize,
+    moves_left: usize,
+}
+
+impl<'a> RopeSimulator<'a> {
+    /**
+     * Panics if `knots` is 0: there has to be a head to pull.
+     */
+    pub fn new(steps: &'a [Step], knots: usize) -> Self {
+        assert!(knots > 0, "a rope needs at least one knot");
+        RopeSimulator {
+            steps,
+            knots: vec![Point { x: 0, y: 0 }; knots],
+            step: 0,
+            moves_left: steps.first().map_or(0, |step| step.count),
+        }
+    }
+}
+
+impl Iterator for RopeSimulator<'_> {
+    type Item = Vec<Point>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.moves_left == 0 {
+            self.step += 1;
+            self.moves_left = self.steps.get(self.step)?.count;
+        }
+
+        self.moves_left -= 1;
+        pull(&mut self.knots, &self.steps[self.step].direction);
+        Some(self.knots.clone())
+    }
+}
+
+#[aoc(day9, part1)]
+pub fn part1(input: &[Step]) -> usize {
+    let mut tail_positions: HashSet<Point> = HashSet::new();
+    simulate::<2>(input, |tail| {
+        tail_positions.insert(tail);
+    });
+    tail_positions.len()
+}
+
+#[aoc(day9, part2)]
+pub fn part2(input: &[Step]) -> usize {
+    let mut tail_positions: HashSet<Point> = HashSet::new();
+    simulate::<10>(input, |tail| {
+        tail_positions.insert(tail);
+    });
+    tail_positions.len()
+}
+
+/*
+ * The same solutions with a VisitedGrid instead of a HashSet, so that
+ * `cargo aoc bench -d 9` can compare the two.
+ */
+#[aoc(day9, part1, Bitmap)]
+pub fn part1_bitmap(input: &[Step]) -> usize {
+    let mut tail_positions = VisitedGrid::new();
+    simulate::<2>(input, |tail| tail_positions.insert(tail));
+    tail_positions.len()
+}
+
+#[aoc(day9, part2, Bitmap)]
+pub fn part2_bitmap(input: &[Step]) -> usize {
+    let mut tail_positions = VisitedGrid::new();
+    simulate::<10>(input, |tail| tail_positions.insert(tail));
+    tail_positions.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALL_EXAMPLE: &str = "R 4\n\
+                                 U 4\n\
+                                 L 3\n\
+                                 D 1\n\
+                                 R 4\n\
+                                 D 1\n\
+                                 L 5\n\
+                                 R 2";
+
+    const LARGE_EXAMPLE: &str = "R 5\n\
+                                 U 8\n\
+                                 L 8\n\
+                                 D 3\n\
+                                 R 17\n\
+                                 D 10\n\
+                                 L 25\n\
+                                 U 20";
+
+    #[test]
+    fn test_part1() {
+        let small_input = generator(SMALL_EXAMPLE);
+        assert_eq!(part1(&small_input), 13);
+    }
+
+    #[test]
+    fn test_part2() {
+        let small_input = generator(SMALL_EXAMPLE);
+        assert_eq!(part2(&small_input), 1);
+
+        let large_input = generator(LARGE_EXAMPLE);
+        assert_eq!(part2(&large_input), 36);
+    }
+
+    #[test]
+    fn test_bitmap() {
+        let small_input = generator(SMALL_EXAMPLE);
+        assert_eq!(part1_bitmap(&small_input), 13);
+
+        let large_input = generator(LARGE_EXAMPLE);
+        assert_eq!(part2_bitmap(&large_input), 36);
+    }
+
+    #[test]
+    fn test_visited_grid_growth() {
+        let mut grid = VisitedGrid::new();
+        let points = [(0, 0), (-100, 3), (250, -90), (0, 0), (31, 31), (-33, 500)];
+        for (x, y) in points {
+            grid.insert(Point { x, y });
+        }
+
+        assert_eq!(grid.len(), 5);
+        let mut stored: Vec<(i32, i32)> = grid.points().map(|p| (p.x, p.y)).collect();
+        stored.sort();
+        assert_eq!(
+            stored,
+            vec![(-100, 3), (-33, 500), (0, 0), (31, 31), (250, -90)]
+        );
+    }
+
+    #[test]
+    fn test_rope_simulator() {
+        let large_input = generator(LARGE_EXAMPLE);
+        let states: Vec<Vec<Point>> = RopeSimulator::new(&large_input, 10).collect();
+
+        // One state per unit move, matching the fixed-size rope.
+        assert_eq!(states.len(), 5 + 8 + 8 + 3 + 17 + 10 + 25 + 20);
+        let tails: HashSet<Point> = states.iter().map(|knots| knots[9]).collect();
+        assert_eq!(tails.len(), 36);
+        assert_eq!(states[4][0], Point { x: 5, y: 0 });
+
+        // Something the parts don't compute: the furthest the head gets
+        // from the tail (in moves), which can't be more than one per knot
+        // in between.
+        let furthest = states
+            .iter()
+            .map(|knots| {
+                let (head, tail) = (knots[0], knots[9]);
+                (head.x - tail.x).abs().max((head.y - tail.y).abs())
+            })
+            .max();
+        assert_eq!(furthest, Some(9));
+
+        assert_eq!(RopeSimulator::new(&[], 2).next(), None);
+    }
+}
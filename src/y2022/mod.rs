@@ -0,0 +1,50 @@
+/*
+ * The 2022 solutions. Later years get a y<year> module of their own from
+ * new-day.
+ */
+#[cfg(feature = "std")]
+pub mod day1;
+#[cfg(feature = "std")]
+pub mod day10;
+#[cfg(feature = "std")]
+pub mod day11;
+#[cfg(feature = "std")]
+pub mod day12;
+#[cfg(feature = "std")]
+pub mod day13;
+#[cfg(feature = "std")]
+pub mod day14;
+#[cfg(feature = "std")]
+pub mod day15;
+#[cfg(feature = "std")]
+pub mod day16;
+#[cfg(feature = "std")]
+pub mod day17;
+#[cfg(feature = "std")]
+pub mod day18;
+#[cfg(feature = "std")]
+pub mod day19;
+pub mod day2;
+#[cfg(feature = "std")]
+pub mod day20;
+#[cfg(feature = "std")]
+pub mod day21;
+#[cfg(feature = "std")]
+pub mod day22;
+#[cfg(feature = "std")]
+pub mod day23;
+#[cfg(feature = "std")]
+pub mod day24;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+#[cfg(feature = "std")]
+pub mod day5;
+#[cfg(feature = "std")]
+pub mod day6;
+#[cfg(feature = "std")]
+pub mod day7;
+#[cfg(feature = "std")]
+pub mod day8;
+#[cfg(feature = "std")]
+pub mod day9;